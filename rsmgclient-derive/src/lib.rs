@@ -0,0 +1,286 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Derive macros of the `GraphNode` and `GraphRelationship` traits of
+//! [rsmgclient](https://docs.rs/rsmgclient), enabled by its `derive` feature.
+//!
+//! Supported attributes:
+//!
+//! - `#[graph(label = "...")]` on a struct deriving `GraphNode` sets the label of the nodes,
+//!   the name of the struct is used by default.
+//! - `#[graph(type = "...")]` on a struct deriving `GraphRelationship` sets the type of the
+//!   relationships, the name of the struct in upper snake case is used by default.
+//! - `#[graph(id)]` on a field marks the property identifying nodes, field named `id` is used by
+//!   default.
+//! - `#[graph(rename = "...")]` on a field sets the property name, the field name is used by
+//!   default.
+//! - `#[graph(skip)]` on a field excludes it from the mapping, the field is initialized using
+//!   `Default` when converting from a node or relationship.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Ident, LitStr};
+
+#[proc_macro_derive(GraphNode, attributes(graph))]
+pub fn derive_graph_node(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input, Kind::Node)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(GraphRelationship, attributes(graph))]
+pub fn derive_graph_relationship(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input, Kind::Relationship)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum Kind {
+    Node,
+    Relationship,
+}
+
+struct Field {
+    ident: Ident,
+    property: String,
+    id: bool,
+    skip: bool,
+}
+
+fn expand(input: &DeriveInput, kind: Kind) -> Result<TokenStream2, Error> {
+    let name = &input.ident;
+    let mut label = None;
+    for attr in input.attrs.iter().filter(|x| x.path().is_ident("graph")) {
+        attr.parse_nested_meta(|meta| {
+            let key = match kind {
+                Kind::Node => "label",
+                Kind::Relationship => "type",
+            };
+            if meta.path.is_ident(key) {
+                let value: LitStr = meta.value()?.parse()?;
+                check_identifier(&value.value(), value.span())?;
+                label = Some(value.value());
+                Ok(())
+            } else {
+                Err(meta.error(format!("expected `{}`", key)))
+            }
+        })?;
+    }
+    let label = label.unwrap_or_else(|| match kind {
+        Kind::Node => name.to_string(),
+        Kind::Relationship => upper_snake_case(&name.to_string()),
+    });
+
+    let fields = parse_fields(input, kind)?;
+    let mapped: Vec<&Field> = fields.iter().filter(|x| !x.skip).collect();
+    let properties: Vec<&str> = mapped.iter().map(|x| x.property.as_str()).collect();
+    let idents: Vec<&Ident> = mapped.iter().map(|x| &x.ident).collect();
+
+    let initializers = fields.iter().map(|field| {
+        let ident = &field.ident;
+        let property = &field.property;
+        match field.skip {
+            true => quote! { #ident: ::std::default::Default::default() },
+            false => quote! { #ident: ::rsmgclient::take_property(&mut properties, #property)? },
+        }
+    });
+    let to_query_params = quote! {
+        fn to_query_params(
+            &self,
+        ) -> ::std::collections::HashMap<::std::string::String, ::rsmgclient::QueryParam> {
+            let mut params = ::std::collections::HashMap::new();
+            #(
+                params.insert(
+                    ::std::string::String::from(#properties),
+                    ::rsmgclient::QueryParam::from(::std::clone::Clone::clone(&self.#idents)),
+                );
+            )*
+            params
+        }
+    };
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(match kind {
+        Kind::Node => {
+            let id = match mapped.iter().find(|x| x.id) {
+                Some(x) => x.property.clone(),
+                None => match mapped.iter().find(|x| x.ident == "id") {
+                    Some(x) => x.property.clone(),
+                    None => {
+                        return Err(Error::new_spanned(
+                            name,
+                            "GraphNode requires an id property, mark a field with #[graph(id)]",
+                        ))
+                    }
+                },
+            };
+            quote! {
+                impl #impl_generics ::rsmgclient::GraphNode for #name #ty_generics #where_clause {
+                    const LABEL: &'static str = #label;
+                    const ID_PROPERTY: &'static str = #id;
+                    const PROPERTIES: &'static [&'static str] = &[#(#properties),*];
+
+                    #to_query_params
+
+                    fn from_node(
+                        node: ::rsmgclient::Node,
+                    ) -> ::std::result::Result<Self, ::rsmgclient::MgError> {
+                        if !node.labels.iter().any(|x| x == #label) {
+                            return ::std::result::Result::Err(::rsmgclient::MgError::new(
+                                ::std::format!("Node doesn't have label {}", #label),
+                            ));
+                        }
+                        let mut properties = node.properties;
+                        ::std::result::Result::Ok(#name { #(#initializers),* })
+                    }
+                }
+            }
+        }
+        Kind::Relationship => {
+            if let Some(field) = mapped.iter().find(|x| x.id) {
+                return Err(Error::new_spanned(
+                    &field.ident,
+                    "#[graph(id)] is only supported by GraphNode",
+                ));
+            }
+            quote! {
+                impl #impl_generics ::rsmgclient::GraphRelationship
+                    for #name #ty_generics #where_clause
+                {
+                    const TYPE: &'static str = #label;
+                    const PROPERTIES: &'static [&'static str] = &[#(#properties),*];
+
+                    #to_query_params
+
+                    fn from_relationship(
+                        relationship: ::rsmgclient::Relationship,
+                    ) -> ::std::result::Result<Self, ::rsmgclient::MgError> {
+                        if relationship.type_ != #label {
+                            return ::std::result::Result::Err(::rsmgclient::MgError::new(
+                                ::std::format!("Relationship doesn't have type {}", #label),
+                            ));
+                        }
+                        let mut properties = relationship.properties;
+                        ::std::result::Result::Ok(#name { #(#initializers),* })
+                    }
+                }
+            }
+        }
+    })
+}
+
+fn parse_fields(input: &DeriveInput, kind: Kind) -> Result<Vec<Field>, Error> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &input.ident,
+                    "only structs with named fields are supported",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "only structs with named fields are supported",
+            ))
+        }
+    };
+
+    let mut result = Vec::new();
+    for field in fields {
+        let ident = field.ident.clone().expect("named field");
+        let mut property = ident.to_string().trim_start_matches("r#").to_string();
+        let mut property_span = ident.span();
+        let mut id = false;
+        let mut skip = false;
+        for attr in field.attrs.iter().filter(|x| x.path().is_ident("graph")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("id") {
+                    id = true;
+                    Ok(())
+                } else if meta.path.is_ident("skip") {
+                    skip = true;
+                    Ok(())
+                } else if meta.path.is_ident("rename") {
+                    let value: LitStr = meta.value()?.parse()?;
+                    property = value.value();
+                    property_span = value.span();
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `id`, `rename` or `skip`"))
+                }
+            })?;
+        }
+        if id && skip {
+            return Err(Error::new_spanned(&ident, "id property can't be skipped"));
+        }
+        if !skip {
+            check_parameter_name(&property, property_span, kind)?;
+        }
+        result.push(Field {
+            ident,
+            property,
+            id,
+            skip,
+        });
+    }
+    Ok(result)
+}
+
+fn check_identifier(identifier: &str, span: Span) -> Result<(), Error> {
+    if identifier.is_empty() || identifier.contains('\0') {
+        return Err(Error::new(
+            span,
+            "identifier must be non-empty and can't contain null characters",
+        ));
+    }
+    Ok(())
+}
+
+/// Property names are also used as query parameter names and have to be plain identifiers.
+fn check_parameter_name(name: &str, span: Span, kind: Kind) -> Result<(), Error> {
+    let mut chars = name.chars();
+    let valid = matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        return Err(Error::new(
+            span,
+            "property name must consist of ASCII letters, digits and underscores",
+        ));
+    }
+    if kind == Kind::Relationship && (name == "start_id" || name == "end_id") {
+        return Err(Error::new(
+            span,
+            "property names `start_id` and `end_id` are reserved for relationship endpoints",
+        ));
+    }
+    Ok(())
+}
+
+fn upper_snake_case(name: &str) -> String {
+    let mut result = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            result.push('_');
+        }
+        result.extend(c.to_uppercase());
+    }
+    result
+}
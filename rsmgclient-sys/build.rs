@@ -133,7 +133,24 @@ fn build_mgclient_macos() -> PathBuf {
 }
 
 fn build_mgclient_linux() -> PathBuf {
-    Config::new("mgclient").build()
+    let mut config = Config::new("mgclient");
+    if cfg!(feature = "static-ssl") {
+        // Makes CMake's FindOpenSSL pick libssl.a and libcrypto.a, e.g. from an OpenSSL built
+        // with musl-gcc and installed into OPENSSL_DIR.
+        println!("cargo:rerun-if-env-changed=OPENSSL_DIR");
+        config.define("OPENSSL_USE_STATIC_LIBS", "TRUE");
+        if let Ok(openssl_dir) = env::var("OPENSSL_DIR") {
+            let openssl_dir = PathBuf::from(openssl_dir);
+            config.define("OPENSSL_ROOT_DIR", format!("{}", openssl_dir.display()));
+            for lib_dir in ["lib", "lib64"] {
+                println!(
+                    "cargo:rustc-link-search=native={}",
+                    openssl_dir.join(lib_dir).display()
+                );
+            }
+        }
+    }
+    config.build()
 }
 
 fn build_mgclient_windows() -> PathBuf {
@@ -147,6 +164,93 @@ fn build_mgclient_windows() -> PathBuf {
         .build()
 }
 
+/// Looks for an installed mgclient, first in `MGCLIENT_LIB_DIR` and then using pkg-config.
+/// Returns the directory containing its headers, or `None` if the bundled one should be built.
+#[cfg(feature = "system-mgclient")]
+fn find_system_mgclient(host_type: &HostType) -> Option<PathBuf> {
+    println!("cargo:rerun-if-env-changed=MGCLIENT_LIB_DIR");
+    println!("cargo:rerun-if-env-changed=MGCLIENT_INCLUDE_DIR");
+    if let Ok(lib_dir) = env::var("MGCLIENT_LIB_DIR") {
+        let lib_dir = PathBuf::from(lib_dir);
+        let include_dir = match env::var("MGCLIENT_INCLUDE_DIR") {
+            Ok(x) => PathBuf::from(x),
+            Err(_) => lib_dir.join("..").join("include"),
+        };
+        println!("cargo:rustc-link-search=native={}", lib_dir.display());
+        println!("cargo:rustc-link-lib=mgclient");
+        // Unlike pkg-config, the directory doesn't describe dependencies of mgclient, which are
+        // needed if it is a static library.
+        link_openssl(host_type);
+        return Some(include_dir);
+    }
+    match pkg_config::Config::new().probe("mgclient") {
+        Ok(library) => {
+            let include_dir = match env::var("MGCLIENT_INCLUDE_DIR") {
+                Ok(x) => PathBuf::from(x),
+                Err(_) => library.include_paths.first().cloned()?,
+            };
+            Some(include_dir)
+        }
+        Err(err) => {
+            println!(
+                "cargo:warning=Installed mgclient not found ({}), building the bundled one.",
+                err
+            );
+            None
+        }
+    }
+}
+
+/// Links OpenSSL libraries mgclient depends on.
+fn link_openssl(host_type: &HostType) {
+    match host_type {
+        HostType::Linux => {
+            if cfg!(feature = "static-ssl") {
+                // Static libraries are linked in order, libssl depends on libcrypto.
+                println!("cargo:rustc-link-lib=static=ssl");
+                println!("cargo:rustc-link-lib=static=crypto");
+            } else {
+                println!("cargo:rustc-link-lib=dylib=crypto");
+                println!("cargo:rustc-link-lib=dylib=ssl");
+            }
+        }
+        HostType::Windows => {
+            println!("cargo:rustc-link-lib=dylib=libcrypto");
+            println!("cargo:rustc-link-lib=dylib=libssl");
+        }
+        HostType::MacOS => {
+            println!("cargo:rustc-link-lib=dylib=crypto");
+            println!("cargo:rustc-link-lib=dylib=ssl");
+        }
+        HostType::Unknown => panic!("Unknown operating system"),
+    }
+}
+
+fn generate_bindings(include_dir: &Path, mgclient: &Path) {
+    let mgclient_h = include_dir.join("mgclient.h");
+    let mgclient_export_h = include_dir.join("mgclient-export.h");
+    println!("cargo:rerun-if-changed={}", mgclient_h.display());
+    println!("cargo:rerun-if-changed={}", mgclient_export_h.display());
+    let mut builder = bindgen::Builder::default()
+        .header(format!("{}", mgclient_h.display()))
+        .header(format!("{}", mgclient_export_h.display()));
+    // Required because of tests that rely on the C struct fields. Installed mgclient doesn't
+    // ship it, the bundled sources are used if they are available.
+    let mgclient_mgvalue_h = mgclient.join("src").join("mgvalue.h");
+    if mgclient_mgvalue_h.exists() {
+        builder = builder.header(format!("{}", mgclient_mgvalue_h.display()));
+    }
+    let bindings = builder
+        .clang_arg(format!("-I{}", include_dir.display()))
+        .parse_callbacks(Box::new(bindgen::CargoCallbacks))
+        .generate()
+        .expect("Unable to generate bindings");
+    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap());
+    bindings
+        .write_to_file(out_path.join("bindings.rs"))
+        .expect("Couldn't write bindings!");
+}
+
 fn main() {
     let host_type = if cfg!(target_os = "linux") {
         HostType::Linux
@@ -159,6 +263,15 @@ fn main() {
     };
 
     let mgclient = PathBuf::new().join("mgclient");
+    #[cfg(feature = "system-mgclient")]
+    if let Some(include_dir) = find_system_mgclient(&host_type) {
+        generate_bindings(&include_dir, &mgclient);
+        return;
+    }
+
+    if cfg!(feature = "static-ssl") && host_type != HostType::Linux {
+        println!("cargo:warning=The static-ssl feature is only supported on Linux, ignoring it.");
+    }
     let mgclient_out = match host_type {
         HostType::Windows => build_mgclient_windows(),
         HostType::MacOS => build_mgclient_macos(),
@@ -166,24 +279,7 @@ fn main() {
         HostType::Unknown => panic!("Unknown operating system"),
     };
 
-    let mgclient_h = mgclient_out.join("include").join("mgclient.h");
-    let mgclient_export_h = mgclient_out.join("include").join("mgclient-export.h");
-    // Required because of tests that rely on the C struct fields.
-    let mgclient_mgvalue_h = mgclient.join("src").join("mgvalue.h");
-    println!("cargo:rerun-if-changed={}", mgclient_h.display());
-    println!("cargo:rerun-if-changed={}", mgclient_export_h.display());
-    let bindings = bindgen::Builder::default()
-        .header(format!("{}", mgclient_h.display()))
-        .header(format!("{}", mgclient_export_h.display()))
-        .header(format!("{}", mgclient_mgvalue_h.display()))
-        .clang_arg(format!("-I{}", mgclient_out.join("include").display()))
-        .parse_callbacks(Box::new(bindgen::CargoCallbacks))
-        .generate()
-        .expect("Unable to generate bindings");
-    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap());
-    bindings
-        .write_to_file(out_path.join("bindings.rs"))
-        .expect("Couldn't write bindings!");
+    generate_bindings(&mgclient_out.join("include"), &mgclient);
 
     let lib_dir = if Path::new(&mgclient_out.join("lib64")).exists() {
         "lib64"
@@ -197,19 +293,5 @@ fn main() {
     println!("cargo:rustc-link-lib=static=mgclient");
     // If the following part of the code is pushed inside build_mgclient_xzy, linking is not done
     // properly.
-    match host_type {
-        HostType::Linux => {
-            println!("cargo:rustc-link-lib=dylib=crypto");
-            println!("cargo:rustc-link-lib=dylib=ssl");
-        }
-        HostType::Windows => {
-            println!("cargo:rustc-link-lib=dylib=libcrypto");
-            println!("cargo:rustc-link-lib=dylib=libssl");
-        }
-        HostType::MacOS => {
-            println!("cargo:rustc-link-lib=dylib=crypto");
-            println!("cargo:rustc-link-lib=dylib=ssl");
-        }
-        HostType::Unknown => panic!("Unknown operating system"),
-    }
+    link_openssl(&host_type);
 }
@@ -0,0 +1,31 @@
+// Copyright (c) 2016-2020 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Raw FFI bindings to [mgclient](https://github.com/memgraph/mgclient), the C client library
+//! for Memgraph.
+//!
+//! By default the library is built from the bundled sources and linked statically, while
+//! OpenSSL is linked dynamically. The `static-ssl` feature links OpenSSL statically on Linux and
+//! the `system-mgclient` feature links an installed mgclient instead, found in `MGCLIENT_LIB_DIR`
+//! or using pkg-config. For a safe interface use the [rsmgclient](https://docs.rs/rsmgclient)
+//! crate.
+
+#![allow(
+    dead_code,
+    non_upper_case_globals,
+    non_camel_case_types,
+    non_snake_case,
+    deref_nullptr
+)]
+include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
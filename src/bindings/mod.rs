@@ -12,11 +12,4 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-#![allow(
-    dead_code,
-    non_upper_case_globals,
-    non_camel_case_types,
-    non_snake_case,
-    deref_nullptr
-)]
-include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+pub use rsmgclient_sys::*;
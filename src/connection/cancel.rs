@@ -0,0 +1,201 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{ConnectParams, Connection, TransactionInfo};
+use crate::error::MgError;
+use crate::value::{QueryParam, Value};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Key of the `tx_metadata` entry identifying transactions of a connection with a cancellation
+/// handle.
+pub(crate) const CANCELLATION_TOKEN_KEY: &str = "rsmgclient_cancellation_token";
+
+/// Handle cancelling queries of a `Connection` from another thread, see
+/// `Connection::cancellation_handle`.
+///
+/// The query is cancelled by terminating its transaction using `TERMINATE TRANSACTIONS` on a
+/// separate connection opened with the same parameters, the user therefore needs the privilege
+/// to manage transactions. Transactions are found by a token unique to the connection, which is
+/// sent in `tx_metadata` of every transaction started after the handle is created. The cancelled
+/// `execute` or fetch returns an error for which `MgError::is_cancelled` is true.
+#[derive(Clone)]
+pub struct CancellationHandle {
+    params: ConnectParams,
+    state: Arc<Mutex<CancellationState>>,
+}
+
+pub(super) struct CancellationState {
+    token: String,
+    query: Option<String>,
+    cancelled: bool,
+}
+
+impl CancellationState {
+    fn new() -> CancellationState {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|x| x.as_nanos())
+            .unwrap_or_default();
+        CancellationState {
+            token: format!(
+                "{}-{}-{}",
+                std::process::id(),
+                nanos,
+                COUNTER.fetch_add(1, Ordering::Relaxed)
+            ),
+            query: None,
+            cancelled: false,
+        }
+    }
+}
+
+fn lock(state: &Mutex<CancellationState>) -> MutexGuard<'_, CancellationState> {
+    match state.lock() {
+        Ok(x) => x,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+impl CancellationHandle {
+    /// Cancels the query the connection is executing or fetching results of.
+    ///
+    /// Returns whether a query was cancelled, `false` if the connection wasn't running a query or
+    /// its transaction was not found on the server, e.g. because the query has just finished or
+    /// its transaction started before the handle was created.
+    pub fn cancel(&self) -> Result<bool, MgError> {
+        let token = {
+            let state = lock(&self.state);
+            match state.query {
+                Some(_) => state.token.clone(),
+                None => return Ok(false),
+            }
+        };
+
+        let mut params = self.params.clone();
+        params.lazy = false;
+        params.autocommit = true;
+        let mut connection = Connection::connect(&params)?;
+        let transactions = connection.show_transactions()?;
+        let ids = transactions_to_terminate(&transactions, &token);
+        if ids.is_empty() {
+            return Ok(false);
+        }
+
+        // The flag needs to be set before terminating, the cancelled query may fail right away.
+        lock(&self.state).cancelled = true;
+        let terminated = connection.terminate_transactions(&ids);
+        let cancelled = matches!(&terminated, Ok(x) if !x.is_empty());
+        if !cancelled {
+            lock(&self.state).cancelled = false;
+        }
+        terminated.map(|_| cancelled)
+    }
+}
+
+/// Returns ids of transactions whose metadata contains the cancellation `token`.
+pub(crate) fn transactions_to_terminate<'a>(
+    transactions: &'a [TransactionInfo],
+    token: &str,
+) -> Vec<&'a str> {
+    transactions
+        .iter()
+        .filter(|x| {
+            matches!(x.metadata.get(CANCELLATION_TOKEN_KEY), Some(Value::String(x)) if x == token)
+        })
+        .map(|x| x.transaction_id.as_str())
+        .collect()
+}
+
+impl Connection {
+    /// Returns a handle cancelling queries of this connection, which can be sent to another
+    /// thread.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rsmgclient::{ConnectParams, Connection};
+    /// use std::thread;
+    /// use std::time::Duration;
+    ///
+    /// let mut connection = Connection::connect(&ConnectParams {
+    ///     host: Some(String::from("localhost")),
+    ///     ..Default::default()
+    /// })
+    /// .unwrap();
+    /// let handle = connection.cancellation_handle();
+    /// thread::spawn(move || {
+    ///     thread::sleep(Duration::from_secs(10));
+    ///     handle.cancel().unwrap();
+    /// });
+    ///
+    /// connection
+    ///     .execute("UNWIND range(1, 1000000000) AS x RETURN sum(x)", None)
+    ///     .unwrap();
+    /// if let Err(err) = connection.fetchall() {
+    ///     assert!(err.is_cancelled());
+    /// }
+    /// ```
+    pub fn cancellation_handle(&mut self) -> CancellationHandle {
+        let state = self
+            .cancellation
+            .get_or_insert_with(|| Arc::new(Mutex::new(CancellationState::new())));
+        lock(state).query = self.current_query.clone();
+        CancellationHandle {
+            params: self.params.clone(),
+            state: state.clone(),
+        }
+    }
+
+    /// Adds the cancellation token to `tx_metadata` of the extra sent with a statement starting a
+    /// transaction, keeping the metadata set by the user.
+    pub(super) fn add_cancellation_token(&self, extra: &mut HashMap<String, QueryParam>) {
+        if let Some(state) = &self.cancellation {
+            let token = QueryParam::String(lock(state).token.clone());
+            // `tx_metadata` is always a map, see `TxOptions::to_extra`.
+            if let QueryParam::Map(x) = extra
+                .entry(String::from("tx_metadata"))
+                .or_insert_with(|| QueryParam::Map(HashMap::new()))
+            {
+                x.insert(String::from(CANCELLATION_TOKEN_KEY), token);
+            }
+        }
+    }
+
+    /// Updates the query which handles cancel, a new query resets the previous cancellation.
+    pub(super) fn track_cancellable_query(&self, query: Option<&str>) {
+        if let Some(state) = &self.cancellation {
+            let mut state = lock(state);
+            if query.is_some() {
+                state.cancelled = false;
+            }
+            state.query = query.map(String::from);
+        }
+    }
+
+    /// Returns whether the running query was cancelled and resets the cancellation.
+    pub(super) fn take_cancelled(&self) -> bool {
+        match &self.cancellation {
+            Some(state) => {
+                let mut state = lock(state);
+                state.query = None;
+                std::mem::take(&mut state.cancelled)
+            }
+            None => false,
+        }
+    }
+}
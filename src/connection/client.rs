@@ -0,0 +1,99 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{ConnectParams, Connection};
+use crate::error::MgError;
+use std::sync::Arc;
+
+/// Initialization of mgclient, finalized when the client and all of its sessions are dropped.
+pub(crate) struct ClientState;
+
+impl Drop for ClientState {
+    fn drop(&mut self) {
+        Connection::finalize();
+    }
+}
+
+/// Owner of the process-level initialization of mgclient, opening sessions to the server.
+///
+/// `Connection::connect` initializes mgclient for every connection and finalizes it when the
+/// connection is dropped. A `Client` instead keeps mgclient initialized for its whole lifetime,
+/// e.g. for a library embedding rsmgclient which wants to control when the socket library and
+/// OpenSSL are torn down. Every session is an independent `Connection` with its own mgclient
+/// session and can be moved to another thread, mgclient stays initialized until the client and
+/// all of its sessions are dropped.
+///
+/// # Examples
+///
+/// ```no_run
+/// use rsmgclient::{Client, ConnectParams, MgError};
+///
+/// # fn main() -> Result<(), MgError> {
+/// let client = Client::new();
+/// let params = ConnectParams::default();
+/// let mut reader = client.connect(&params)?;
+/// let mut writer = client.connect(&params)?;
+/// // ...
+/// drop(reader);
+/// drop(writer);
+/// client.shutdown()?;
+/// # Ok(()) }
+/// ```
+pub struct Client {
+    state: Arc<ClientState>,
+}
+
+impl Client {
+    /// Initializes mgclient.
+    pub fn new() -> Client {
+        Connection::init();
+        Client {
+            state: Arc::new(ClientState),
+        }
+    }
+
+    /// Opens a new session using `param_struct`, see `Connection::connect`.
+    ///
+    /// Sessions cloned using `Connection::try_clone` belong to the client as well.
+    pub fn connect(&self, param_struct: &ConnectParams) -> Result<Connection, MgError> {
+        let mut connection = Connection::connect(param_struct)?;
+        connection.client = Some(self.state.clone());
+        Ok(connection)
+    }
+
+    /// Returns the number of sessions opened by the client which were not dropped yet.
+    pub fn session_count(&self) -> usize {
+        Arc::strong_count(&self.state) - 1
+    }
+
+    /// Finalizes mgclient if all sessions were dropped.
+    ///
+    /// Returns error if some sessions are still open, mgclient is then finalized when the last
+    /// of them is dropped.
+    pub fn shutdown(self) -> Result<(), MgError> {
+        match self.session_count() {
+            0 => Ok(()),
+            count => Err(MgError::new(format!(
+                "Can't shut down client while {} sessions are open",
+                count
+            ))),
+        }
+    }
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Client::new()
+    }
+}
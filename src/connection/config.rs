@@ -0,0 +1,255 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Loading `ConnectParams` from configuration files, enabled by the `config` feature.
+
+use super::env::{parse_host_strategy, parse_hosts, parse_sslmode};
+use super::{ConnectParams, ProxyConfig, ProxyKind};
+use crate::error::MgError;
+use serde_json::{Map, Value as JsonValue};
+use std::convert::TryFrom;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+const KEYS: &[&str] = &[
+    "host",
+    "address",
+    "port",
+    "hosts",
+    "host_strategy",
+    "username",
+    "password",
+    "client_name",
+    "sslmode",
+    "sslcert",
+    "sslkey",
+    "trusted_fingerprints",
+    "lazy",
+    "autocommit",
+    "database",
+    "impersonated_user",
+    "wire_log",
+    "proxy",
+    "tcp_keepalive",
+    "tcp_nodelay",
+];
+
+const PROXY_KEYS: &[&str] = &["kind", "host", "port", "username", "password"];
+
+/// Table of a configuration file, `prefix` is prepended to keys in errors.
+struct Table<'a> {
+    map: &'a Map<String, JsonValue>,
+    prefix: &'a str,
+}
+
+impl<'a> Table<'a> {
+    fn new(value: &'a JsonValue, prefix: &'a str, keys: &[&str]) -> Result<Table<'a>, MgError> {
+        let map = match value {
+            JsonValue::Object(x) => x,
+            _ => return Err(MgError::new(String::from("expected a table"))),
+        };
+        if let Some(key) = map.keys().find(|x| !keys.contains(&x.as_str())) {
+            return Err(MgError::new(format!("unknown key `{}{}`", prefix, key)));
+        }
+        Ok(Table { map, prefix })
+    }
+
+    fn get<T, F>(&self, key: &str, expected: &str, convert: F) -> Result<Option<T>, MgError>
+    where
+        F: Fn(&JsonValue) -> Option<T>,
+    {
+        match self.map.get(key) {
+            None | Some(JsonValue::Null) => Ok(None),
+            Some(value) => match convert(value) {
+                Some(x) => Ok(Some(x)),
+                None => Err(MgError::new(format!(
+                    "invalid value of `{}{}`, expected {}",
+                    self.prefix, key, expected
+                ))),
+            },
+        }
+    }
+
+    fn string(&self, key: &str) -> Result<Option<String>, MgError> {
+        self.get(key, "a string", |x| x.as_str().map(String::from))
+    }
+
+    fn bool(&self, key: &str) -> Result<Option<bool>, MgError> {
+        self.get(key, "a boolean", JsonValue::as_bool)
+    }
+
+    fn port(&self, key: &str) -> Result<Option<u16>, MgError> {
+        self.get(key, "a port number", |x| {
+            x.as_u64().and_then(|x| u16::try_from(x).ok())
+        })
+    }
+
+    fn strings(&self, key: &str) -> Result<Option<Vec<String>>, MgError> {
+        self.get(key, "an array of strings", |x| {
+            x.as_array()?
+                .iter()
+                .map(|x| x.as_str().map(String::from))
+                .collect()
+        })
+    }
+}
+
+fn proxy_from_table(value: &JsonValue) -> Result<ProxyConfig, MgError> {
+    let table = Table::new(value, "proxy.", PROXY_KEYS)?;
+    let kind = table
+        .get("kind", "socks5 or http", |x| match x.as_str()? {
+            "socks5" => Some(ProxyKind::Socks5),
+            "http" => Some(ProxyKind::HttpConnect),
+            _ => None,
+        })?
+        .ok_or_else(|| MgError::new(String::from("missing key `proxy.kind`")))?;
+    let host = table
+        .string("host")?
+        .ok_or_else(|| MgError::new(String::from("missing key `proxy.host`")))?;
+    let port = table
+        .port("port")?
+        .ok_or_else(|| MgError::new(String::from("missing key `proxy.port`")))?;
+    Ok(ProxyConfig {
+        kind,
+        host,
+        port,
+        username: table.string("username")?,
+        password: table.string("password")?,
+    })
+}
+
+/// Returns parameters configured by a parsed configuration file, see `ConnectParams::from_toml`.
+pub(crate) fn params_from_value(value: &JsonValue) -> Result<ConnectParams, MgError> {
+    let table = Table::new(value, "", KEYS)?;
+    let default = ConnectParams::default();
+    let proxy = match table.map.get("proxy") {
+        None | Some(JsonValue::Null) => None,
+        Some(x) => Some(proxy_from_table(x)?),
+    };
+    Ok(ConnectParams {
+        host: table.string("host")?,
+        address: table.string("address")?,
+        port: table.port("port")?.unwrap_or(default.port),
+        hosts: table
+            .get("hosts", "an array of host:port strings", |x| {
+                let hosts: Option<Vec<&str>> = x.as_array()?.iter().map(|x| x.as_str()).collect();
+                parse_hosts(&hosts?.join(","))
+            })?
+            .unwrap_or_default(),
+        host_strategy: table
+            .get("host_strategy", "first-reachable or round-robin", |x| {
+                parse_host_strategy(x.as_str()?)
+            })?
+            .unwrap_or_default(),
+        username: table.string("username")?,
+        password: table.string("password")?,
+        client_name: table.string("client_name")?.unwrap_or(default.client_name),
+        sslmode: table
+            .get("sslmode", "disable or require", |x| {
+                parse_sslmode(x.as_str()?)
+            })?
+            .unwrap_or(default.sslmode),
+        sslcert: table.string("sslcert")?,
+        sslkey: table.string("sslkey")?,
+        trusted_fingerprints: table.strings("trusted_fingerprints")?,
+        lazy: table.bool("lazy")?.unwrap_or(default.lazy),
+        autocommit: table.bool("autocommit")?.unwrap_or(default.autocommit),
+        database: table.string("database")?,
+        impersonated_user: table.string("impersonated_user")?,
+        wire_log: table.bool("wire_log")?.unwrap_or(default.wire_log),
+        proxy,
+        tcp_keepalive: table
+            .get("tcp_keepalive", "a number of seconds", JsonValue::as_u64)?
+            .map(Duration::from_secs),
+        tcp_nodelay: table.bool("tcp_nodelay")?.unwrap_or(default.tcp_nodelay),
+        ..default
+    })
+}
+
+pub(crate) fn params_from_toml_str(content: &str) -> Result<ConnectParams, MgError> {
+    let value: toml::Table = content
+        .parse()
+        .map_err(|err: toml::de::Error| MgError::new(err.to_string()))?;
+    let value = serde_json::to_value(value).map_err(|err| MgError::new(err.to_string()))?;
+    params_from_value(&value)
+}
+
+pub(crate) fn params_from_json_str(content: &str) -> Result<ConnectParams, MgError> {
+    let value: JsonValue =
+        serde_json::from_str(content).map_err(|err| MgError::new(err.to_string()))?;
+    params_from_value(&value)
+}
+
+fn load<F>(path: &Path, parse: F) -> Result<ConnectParams, MgError>
+where
+    F: Fn(&str) -> Result<ConnectParams, MgError>,
+{
+    let content = fs::read_to_string(path).map_err(|err| {
+        MgError::new(format!(
+            "Unable to read config file {}: {}",
+            path.display(),
+            err
+        ))
+    })?;
+    parse(&content)
+        .map_err(|err| MgError::new(format!("Invalid config file {}: {}", path.display(), err)))
+}
+
+impl ConnectParams {
+    /// Loads parameters from a TOML file.
+    ///
+    /// Keys are names of `ConnectParams` fields which can be expressed in the file, all of
+    /// them optional: `host`, `address`, `port`, `hosts` (array of `host:port` strings),
+    /// `host_strategy` (`first-reachable` or `round-robin`), `username`, `password`,
+    /// `client_name`, `sslmode` (`disable` or `require`), `sslcert`, `sslkey`,
+    /// `trusted_fingerprints`, `lazy`, `autocommit`, `database`, `impersonated_user`,
+    /// `wire_log`, `tcp_keepalive` (seconds), `tcp_nodelay` and `proxy`, a table with `kind`
+    /// (`socks5` or `http`), `host`, `port`, `username` and `password`. Unknown keys are
+    /// rejected to catch typos.
+    ///
+    /// # Examples
+    ///
+    /// With `memgraph.toml` containing:
+    /// ```toml
+    /// host = "memgraph.internal"
+    /// username = "analytics"
+    /// sslmode = "require"
+    /// tcp_keepalive = 60
+    ///
+    /// [proxy]
+    /// kind = "socks5"
+    /// host = "bastion.example.com"
+    /// port = 1080
+    /// ```
+    ///
+    /// ```no_run
+    /// use rsmgclient::{ConnectParams, Connection, MgError};
+    ///
+    /// # fn main() -> Result<(), MgError> {
+    /// let mut params = ConnectParams::from_toml("memgraph.toml")?;
+    /// params.password = std::env::var("MEMGRAPH_PASSWORD").ok();
+    /// let mut connection = Connection::connect(&params)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn from_toml<P: AsRef<Path>>(path: P) -> Result<ConnectParams, MgError> {
+        load(path.as_ref(), params_from_toml_str)
+    }
+
+    /// Loads parameters from a JSON file containing an object with the keys described in
+    /// `from_toml`.
+    pub fn from_json<P: AsRef<Path>>(path: P) -> Result<ConnectParams, MgError> {
+        load(path.as_ref(), params_from_json_str)
+    }
+}
@@ -0,0 +1,191 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::routing::split_socket_address;
+use super::{ConnectParams, HostStrategy, SSLMode};
+use crate::error::MgError;
+use std::env::{self, VarError};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Reads environment variables using `lookup`, which behaves like `std::env::var`.
+struct Vars<F> {
+    lookup: F,
+}
+
+impl<F> Vars<F>
+where
+    F: Fn(&str) -> Result<String, VarError>,
+{
+    /// Returns value of `name`, `None` if it is not set or empty.
+    fn string(&self, name: &str) -> Result<Option<String>, MgError> {
+        match (self.lookup)(name) {
+            Ok(x) if x.is_empty() => Ok(None),
+            Ok(x) => Ok(Some(x)),
+            Err(VarError::NotPresent) => Ok(None),
+            Err(VarError::NotUnicode(_)) => Err(MgError::new(format!(
+                "Environment variable {} is not valid unicode",
+                name
+            ))),
+        }
+    }
+
+    fn parse<T, P>(&self, name: &str, expected: &str, parse: P) -> Result<Option<T>, MgError>
+    where
+        P: Fn(&str) -> Option<T>,
+    {
+        match self.string(name)? {
+            Some(x) => match parse(x.trim()) {
+                Some(value) => Ok(Some(value)),
+                None => Err(MgError::new(format!(
+                    "Invalid value `{}` of environment variable {}, expected {}",
+                    x, name, expected
+                ))),
+            },
+            None => Ok(None),
+        }
+    }
+
+    fn number<T: FromStr>(&self, name: &str, expected: &str) -> Result<Option<T>, MgError> {
+        self.parse(name, expected, |x| x.parse().ok())
+    }
+
+    fn bool(&self, name: &str) -> Result<Option<bool>, MgError> {
+        self.parse(name, "true, false, 1 or 0", |x| {
+            match x.to_ascii_lowercase().as_str() {
+                "true" | "1" => Some(true),
+                "false" | "0" => Some(false),
+                _ => None,
+            }
+        })
+    }
+}
+
+pub(super) fn parse_sslmode(value: &str) -> Option<SSLMode> {
+    match value.to_ascii_lowercase().as_str() {
+        "disable" => Some(SSLMode::Disable),
+        "require" => Some(SSLMode::Require),
+        _ => None,
+    }
+}
+
+pub(super) fn parse_host_strategy(value: &str) -> Option<HostStrategy> {
+    match value.to_ascii_lowercase().as_str() {
+        "first-reachable" => Some(HostStrategy::FirstReachable),
+        "round-robin" => Some(HostStrategy::round_robin()),
+        _ => None,
+    }
+}
+
+pub(super) fn parse_hosts(value: &str) -> Option<Vec<(String, u16)>> {
+    value
+        .split(',')
+        .map(|x| split_socket_address(x.trim()))
+        .collect()
+}
+
+/// Returns parameters configured by environment variables read using `lookup`, see
+/// `ConnectParams::from_env`.
+pub(crate) fn params_from_vars<F>(lookup: F) -> Result<ConnectParams, MgError>
+where
+    F: Fn(&str) -> Result<String, VarError>,
+{
+    let vars = Vars { lookup };
+    let default = ConnectParams::default();
+    Ok(ConnectParams {
+        host: vars.string("MEMGRAPH_HOST")?,
+        address: vars.string("MEMGRAPH_ADDRESS")?,
+        port: vars
+            .number("MEMGRAPH_PORT", "a port number")?
+            .unwrap_or(default.port),
+        hosts: vars
+            .parse(
+                "MEMGRAPH_HOSTS",
+                "comma-separated host:port pairs",
+                parse_hosts,
+            )?
+            .unwrap_or_default(),
+        host_strategy: vars
+            .parse(
+                "MEMGRAPH_HOST_STRATEGY",
+                "first-reachable or round-robin",
+                parse_host_strategy,
+            )?
+            .unwrap_or_default(),
+        username: vars.string("MEMGRAPH_USERNAME")?,
+        password: vars.string("MEMGRAPH_PASSWORD")?,
+        client_name: vars
+            .string("MEMGRAPH_CLIENT_NAME")?
+            .unwrap_or(default.client_name),
+        sslmode: vars
+            .parse("MEMGRAPH_SSLMODE", "disable or require", parse_sslmode)?
+            .unwrap_or(default.sslmode),
+        sslcert: vars.string("MEMGRAPH_SSLCERT")?,
+        sslkey: vars.string("MEMGRAPH_SSLKEY")?,
+        database: vars.string("MEMGRAPH_DATABASE")?,
+        impersonated_user: vars.string("MEMGRAPH_IMPERSONATED_USER")?,
+        lazy: vars.bool("MEMGRAPH_LAZY")?.unwrap_or(default.lazy),
+        autocommit: vars
+            .bool("MEMGRAPH_AUTOCOMMIT")?
+            .unwrap_or(default.autocommit),
+        tcp_keepalive: vars
+            .number("MEMGRAPH_TCP_KEEPALIVE", "a number of seconds")?
+            .map(Duration::from_secs),
+        tcp_nodelay: vars
+            .bool("MEMGRAPH_TCP_NODELAY")?
+            .unwrap_or(default.tcp_nodelay),
+        ..default
+    })
+}
+
+impl ConnectParams {
+    /// Creates parameters configured by environment variables, using the default value of
+    /// every parameter whose variable is not set or is empty.
+    ///
+    /// | Variable | Parameter |
+    /// |----------|-----------|
+    /// | `MEMGRAPH_HOST` | `host` |
+    /// | `MEMGRAPH_ADDRESS` | `address` |
+    /// | `MEMGRAPH_PORT` | `port` |
+    /// | `MEMGRAPH_HOSTS` | `hosts`, e.g. `main:7687,replica:7687` |
+    /// | `MEMGRAPH_HOST_STRATEGY` | `host_strategy`, `first-reachable` or `round-robin` |
+    /// | `MEMGRAPH_USERNAME` | `username` |
+    /// | `MEMGRAPH_PASSWORD` | `password` |
+    /// | `MEMGRAPH_CLIENT_NAME` | `client_name` |
+    /// | `MEMGRAPH_SSLMODE` | `sslmode`, `disable` or `require` |
+    /// | `MEMGRAPH_SSLCERT` | `sslcert` |
+    /// | `MEMGRAPH_SSLKEY` | `sslkey` |
+    /// | `MEMGRAPH_DATABASE` | `database` |
+    /// | `MEMGRAPH_IMPERSONATED_USER` | `impersonated_user` |
+    /// | `MEMGRAPH_LAZY` | `lazy`, `true`, `false`, `1` or `0` |
+    /// | `MEMGRAPH_AUTOCOMMIT` | `autocommit`, `true`, `false`, `1` or `0` |
+    /// | `MEMGRAPH_TCP_KEEPALIVE` | `tcp_keepalive` in seconds |
+    /// | `MEMGRAPH_TCP_NODELAY` | `tcp_nodelay`, `true`, `false`, `1` or `0` |
+    ///
+    /// Returns error naming the variable if a value is invalid. Parameters which can't be
+    /// expressed as strings, e.g. `trust_callback`, can be set on the returned parameters.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rsmgclient::{ConnectParams, Connection, MgError};
+    ///
+    /// # fn main() -> Result<(), MgError> {
+    /// let mut connection = Connection::connect(&ConnectParams::from_env()?)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn from_env() -> Result<ConnectParams, MgError> {
+        params_from_vars(|name| env::var(name))
+    }
+}
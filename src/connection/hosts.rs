@@ -0,0 +1,146 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{ConnectParams, Connection};
+use crate::error::MgError;
+use std::net::{IpAddr, ToSocketAddrs};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Order in which `ConnectParams::hosts`, or addresses `ConnectParams::host` resolves to, are
+/// tried when connecting.
+///
+/// # Examples
+///
+/// Spreading connections over two instances.
+/// ```no_run
+/// use rsmgclient::{ConnectParams, Connection, HostStrategy};
+///
+/// let connect_params = ConnectParams {
+///     hosts: vec![
+///         (String::from("memgraph-1"), 7687),
+///         (String::from("memgraph-2"), 7687),
+///     ],
+///     host_strategy: HostStrategy::round_robin(),
+///     ..Default::default()
+/// };
+/// let first = Connection::connect(&connect_params);
+/// let second = Connection::connect(&connect_params);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub enum HostStrategy {
+    /// Always tries hosts in the given order and uses the first reachable one, i.e. fails over
+    /// to a standby only when the main instance is down.
+    #[default]
+    FirstReachable,
+    /// Each connection starts with the host following the one the previous connection started
+    /// with, falling back to the remaining hosts in order. If `hosts` is empty, addresses `host`
+    /// resolves to are rotated instead, using `resolver` or the system resolver.
+    RoundRobin(RoundRobinCounter),
+}
+
+impl HostStrategy {
+    /// Creates `HostStrategy::RoundRobin` with a new counter.
+    pub fn round_robin() -> HostStrategy {
+        HostStrategy::RoundRobin(RoundRobinCounter::default())
+    }
+
+    /// Returns index of the first of `len` hosts to try.
+    pub(crate) fn start(&self, len: usize) -> usize {
+        match self {
+            HostStrategy::FirstReachable => 0,
+            HostStrategy::RoundRobin(counter) => {
+                counter.0.fetch_add(1, Ordering::Relaxed) % len.max(1)
+            }
+        }
+    }
+}
+
+/// Position of `HostStrategy::RoundRobin` in the list of hosts.
+///
+/// Cloning creates another handle to the same position, e.g. connections created from cloned
+/// `ConnectParams` continue the same rotation.
+#[derive(Debug, Clone, Default)]
+pub struct RoundRobinCounter(Arc<AtomicUsize>);
+
+/// Returns `items` rotated to start at `start`.
+pub(crate) fn rotate<T>(mut items: Vec<T>, start: usize) -> Vec<T> {
+    if !items.is_empty() {
+        let len = items.len();
+        items.rotate_left(start % len);
+    }
+    items
+}
+
+impl Connection {
+    /// Connects to the first reachable of `ConnectParams::hosts` in the order given by
+    /// `ConnectParams::host_strategy`.
+    ///
+    /// Parameters of the created connection refer to the host it connected to, so reconnecting,
+    /// cloning and cancelling queries use the same instance.
+    pub(super) fn connect_hosts(param_struct: &ConnectParams) -> Result<Connection, MgError> {
+        if param_struct.hosts.is_empty() {
+            return Connection::connect_resolving(param_struct);
+        }
+        if param_struct.host.is_some() || param_struct.address.is_some() {
+            return Err(MgError::new(String::from(
+                "hosts can't be specified together with host or address",
+            )));
+        }
+
+        let start = param_struct.host_strategy.start(param_struct.hosts.len());
+        let mut last_error = None;
+        for (host, port) in rotate(param_struct.hosts.clone(), start) {
+            match Connection::connect_resolving(&endpoint_params(param_struct, host, port)) {
+                Ok(connection) => return Ok(connection),
+                Err(err) => last_error = Some(err),
+            }
+        }
+        Err(last_error.unwrap())
+    }
+}
+
+/// Returns `params` changed to connect only to `host`, which is a host name or an IP address,
+/// and `port`.
+pub(crate) fn endpoint_params(params: &ConnectParams, host: String, port: u16) -> ConnectParams {
+    let mut params = params.clone();
+    params.hosts = Vec::new();
+    params.host_strategy = HostStrategy::FirstReachable;
+    params.port = port;
+    match host.parse::<IpAddr>() {
+        Ok(_) => {
+            params.host = None;
+            params.address = Some(host);
+        }
+        Err(_) => {
+            params.host = Some(host);
+            params.address = None;
+        }
+    }
+    params
+}
+
+/// Resolves `host` using the system resolver, for rotating its addresses.
+pub(crate) fn resolve_system(host: &str, port: u16) -> Result<Vec<IpAddr>, MgError> {
+    let addresses = (host, port)
+        .to_socket_addrs()
+        .map_err(|err| MgError::new(format!("Unable to resolve host {}: {}", host, err)))?;
+    let mut ips: Vec<IpAddr> = Vec::new();
+    for address in addresses {
+        if !ips.contains(&address.ip()) {
+            ips.push(address.ip());
+        }
+    }
+    Ok(ips)
+}
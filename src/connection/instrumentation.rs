@@ -0,0 +1,66 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::{MgError, ServerErrorCode};
+use std::time::Duration;
+
+/// Counter of executed queries.
+const QUERIES: &str = "rsmgclient_queries_total";
+/// Histogram of query execution times in seconds, until all records are received.
+const QUERY_DURATION: &str = "rsmgclient_query_duration_seconds";
+/// Counter of fetched records.
+const ROWS_FETCHED: &str = "rsmgclient_rows_fetched_total";
+/// Counter of failed queries and connection attempts, labeled by `kind`, see `error_kind`.
+const ERRORS: &str = "rsmgclient_errors_total";
+/// Histogram of the time needed to connect in seconds, including failed attempts.
+const CONNECT_DURATION: &str = "rsmgclient_connect_duration_seconds";
+
+pub(super) fn query_started() {
+    metrics::counter!(QUERIES).increment(1);
+}
+
+pub(super) fn query_finished(duration: Duration, error: Option<&MgError>) {
+    metrics::histogram!(QUERY_DURATION).record(duration.as_secs_f64());
+    if let Some(x) = error {
+        metrics::counter!(ERRORS, "kind" => error_kind(x)).increment(1);
+    }
+}
+
+pub(super) fn rows_fetched(count: u64) {
+    if count > 0 {
+        metrics::counter!(ROWS_FETCHED).increment(count);
+    }
+}
+
+pub(super) fn connect_finished(duration: Duration, error: Option<&MgError>) {
+    metrics::histogram!(CONNECT_DURATION).record(duration.as_secs_f64());
+    if error.is_some() {
+        metrics::counter!(ERRORS, "kind" => "connect").increment(1);
+    }
+}
+
+pub(super) fn error_kind(error: &MgError) -> &'static str {
+    if error.is_cancelled() {
+        return "cancelled";
+    }
+    if error.is_conflict() {
+        return "conflict";
+    }
+    match error.code() {
+        Some(ServerErrorCode::ClientError) => "client",
+        Some(ServerErrorCode::TransientError) => "transient",
+        Some(ServerErrorCode::DatabaseError) => "database",
+        None => "other",
+    }
+}
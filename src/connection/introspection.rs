@@ -0,0 +1,159 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::Connection;
+use crate::error::MgError;
+use crate::value::{Record, Value};
+use std::collections::HashMap;
+
+/// Storage statistics of the current database, see `Connection::storage_info`.
+///
+/// The set of reported values differs between Memgraph versions, values missing in the reply
+/// of the server are `None`. Memory sizes are in bytes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StorageInfo {
+    pub vertex_count: Option<u64>,
+    pub edge_count: Option<u64>,
+    pub average_degree: Option<f64>,
+    /// Resident memory of the server process.
+    pub memory_res: Option<u64>,
+    /// Peak resident memory of the server process.
+    pub peak_memory_res: Option<u64>,
+    /// Memory tracked by the memory limit of the server.
+    pub memory_tracked: Option<u64>,
+    /// Memory limit of the server.
+    pub allocation_limit: Option<u64>,
+    /// Disk space used by the durability files.
+    pub disk_usage: Option<u64>,
+    /// Storage mode, e.g. `IN_MEMORY_TRANSACTIONAL`.
+    pub storage_mode: Option<String>,
+    /// All values as returned by the server, keyed by their names.
+    pub raw: HashMap<String, Value>,
+}
+
+impl StorageInfo {
+    pub(crate) fn from_records(records: Vec<Record>) -> StorageInfo {
+        let raw: HashMap<String, Value> = records
+            .into_iter()
+            .filter_map(|record| {
+                let mut values = record.values.into_iter();
+                match (values.next(), values.next()) {
+                    (Some(Value::String(name)), Some(value)) => Some((name, value)),
+                    _ => None,
+                }
+            })
+            .collect();
+        let count = |name: &str| match raw.get(name) {
+            Some(Value::Int(x)) if *x >= 0 => Some(*x as u64),
+            _ => None,
+        };
+        let memory = |name: &str| raw.get(name).and_then(parse_memory);
+        StorageInfo {
+            vertex_count: count("vertex_count"),
+            edge_count: count("edge_count"),
+            average_degree: match raw.get("average_degree") {
+                Some(Value::Float(x)) => Some(*x),
+                Some(Value::Int(x)) => Some(*x as f64),
+                _ => None,
+            },
+            memory_res: memory("memory_res").or_else(|| memory("memory_usage")),
+            peak_memory_res: memory("peak_memory_res"),
+            memory_tracked: memory("memory_tracked"),
+            allocation_limit: memory("allocation_limit"),
+            disk_usage: memory("disk_usage"),
+            storage_mode: match raw.get("storage_mode") {
+                Some(Value::String(x)) => Some(x.clone()),
+                _ => None,
+            },
+            raw,
+        }
+    }
+}
+
+/// Server configuration setting, see `Connection::show_config`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigEntry {
+    pub name: String,
+    pub default_value: String,
+    pub current_value: String,
+    pub description: String,
+}
+
+impl ConfigEntry {
+    /// Returns whether the setting has its default value.
+    pub fn is_default(&self) -> bool {
+        self.default_value == self.current_value
+    }
+
+    fn from_record(record: &Record) -> ConfigEntry {
+        let field = |name: &str| match record.get(name) {
+            Some(Value::String(x)) => x.clone(),
+            Some(Value::Null) | None => String::new(),
+            Some(x) => x.to_string(),
+        };
+        ConfigEntry {
+            name: field("name"),
+            default_value: field("default_value"),
+            current_value: field("current_value"),
+            description: field("description"),
+        }
+    }
+}
+
+/// Parses memory size reported either as a number of bytes or as a human readable string, e.g.
+/// `43.16MiB`.
+pub(crate) fn parse_memory(value: &Value) -> Option<u64> {
+    let text = match value {
+        Value::Int(x) if *x >= 0 => return Some(*x as u64),
+        Value::Float(x) if *x >= 0.0 => return Some(*x as u64),
+        Value::String(x) => x.trim(),
+        _ => return None,
+    };
+    let split = text
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let multiplier: u64 = match unit.trim() {
+        "" | "B" => 1,
+        "KiB" | "KB" => 1 << 10,
+        "MiB" | "MB" => 1 << 20,
+        "GiB" | "GB" => 1 << 30,
+        "TiB" | "TB" => 1 << 40,
+        _ => return None,
+    };
+    number
+        .parse::<f64>()
+        .ok()
+        .map(|x| (x * multiplier as f64).round() as u64)
+}
+
+impl Connection {
+    /// Returns storage statistics of the current database by running `SHOW STORAGE INFO`.
+    ///
+    /// Connection needs to be in status `Ready`. The query is executed in its own transaction
+    /// regardless of `autocommit`.
+    pub fn storage_info(&mut self) -> Result<StorageInfo, MgError> {
+        let records = self.read_all_autocommit("storage_info", "SHOW STORAGE INFO")?;
+        Ok(StorageInfo::from_records(records))
+    }
+
+    /// Returns configuration settings of the server by running `SHOW CONFIG`.
+    ///
+    /// Connection needs to be in status `Ready`. The query is executed in its own transaction
+    /// regardless of `autocommit`.
+    pub fn show_config(&mut self) -> Result<Vec<ConfigEntry>, MgError> {
+        let records = self.read_all_autocommit("show_config", "SHOW CONFIG")?;
+        Ok(records.iter().map(ConfigEntry::from_record).collect())
+    }
+}
@@ -13,15 +13,24 @@
 // limitations under the License.
 
 use super::bindings;
-use super::error::MgError;
+use super::cypher::escape_identifier;
+use super::error::{MgError, ServerErrorCode};
+use super::statement_stats::{normalize_query, ConflictStats, QueryTimings, StatementStats};
+use super::summary::{QueryStats, QuerySummary};
 use super::value::{
     c_string_to_string, hash_map_to_mg_map, mg_list_to_vec, mg_map_to_hash_map, mg_value_string,
-    str_to_c_str, QueryParam, Record, Value,
+    str_to_c_str, try_mg_list_to_vec, KeyInterner, Node, ParamLimits, QueryParam, Record,
+    RecordRef, Relationship, Value,
 };
 
 use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::ffi::CString;
-use std::os::raw::c_char;
+use std::net::IpAddr;
+use std::os::raw::{c_char, c_int};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 use std::vec::IntoIter;
 
 /// Parameters for connecting to database.
@@ -44,6 +53,7 @@ use std::vec::IntoIter;
 /// let mut connection = Connection::connect(&connect_params)?;
 /// # Ok(()) }
 /// ```
+#[derive(Clone)]
 pub struct ConnectParams {
     /// Port number to connect to at the server host. Default port is 7687.
     pub port: u16,
@@ -71,17 +81,108 @@ pub struct ConnectParams {
     /// This parameter is ignored in case an SSL connection is not made.
     pub sslkey: Option<String>,
     /// After performing the SSL handshake, `Connection::connect` will call this function providing
-    /// the hostname, IP address, public key type and fingerprint and user provided data. If the
-    /// function returns a non-zero value, SSL connection will be immediately terminated. This can
-    /// be used to implement TOFU (trust on first use) mechanism.
-    pub trust_callback: Option<*const dyn Fn(&String, &String, &String, &String) -> i32>,
+    /// the hostname, IP address, public key type and fingerprint of the server. If the function
+    /// returns `TrustDecision::Reject`, SSL connection will be immediately terminated. This can
+    /// be used to implement TOFU (trust on first use) mechanism. The callback is also called when
+    /// the connection reconnects.
+    pub trust_callback: Option<TrustCallback>,
+    /// Fingerprints of server certificates which are accepted, in the format passed to
+    /// `trust_callback`. Connecting to a server with any other certificate fails, which guards
+    /// against connecting to a wrong server or cluster. Requires `sslmode` to be
+    /// `SSLMode::Require`. If `trust_callback` is also provided, it is called only for accepted
+    /// certificates.
+    pub trusted_fingerprints: Option<Vec<String>>,
     /// Initial value of `lazy` field, defaults to true, Can be changed using `Connection::set_lazy`.
     pub lazy: bool,
     /// Initial value of `autocommit` field, defaults to false. Can be changed using
     /// `Connection::set_autocommit`.
     pub autocommit: bool,
+    /// Custom resolver used to translate `host` into IP addresses instead of the system resolver.
+    /// Addresses are tried in the returned order, rotated if `host_strategy` is
+    /// `HostStrategy::RoundRobin`, until a connection is established. Ignored if
+    /// `host` is not specified, `address` is specified or `proxy` is used.
+    pub resolver: Option<Arc<Resolver>>,
+    /// Database of a multi-tenant Memgraph instance to use. The default database of the server
+    /// is used if not specified. Can be changed using `Connection::use_database`.
+    pub database: Option<String>,
+    /// User whose privileges are used to execute queries instead of the privileges of the
+    /// authenticated `username`, which needs the permission to impersonate it. Can be overridden
+    /// for a single query using `ExecuteOptions::impersonated_user`. The user is sent with
+    /// statements starting a transaction, statements the driver runs itself, e.g. `COMMIT` or
+    /// the query of `ping`, aren't impersonated.
+    pub impersonated_user: Option<String>,
+    /// Logs every Bolt message exchanged with the server together with the time it took,
+    /// defaults to false. The log can be enabled for all connections using the
+    /// `RSMGCLIENT_WIRE_LOG` environment variable.
+    ///
+    /// mgclient doesn't expose its transport, messages are therefore logged per call into
+    /// mgclient, e.g. `RUN` together with its response, and their encoded sizes are not known.
+    /// The log is written using the `log` crate with target `rsmgclient::wire` and level `Debug`
+    /// if the `log` feature is enabled, to the standard error otherwise. Queries are logged
+    /// without parameters, literals of queries setting passwords are replaced by `?`.
+    pub wire_log: bool,
+    /// SOCKS5 or HTTP `CONNECT` proxy through which the connection to the server is tunneled.
+    /// Host names are resolved by the proxy, `resolver` is therefore ignored. Certificates are
+    /// checked and passed to `trust_callback` as if the connection was made directly.
+    ///
+    /// mgclient can't use a proxy itself, the tunnel is thus exposed to it on a random local
+    /// port which accepts a single connection. Only a connection from a socket of this process is
+    /// accepted, so that another local process can't take over the tunnel authenticated to the
+    /// proxy. Sockets of the process can only be listed on Unix, using a proxy on other
+    /// platforms fails.
+    pub proxy: Option<ProxyConfig>,
+    /// Idle time after which TCP keepalive probes are sent on the connection to the server,
+    /// keepalive is not enabled by the driver if not specified. Prevents NAT gateways and
+    /// firewalls from dropping idle connections. Requires the `socket2` feature.
+    pub tcp_keepalive: Option<Duration>,
+    /// Disables Nagle's algorithm on the connection to the server, defaults to false in which
+    /// case the socket is configured by mgclient.
+    ///
+    /// mgclient doesn't expose its socket, socket options are therefore set after connecting on
+    /// the socket of this process connected to the server which didn't exist before. Sockets
+    /// can only be listed on Unix, setting either option on other platforms fails. Another
+    /// connection to the same port opened by this process at the same time without socket
+    /// options, e.g. by another driver, gets the options too. With `proxy`, the options are set
+    /// on the connection to the proxy.
+    pub tcp_nodelay: bool,
+    /// Hosts or IP addresses and ports of instances to connect to, e.g. the main instance and
+    /// replicas of a replicated Memgraph. Hosts are tried in the order given by
+    /// `host_strategy` until a connection is established, `host` and `address` must not be
+    /// specified together with them and `port` is ignored. Empty by default.
+    ///
+    /// Connections created by `Connection::try_clone`, reconnects and query cancellation use the
+    /// instance the original connection was made to.
+    pub hosts: Vec<(String, u16)>,
+    /// Order in which `hosts`, or addresses `host` resolves to, are tried. Defaults to
+    /// `HostStrategy::FirstReachable`.
+    pub host_strategy: HostStrategy,
 }
 
+/// Function translating a host name into a list of IP addresses, see `ConnectParams::resolver`.
+///
+/// # Examples
+///
+/// Resolving host names using a static host map.
+/// ```
+/// use rsmgclient::{ConnectParams, MgError};
+/// use std::collections::HashMap;
+/// use std::net::IpAddr;
+/// use std::sync::Arc;
+///
+/// let mut hosts: HashMap<String, IpAddr> = HashMap::new();
+/// hosts.insert(String::from("memgraph.internal"), "10.0.0.7".parse().unwrap());
+///
+/// let connect_params = ConnectParams {
+///     host: Some(String::from("memgraph.internal")),
+///     resolver: Some(Arc::new(move |host: &str| match hosts.get(host) {
+///         Some(address) => Ok(vec![*address]),
+///         None => Err(MgError::new(format!("Unknown host {}", host))),
+///     })),
+///     ..Default::default()
+/// };
+/// ```
+pub type Resolver = dyn Fn(&str) -> Result<Vec<IpAddr>, MgError> + Send + Sync;
+
 impl Default for ConnectParams {
     fn default() -> Self {
         ConnectParams {
@@ -95,18 +196,114 @@ impl Default for ConnectParams {
             sslcert: None,
             sslkey: None,
             trust_callback: None,
+            trusted_fingerprints: None,
             lazy: true,
             autocommit: false,
+            resolver: None,
+            database: None,
+            impersonated_user: None,
+            wire_log: false,
+            proxy: None,
+            tcp_keepalive: None,
+            tcp_nodelay: false,
+            hosts: Vec::new(),
+            host_strategy: HostStrategy::FirstReachable,
         }
     }
 }
 
+/// Server certificate information passed to `ConnectParams::trust_callback`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrustInfo {
+    /// Host name the connection was made to, empty if connected using an address.
+    pub hostname: String,
+    /// IP address of the server.
+    pub ip: String,
+    /// Port the connection was made to.
+    pub port: u16,
+    /// Type of the public key of the certificate, e.g. `rsaEncryption`.
+    pub key_type: String,
+    /// Fingerprint of the certificate.
+    pub fingerprint: String,
+}
+
+/// Decision made by `ConnectParams::trust_callback` about a server certificate.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum TrustDecision {
+    /// Continue connecting.
+    Accept,
+    /// Terminate the connection.
+    Reject,
+}
+
+/// Callback deciding whether a server certificate is trusted, see
+/// `ConnectParams::trust_callback`.
+///
+/// Cloning creates another handle to the same callback, e.g. connections created from cloned
+/// `ConnectParams` share the callback.
+///
+/// # Examples
+///
+/// Trusting the certificate seen on the first connection (TOFU).
+/// ```
+/// use rsmgclient::{ConnectParams, SSLMode, TrustCallback, TrustDecision};
+///
+/// let mut known: Option<String> = None;
+/// let connect_params = ConnectParams {
+///     host: Some(String::from("localhost")),
+///     sslmode: SSLMode::Require,
+///     trust_callback: Some(TrustCallback::new(move |info| match &known {
+///         Some(x) if *x != info.fingerprint => TrustDecision::Reject,
+///         Some(_) => TrustDecision::Accept,
+///         None => {
+///             known = Some(info.fingerprint.clone());
+///             TrustDecision::Accept
+///         }
+///     })),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone)]
+pub struct TrustCallback(Arc<Mutex<Box<TrustCallbackFn>>>);
+
+type TrustCallbackFn = dyn FnMut(&TrustInfo) -> TrustDecision + Send;
+
+impl TrustCallback {
+    /// Makes a trust callback from a closure.
+    pub fn new<F>(callback: F) -> TrustCallback
+    where
+        F: FnMut(&TrustInfo) -> TrustDecision + Send + 'static,
+    {
+        TrustCallback::from(Box::new(callback) as Box<TrustCallbackFn>)
+    }
+
+    fn call(&self, info: &TrustInfo) -> TrustDecision {
+        let mut callback = match self.0.lock() {
+            Ok(x) => x,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        callback(info)
+    }
+}
+
+impl From<Box<dyn FnMut(&TrustInfo) -> TrustDecision + Send>> for TrustCallback {
+    fn from(callback: Box<dyn FnMut(&TrustInfo) -> TrustDecision + Send>) -> TrustCallback {
+        TrustCallback(Arc::new(Mutex::new(callback)))
+    }
+}
+
 /// Determines whether a secure SSL TCP/IP connection will be negotiated with the server.
-#[derive(PartialEq, Eq)]
+///
+/// mgclient does not verify the server certificate against a certificate authority, nor does it
+/// check that the certificate matches the host name. Applications that need to authenticate the
+/// server should check the certificate fingerprint using `ConnectParams::trusted_fingerprints`
+/// or `ConnectParams::trust_callback`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
 pub enum SSLMode {
     /// Only try a non-SSL connection.
     Disable,
-    /// Only try a SSL connection.
+    /// Only try a SSL connection. Any server certificate is accepted unless rejected by
+    /// `ConnectParams::trust_callback`.
     Require,
 }
 
@@ -138,12 +335,234 @@ pub enum SSLMode {
 /// ```
 pub struct Connection {
     mg_session: *mut bindings::mg_session,
+    params: ConnectParams,
     lazy: bool,
     autocommit: bool,
     status: ConnectionStatus,
     results_iter: Option<IntoIter<Record>>,
+    property_keys: KeyInterner,
     arraysize: u32,
-    summary: Option<HashMap<String, Value>>,
+    fetch_batch_size: u32,
+    max_buffered_rows: Option<usize>,
+    pull_size: Option<u32>,
+    summary: Option<QuerySummary>,
+    columns: Option<Arc<Vec<String>>>,
+    query_policy: Option<Box<QueryPolicy>>,
+    param_limits: ParamLimits,
+    strict_conversions: bool,
+    elide_read_begin: bool,
+    begin_elided: bool,
+    statement_stats: Option<HashMap<String, StatementStats>>,
+    pending_statement: Option<(String, Instant)>,
+    conflict_stats: ConflictStats,
+    current_query: Option<String>,
+    collect_query_timings: bool,
+    query_timings: Option<QueryTimings>,
+    drop_policy: DropPolicy,
+    explicit_transaction: bool,
+    cancellation: Option<Arc<Mutex<CancellationState>>>,
+    prepared: HashMap<String, PreparedQuery>,
+    observer: Option<Box<dyn QueryObserver>>,
+    wire_log: bool,
+    /// Rows received in the current pull, added to the metrics once the pull finishes.
+    #[cfg(feature = "metrics")]
+    pulled_rows: u64,
+    /// Client which opened the connection, keeping mgclient initialized.
+    client: Option<Arc<ClientState>>,
+}
+
+// SAFETY: `Connection` owns its `mg_session` exclusively and mgclient sessions have no thread
+// affinity, a session may be used from any thread as long as it is not used from two threads at the
+// same time, which `&mut self` methods guarantee. The only global state is initialized by `mg_init`
+// and `mg_finalize` (socket library and OpenSSL), which are thread-safe. Callbacks stored in the
+// connection are required to be `Send`. `Connection` is not `Sync`, use `SyncConnection` to share
+// it between threads.
+unsafe impl Send for Connection {}
+
+/// Callback inspecting every user query (and its parameters) before it is sent to the server.
+///
+/// See `Connection::set_query_policy`.
+pub type QueryPolicy = dyn Fn(&str, Option<&HashMap<String, QueryParam>>) -> PolicyDecision + Send;
+
+/// Decision made by a `QueryPolicy` about a query that is about to be executed.
+pub enum PolicyDecision {
+    /// Execute the query unchanged.
+    Allow,
+    /// Refuse to execute the query. The message is returned to the caller as an error.
+    Deny(String),
+    /// Execute the given query and parameters instead of the original ones.
+    Rewrite(String, Option<HashMap<String, QueryParam>>),
+}
+
+/// Query and parameters a `QueryPolicy` replaced a query with.
+type RewrittenQuery = (String, Option<HashMap<String, QueryParam>>);
+
+/// Information about the server, see `Connection::server_info`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerInfo {
+    /// Version of Memgraph, e.g. `2.19.0`.
+    pub version: String,
+}
+
+/// Determines how `Connection::execute_read_with_retry` retries failed reads.
+///
+/// The delay before the n-th retry is `initial_backoff * 2^(n - 1)`, capped at `max_backoff`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct RetryPolicy {
+    /// Maximum number of retries after the first attempt. Default value is 3.
+    pub max_retries: u32,
+    /// Delay before the first retry. Default value is 100 milliseconds.
+    pub initial_backoff: Duration,
+    /// Upper bound of the delay between retries. Default value is 5 seconds.
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Returns the delay before retry number `retry`, starting from 1.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 1u32
+            .checked_shl(retry.saturating_sub(1))
+            .unwrap_or(u32::MAX);
+        match self.initial_backoff.checked_mul(factor) {
+            Some(x) if x < self.max_backoff => x,
+            _ => self.max_backoff,
+        }
+    }
+}
+
+/// Configuration of a transaction started using `Connection::begin`.
+#[derive(Debug, Clone, Default)]
+pub struct TxOptions {
+    /// Transaction timeout, after which the server aborts the transaction. Server default is used
+    /// if `None`.
+    pub timeout: Option<Duration>,
+    /// Metadata attached to the transaction, e.g. to identify it in `SHOW TRANSACTIONS`.
+    pub metadata: Option<HashMap<String, QueryParam>>,
+    /// Access mode of the transaction. Server default is used if `None`.
+    pub mode: Option<AccessMode>,
+}
+
+/// Access mode of a transaction, see `TxOptions::mode`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum AccessMode {
+    /// Transaction only reads data.
+    Read,
+    /// Transaction reads and writes data.
+    Write,
+}
+
+impl TxOptions {
+    fn to_extra(&self) -> HashMap<String, QueryParam> {
+        let mut extra = HashMap::new();
+        if let Some(x) = self.timeout {
+            let millis = i64::try_from(x.as_millis()).unwrap_or(i64::MAX);
+            extra.insert(String::from("tx_timeout"), QueryParam::Int(millis));
+        }
+        if let Some(x) = &self.metadata {
+            extra.insert(String::from("tx_metadata"), QueryParam::Map(x.clone()));
+        }
+        if let Some(x) = self.mode {
+            let mode = match x {
+                AccessMode::Read => "r",
+                AccessMode::Write => "w",
+            };
+            extra.insert(String::from("mode"), QueryParam::String(String::from(mode)));
+        }
+        extra
+    }
+}
+
+/// Per-query options of `Connection::execute_with_options`.
+#[derive(Debug, Clone, Default)]
+pub struct ExecuteOptions {
+    /// User to impersonate, overrides `ConnectParams::impersonated_user`. The server applies it
+    /// only to statements starting a transaction, it is therefore ignored if the connection is
+    /// already in a transaction.
+    pub impersonated_user: Option<String>,
+    /// Timeout of the transaction running the query, sent as `tx_timeout`. Like
+    /// `impersonated_user`, it is ignored if the connection is already in a transaction.
+    pub timeout: Option<Duration>,
+    /// Metadata attached to the transaction running the query, sent as `tx_metadata`. Ignored if
+    /// the connection is already in a transaction.
+    pub tx_metadata: Option<HashMap<String, QueryParam>>,
+    /// Number of records requested from the server at once in lazy mode, overrides
+    /// `Connection::fetch_batch_size` for this query. Like with `fetch_batch_size`, 0 requests
+    /// all records at once.
+    pub n_pull: Option<u32>,
+}
+
+impl ExecuteOptions {
+    fn to_tx_extra(&self) -> HashMap<String, QueryParam> {
+        TxOptions {
+            timeout: self.timeout,
+            metadata: self.tx_metadata.clone(),
+            mode: None,
+        }
+        .to_extra()
+    }
+}
+
+/// Transaction isolation level, see `Connection::set_isolation_level`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum IsolationLevel {
+    SnapshotIsolation,
+    ReadCommitted,
+    ReadUncommitted,
+}
+
+impl IsolationLevel {
+    fn to_cypher(self) -> &'static str {
+        match self {
+            IsolationLevel::SnapshotIsolation => "SNAPSHOT ISOLATION",
+            IsolationLevel::ReadCommitted => "READ COMMITTED",
+            IsolationLevel::ReadUncommitted => "READ UNCOMMITTED",
+        }
+    }
+}
+
+/// Storage mode of the database, see `Connection::set_storage_mode`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum StorageMode {
+    InMemoryTransactional,
+    InMemoryAnalytical,
+    OnDiskTransactional,
+}
+
+impl StorageMode {
+    fn to_cypher(self) -> &'static str {
+        match self {
+            StorageMode::InMemoryTransactional => "IN_MEMORY_TRANSACTIONAL",
+            StorageMode::InMemoryAnalytical => "IN_MEMORY_ANALYTICAL",
+            StorageMode::OnDiskTransactional => "ON_DISK_TRANSACTIONAL",
+        }
+    }
+}
+
+/// Determines what happens when a `Connection` with an open transaction is dropped, see
+/// `Connection::set_drop_policy`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum DropPolicy {
+    /// Destroy the session, the server aborts the transaction only once it notices the
+    /// connection was closed.
+    Discard,
+    /// Explicitly roll back the transaction before destroying the session. Rolling back is best
+    /// effort, errors are ignored. A query which is still executing is not interrupted, its
+    /// transaction is discarded. This is the default.
+    Rollback,
+    /// Print a warning to the standard error and destroy the session.
+    Warn,
+    /// Panic in debug builds, behave as `Warn` in release builds.
+    Panic,
 }
 
 /// Representation of current connection status.
@@ -164,22 +583,113 @@ pub enum ConnectionStatus {
     Bad,
 }
 
+const DEFAULT_FETCH_BATCH_SIZE: u32 = 1000;
+
+/// Number of rows inserted by a single query of `insert_nodes` and `insert_relationships`.
+const INSERT_BATCH_SIZE: usize = 1000;
+
+// Values of `MG_SESSION_READY` and `MG_SESSION_BAD`. Bindings constants are not used because
+// their type differs between platforms.
+const MG_SESSION_READY: c_int = 0;
+const MG_SESSION_BAD: c_int = 2;
+
+// Messages of `DropPolicy::Warn` and `DropPolicy::Panic`, neither rolls the transaction back.
+const DROPPED_TRANSACTION_WARNING: &str =
+    "rsmgclient: Connection dropped with an open transaction, the transaction is not rolled back \
+     and is aborted once the server notices the closed connection";
+const DROPPED_TRANSACTION_PANIC: &str =
+    "rsmgclient: Connection dropped with an open transaction, commit or roll back transactions \
+     before dropping connections with DropPolicy::Panic";
+
+/// Makes the extra map sent along with a query or a transaction begin, null if there is nothing
+/// to send.
+fn make_run_extra(
+    impersonated_user: Option<&str>,
+    tx_extra: Option<&HashMap<String, QueryParam>>,
+) -> *mut bindings::mg_map {
+    let mut extra = tx_extra.cloned().unwrap_or_default();
+    if let Some(x) = impersonated_user {
+        extra.insert(String::from("imp_user"), QueryParam::String(x.to_string()));
+    }
+    match extra.is_empty() {
+        true => std::ptr::null_mut(),
+        false => hash_map_to_mg_map(&extra),
+    }
+}
+
+// Maps passed to `mg_session_run` are only read by it, the caller keeps their ownership.
+fn destroy_mg_map(map: *mut bindings::mg_map) {
+    if !map.is_null() {
+        unsafe { bindings::mg_map_destroy(map) };
+    }
+}
+
+fn run_message(query: &str, params: Option<&HashMap<String, QueryParam>>, status: c_int) -> String {
+    format!(
+        "RUN {:?} with {} parameters -> {}",
+        redact_query(query),
+        params.map_or(0, |x| x.len()),
+        response_name(status)
+    )
+}
+
+fn fetch_message(status: c_int) -> String {
+    match status {
+        1 => String::from("RECORD"),
+        0 => String::from("SUCCESS"),
+        _ => String::from("FAILURE"),
+    }
+}
+
 fn read_error_message(mg_session: *mut bindings::mg_session) -> String {
     let c_error_message = unsafe { bindings::mg_session_error(mg_session) };
     unsafe { c_string_to_string(c_error_message, None) }
 }
 
+/// Makes an error from the last error message of the session and the status code returned by
+/// the failed call.
+fn session_error(mg_session: *mut bindings::mg_session, status: c_int) -> MgError {
+    MgError::with_code(
+        read_error_message(mg_session),
+        ServerErrorCode::from_status(status),
+    )
+}
+
 impl Drop for Connection {
     fn drop(&mut self) {
-        unsafe { bindings::mg_session_destroy(self.mg_session) };
+        let mut panic = false;
+        if self.has_open_transaction() {
+            match self.drop_policy {
+                DropPolicy::Discard => {}
+                DropPolicy::Rollback => {
+                    if self.status == ConnectionStatus::InTransaction {
+                        let _ = self.rollback();
+                    }
+                }
+                DropPolicy::Warn => eprintln!("{}", DROPPED_TRANSACTION_WARNING),
+                DropPolicy::Panic => {
+                    panic = cfg!(debug_assertions) && !std::thread::panicking();
+                    if !panic {
+                        eprintln!("{}", DROPPED_TRANSACTION_WARNING);
+                    }
+                }
+            }
+        }
+        if !self.mg_session.is_null() {
+            unsafe { bindings::mg_session_destroy(self.mg_session) };
+        }
         Connection::finalize();
+        if panic {
+            panic!("{}", DROPPED_TRANSACTION_PANIC);
+        }
     }
 }
 
 impl Connection {
     /// Initializes underlying mgclient.
     ///
-    /// Should be called at the beginning of each process using the client.
+    /// Called by `Connection::connect` for every connection, see `Client` for keeping mgclient
+    /// initialized explicitly.
     pub fn init() {
         unsafe {
             bindings::mg_init();
@@ -188,7 +698,8 @@ impl Connection {
 
     /// Finalizes underlying mgclient.
     ///
-    /// Should be called at the end of each process using the client.
+    /// Called when a connection is dropped, balancing the initialization done by
+    /// `Connection::connect`.
     pub fn finalize() {
         unsafe {
             bindings::mg_finalize();
@@ -230,53 +741,412 @@ impl Connection {
         self.status
     }
 
+    /// Returns the underlying mgclient session, for calling mgclient functions which aren't
+    /// wrapped by this crate through `rsmgclient::bindings`. Enabled by the `raw` feature.
+    ///
+    /// The pointer is valid until the connection is closed or dropped, or until its session is
+    /// replaced with a new one, which `reset` does unless the session can be recovered and
+    /// `execute_read_with_retry` does before every retry.
+    ///
+    /// # Safety
+    ///
+    /// The connection keeps its own status and doesn't see what is done with the session
+    /// directly. The session must only be used while the connection is in status `Ready` and it
+    /// must be left in the same state afterwards, i.e. all started queries must be fully fetched
+    /// and transactions begun on it must be finished. It must not be destroyed. A new pointer must
+    /// be obtained after any call which may replace the session.
+    #[cfg(feature = "raw")]
+    pub fn as_raw_session(&mut self) -> *mut bindings::mg_session {
+        self.mg_session
+    }
+
     /// Returns query summary if it is present.
     ///
     /// Query summary is present after query has completed execution( all records have been
     /// fetched). Executing new query will remove previous query summary.
-    pub fn summary(&self) -> Option<HashMap<String, Value>> {
-        self.summary.as_ref().map(|x| (*x).clone())
+    ///
+    /// Returns a copy of the summary, see `summary_ref` and `take_summary` to avoid copying.
+    pub fn summary(&self) -> Option<QuerySummary> {
+        self.summary.clone()
+    }
+
+    /// Returns names of columns of the last query executed using `execute`.
+    ///
+    /// Columns are available until another query is executed, they are `None` if the last query
+    /// failed or was executed using `execute_without_results`.
+    pub fn columns(&self) -> Option<&[String]> {
+        self.columns.as_ref().map(|x| x.as_slice())
+    }
+
+    /// Returns reference to query summary if it is present, see `summary`.
+    pub fn summary_ref(&self) -> Option<&QuerySummary> {
+        self.summary.as_ref()
+    }
+
+    /// Removes query summary from the connection and returns it if it is present, see `summary`.
+    pub fn take_summary(&mut self) -> Option<QuerySummary> {
+        self.summary.take()
     }
 
     /// Setter for `lazy` field.
     ///
     /// # Panics
     ///
-    /// Panics if connection is not in a `Ready` status.
+    /// Panics if connection is not in a `Ready` status, see `try_set_lazy` for a non-panicking
+    /// version.
     pub fn set_lazy(&mut self, lazy: bool) {
-        match self.status {
-            ConnectionStatus::Ready => self.lazy = lazy,
-            ConnectionStatus::InTransaction => panic!("Can't set lazy while in transaction"),
-            ConnectionStatus::Executing => panic!("Can't set lazy while executing"),
-            ConnectionStatus::Fetching => panic!("Can't set lazy while fetching"),
-            ConnectionStatus::Bad => panic!("Can't set lazy while connection is bad"),
-            ConnectionStatus::Closed => panic!("Can't set lazy while connection is closed"),
+        if let Err(err) = self.try_set_lazy(lazy) {
+            panic!("{}", err)
         }
     }
 
+    /// Setter for `lazy` field.
+    ///
+    /// Returns error if connection is not in a `Ready` status.
+    pub fn try_set_lazy(&mut self, lazy: bool) -> Result<(), MgError> {
+        let reason = match self.status {
+            ConnectionStatus::Ready => {
+                self.lazy = lazy;
+                return Ok(());
+            }
+            ConnectionStatus::InTransaction => "while in transaction",
+            ConnectionStatus::Executing => "while executing",
+            ConnectionStatus::Fetching => "while fetching",
+            ConnectionStatus::Bad => "while connection is bad",
+            ConnectionStatus::Closed => "while connection is closed",
+        };
+        Err(MgError::new(format!("Can't set lazy {}", reason)))
+    }
+
     /// Setter for `autocommit` field.
     ///
     /// # Panics
     ///
-    /// Panics if connection is not in a `Ready` status.
+    /// Panics if connection is not in a `Ready` status, see `try_set_autocommit` for a
+    /// non-panicking version.
     pub fn set_autocommit(&mut self, autocommit: bool) {
-        match self.status {
-            ConnectionStatus::Ready => self.autocommit = autocommit,
-            ConnectionStatus::InTransaction => {
-                panic!("Can't set autocommit while in transaction")
-            }
-            ConnectionStatus::Executing => panic!("Can't set autocommit while executing"),
-            ConnectionStatus::Fetching => panic!("Can't set autocommit while fetching"),
-            ConnectionStatus::Bad => panic!("Can't set autocommit while connection is bad"),
-            ConnectionStatus::Closed => panic!("Can't set autocommit while connection is closed"),
+        if let Err(err) = self.try_set_autocommit(autocommit) {
+            panic!("{}", err)
         }
     }
 
+    /// Setter for `autocommit` field.
+    ///
+    /// Returns error if connection is not in a `Ready` status.
+    pub fn try_set_autocommit(&mut self, autocommit: bool) -> Result<(), MgError> {
+        let reason = match self.status {
+            ConnectionStatus::Ready => {
+                self.autocommit = autocommit;
+                return Ok(());
+            }
+            ConnectionStatus::InTransaction => "while in transaction",
+            ConnectionStatus::Executing => "while executing",
+            ConnectionStatus::Fetching => "while fetching",
+            ConnectionStatus::Bad => "while connection is bad",
+            ConnectionStatus::Closed => "while connection is closed",
+        };
+        Err(MgError::new(format!("Can't set autocommit {}", reason)))
+    }
+
     /// Setter for `arraysize` field.
     pub fn set_arraysize(&mut self, arraysize: u32) {
         self.arraysize = arraysize;
     }
 
+    /// Returns the number of records requested from the server at once in lazy mode.
+    pub fn fetch_batch_size(&self) -> u32 {
+        self.fetch_batch_size
+    }
+
+    /// Sets the number of records requested from the server at once in lazy mode, default is
+    /// 1000.
+    ///
+    /// Records are still returned one at a time by the fetch methods, the next batch is requested
+    /// once all records of the previous one are fetched. Larger batches need fewer round trips,
+    /// smaller ones keep less records in memory. If set to 0, all records are requested at once.
+    /// Takes effect with the next batch.
+    pub fn set_fetch_batch_size(&mut self, fetch_batch_size: u32) {
+        self.fetch_batch_size = fetch_batch_size;
+    }
+
+    /// Returns the maximum number of records received in advance in non-lazy mode.
+    pub fn max_buffered_rows(&self) -> Option<usize> {
+        self.max_buffered_rows
+    }
+
+    /// Sets the maximum number of records received in advance in non-lazy mode, unlimited by
+    /// default.
+    ///
+    /// If a query returns more records, `execute` fails instead of exhausting memory and the
+    /// connection goes into status `Bad`. Use lazy mode or `for_each_row` for such queries.
+    pub fn set_max_buffered_rows(&mut self, max_buffered_rows: Option<usize>) {
+        self.max_buffered_rows = max_buffered_rows;
+    }
+
+    /// Returns whether `execute` skips the implicit `BEGIN` for read-only queries.
+    pub fn elide_read_begin(&self) -> bool {
+        self.elide_read_begin
+    }
+
+    /// Sets whether `execute` skips the implicit `BEGIN` for read-only queries.
+    ///
+    /// When autocommit is set to false, `execute` starts a transaction before running a query
+    /// outside of a transaction. If enabled, queries detected as read-only (queries starting
+    /// with `MATCH`, `OPTIONAL MATCH`, `WITH`, `UNWIND`, `RETURN` or `SHOW` and without clauses
+    /// such as `CREATE`, `MERGE`, `SET`, `DELETE`, `REMOVE` or `CALL`) are instead run
+    /// outside of a transaction and the connection returns to `Ready` status after all records
+    /// are fetched. Queries executed while a transaction is already open are not affected.
+    ///
+    /// Detection is conservative, queries which can't be recognized as reads always start a
+    /// transaction.
+    pub fn set_elide_read_begin(&mut self, elide_read_begin: bool) {
+        self.elide_read_begin = elide_read_begin;
+    }
+
+    /// Returns what happens when the connection is dropped with an open transaction.
+    pub fn drop_policy(&self) -> DropPolicy {
+        self.drop_policy
+    }
+
+    /// Sets what happens when the connection is dropped with an open transaction.
+    ///
+    /// A transaction is open while the connection is in status `InTransaction`, or executing a
+    /// query inside of a transaction. Such transaction is never committed, e.g. when a function
+    /// returns early with an error before calling `commit`. The policy can be used to detect
+    /// these lost transactions.
+    pub fn set_drop_policy(&mut self, drop_policy: DropPolicy) {
+        self.drop_policy = drop_policy;
+    }
+
+    /// Drops the connection without rolling back its open transaction, regardless of the drop
+    /// policy.
+    ///
+    /// The session is destroyed and the transaction is left to the server to abort.
+    pub fn leak_transaction(mut self) {
+        self.drop_policy = DropPolicy::Discard;
+    }
+
+    fn has_open_transaction(&self) -> bool {
+        match self.status {
+            ConnectionStatus::InTransaction => true,
+            ConnectionStatus::Executing | ConnectionStatus::Fetching => {
+                self.explicit_transaction || (!self.autocommit && !self.begin_elided)
+            }
+            ConnectionStatus::Ready | ConnectionStatus::Closed | ConnectionStatus::Bad => false,
+        }
+    }
+
+    /// Sets a policy which inspects every query passed to `execute` and `execute_without_results`
+    /// before it is sent to the server.
+    ///
+    /// The policy can allow the query, deny it or rewrite the query and its parameters, e.g. to
+    /// enforce a tenant label filter in a middle tier. Transaction control statements issued by
+    /// the connection itself are not passed to the policy. Passing `None` removes the policy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rsmgclient::{ConnectParams, Connection, PolicyDecision};
+    /// # use rsmgclient::{MgError};
+    /// # fn set_policy() -> Result<(), MgError> {
+    ///
+    /// let mut connection = Connection::connect(&ConnectParams {
+    ///     host: Some(String::from("localhost")),
+    ///     ..Default::default()
+    /// })?;
+    /// connection.set_query_policy(Some(Box::new(|query, _params| {
+    ///     if query.to_uppercase().contains("DELETE") {
+    ///         PolicyDecision::Deny(String::from("Deleting is not allowed"))
+    ///     } else {
+    ///         PolicyDecision::Allow
+    ///     }
+    /// })));
+    /// # Ok(()) }
+    /// ```
+    pub fn set_query_policy(&mut self, policy: Option<Box<QueryPolicy>>) {
+        self.query_policy = policy;
+    }
+
+    /// Returns limits checked on query parameters before they are sent to the server.
+    pub fn param_limits(&self) -> ParamLimits {
+        self.param_limits
+    }
+
+    /// Sets limits checked on query parameters before they are sent to the server.
+    ///
+    /// Queries with parameters exceeding the limits fail with an error without being sent.
+    pub fn set_param_limits(&mut self, param_limits: ParamLimits) {
+        self.param_limits = param_limits;
+    }
+
+    /// Returns whether fetching fails on values which can't be converted.
+    pub fn strict_conversions(&self) -> bool {
+        self.strict_conversions
+    }
+
+    /// Sets whether fetching fails on values which can't be converted.
+    ///
+    /// Values received from the database which can't be represented as `Value`, e.g. dates out
+    /// of range of `chrono` types, are by default replaced by `Value::Null`. With strict
+    /// conversions fetch methods return error instead.
+    pub fn set_strict_conversions(&mut self, strict_conversions: bool) {
+        self.strict_conversions = strict_conversions;
+    }
+
+    /// Enables or disables collection of per query statistics, see `statement_stats`.
+    ///
+    /// Disabling the collection discards already collected statistics.
+    pub fn set_collect_statement_stats(&mut self, collect: bool) {
+        match (collect, &self.statement_stats) {
+            (true, None) => self.statement_stats = Some(HashMap::new()),
+            (false, _) => self.statement_stats = None,
+            (true, Some(_)) => {}
+        }
+    }
+
+    /// Returns execution statistics aggregated per normalized query, ordered by total execution
+    /// time (descending).
+    ///
+    /// Queries are grouped by the shape returned by `normalize_query`. The execution time of a
+    /// query is measured from the call to `execute` until all of its records have been fetched.
+    /// Statistics are collected only after enabling them using `set_collect_statement_stats`,
+    /// otherwise the returned list is empty.
+    pub fn statement_stats(&self) -> Vec<&StatementStats> {
+        let mut stats: Vec<&StatementStats> = match &self.statement_stats {
+            Some(x) => x.values().collect(),
+            None => Vec::new(),
+        };
+        stats.sort_by_key(|x| std::cmp::Reverse(x.total_time));
+        stats
+    }
+
+    /// Clears collected statement statistics.
+    pub fn reset_statement_stats(&mut self) {
+        if let Some(stats) = self.statement_stats.as_mut() {
+            stats.clear();
+        }
+    }
+
+    /// Enables or disables measuring of time spent in individual phases of queries, see
+    /// `query_timings`.
+    pub fn set_collect_query_timings(&mut self, collect: bool) {
+        self.collect_query_timings = collect;
+        if !collect {
+            self.query_timings = None;
+        }
+    }
+
+    /// Returns breakdown of time spent on the last query run using `execute`, so far.
+    ///
+    /// Timings are measured only after enabling them using `set_collect_query_timings`,
+    /// otherwise `None` is returned. Time of the implicit `BEGIN` is not included.
+    pub fn query_timings(&self) -> Option<QueryTimings> {
+        self.query_timings
+    }
+
+    fn record_timing<F: FnOnce(&mut QueryTimings)>(&mut self, record: F) {
+        if let Some(timings) = self.query_timings.as_mut() {
+            record(timings);
+        }
+    }
+
+    /// Returns statistics of statements which failed due to a conflict with a concurrent
+    /// transaction.
+    ///
+    /// Conflicts are counted regardless of `set_collect_statement_stats`. Frequent conflicts
+    /// while ingesting data usually mean that transactions are too large and should be split
+    /// into smaller batches.
+    pub fn conflict_stats(&self) -> &ConflictStats {
+        &self.conflict_stats
+    }
+
+    /// Clears transaction conflict statistics.
+    pub fn reset_conflict_stats(&mut self) {
+        self.conflict_stats = ConflictStats::default();
+    }
+
+    fn statement_error(&mut self, query: &str, status: c_int) -> MgError {
+        let err = session_error(self.mg_session, status);
+        if self.take_cancelled() {
+            return MgError::cancelled(format!("Query was cancelled: {}", err), err.code());
+        }
+        if !err.is_conflict() {
+            return err;
+        }
+        self.conflict_stats.conflicts += 1;
+        self.conflict_stats.consecutive_conflicts += 1;
+        // Like in the wire log, passwords set by the query must not leak into errors.
+        let query = redact_query(query);
+        self.conflict_stats.last_conflicting_query = Some(query.to_string());
+        MgError::with_code(
+            format!(
+                "{} [conflict {} in a row while executing `{}`; retry the transaction or reduce its size]",
+                err, self.conflict_stats.consecutive_conflicts, query
+            ),
+            err.code(),
+        )
+    }
+
+    fn current_statement_error(&mut self, status: c_int) -> MgError {
+        let query = self.current_query.take().unwrap_or_default();
+        self.statement_error(&query, status)
+    }
+
+    fn start_statement(&mut self, query: &str, params: Option<&HashMap<String, QueryParam>>) {
+        if let Some(observer) = self.observer.as_mut() {
+            observer.on_execute(query, params);
+        }
+        #[cfg(feature = "metrics")]
+        instrumentation::query_started();
+        if self.statement_stats.is_some() || self.observer.is_some() || cfg!(feature = "metrics") {
+            self.pending_statement = Some((query.to_string(), Instant::now()));
+        }
+    }
+
+    fn finish_statement(&mut self, error: Option<&MgError>) {
+        if error.is_none() && self.autocommit && !self.explicit_transaction {
+            self.conflict_stats.consecutive_conflicts = 0;
+        }
+        let (query, start) = match self.pending_statement.take() {
+            Some(x) => x,
+            None => return,
+        };
+        #[cfg(feature = "metrics")]
+        instrumentation::query_finished(start.elapsed(), error);
+        if let Some(observer) = self.observer.as_mut() {
+            match error {
+                Some(x) => observer.on_error(x),
+                None => observer.on_complete(start.elapsed(), self.summary.as_ref()),
+            }
+        }
+        if let Some(stats) = self.statement_stats.as_mut() {
+            let normalized = normalize_query(&query);
+            let stats = stats
+                .entry(normalized.clone())
+                .or_insert_with(|| StatementStats::new(normalized));
+            stats.record(start.elapsed(), error.is_some());
+            if matches!(error, Some(x) if x.is_conflict()) {
+                stats.conflicts += 1;
+            }
+        }
+    }
+
+    fn apply_query_policy(
+        &self,
+        query: &str,
+        params: Option<&HashMap<String, QueryParam>>,
+    ) -> Result<Option<RewrittenQuery>, MgError> {
+        let policy = match &self.query_policy {
+            Some(x) => x,
+            None => return Ok(None),
+        };
+        match policy(query, params) {
+            PolicyDecision::Allow => Ok(None),
+            PolicyDecision::Deny(message) => Err(MgError::new(message)),
+            PolicyDecision::Rewrite(query, params) => Ok(Some((query, params))),
+        }
+    }
+
     /// Creates a connection to database using provided connection parameters.
     ///
     /// Returns `Connection` if connection to database is successfully established, otherwise
@@ -298,20 +1168,125 @@ impl Connection {
     /// # Ok(()) }
     /// ```
     pub fn connect(param_struct: &ConnectParams) -> Result<Connection, MgError> {
+        #[cfg(feature = "metrics")]
+        let start = Instant::now();
+        let result = Connection::connect_hosts(param_struct);
+        #[cfg(feature = "metrics")]
+        instrumentation::connect_finished(start.elapsed(), result.as_ref().err());
+        result
+    }
+
+    /// Connects to the first reachable address the host resolves to if there is a resolver or
+    /// the addresses are rotated.
+    fn connect_resolving(param_struct: &ConnectParams) -> Result<Connection, MgError> {
+        let host = match (
+            &param_struct.host,
+            &param_struct.address,
+            &param_struct.proxy,
+        ) {
+            (Some(host), None, None) => host,
+            _ => return Connection::connect_to(param_struct, None),
+        };
+        let addresses = match (&param_struct.resolver, &param_struct.host_strategy) {
+            (Some(resolver), _) => resolver(host)?,
+            (None, HostStrategy::RoundRobin(_)) => resolve_system(host, param_struct.port)?,
+            (None, HostStrategy::FirstReachable) => {
+                return Connection::connect_to(param_struct, None)
+            }
+        };
+
+        let start = param_struct.host_strategy.start(addresses.len());
+        let addresses = rotate(addresses, start);
+        let mut last_error = MgError::new(format!("Unable to resolve host {}", host));
+        for address in addresses {
+            match Connection::connect_to(param_struct, Some(&address)) {
+                Ok(connection) => return Ok(connection),
+                Err(err) => last_error = err,
+            }
+        }
+        Err(last_error)
+    }
+
+    /// Connects using `param_struct`, or to `address` instead of the host if it is provided.
+    fn connect_to(
+        param_struct: &ConnectParams,
+        address: Option<&IpAddr>,
+    ) -> Result<Connection, MgError> {
+        if param_struct.trusted_fingerprints.is_some() && param_struct.sslmode != SSLMode::Require {
+            return Err(MgError::new(String::from(
+                "trusted_fingerprints can only be checked if sslmode is SSLMode::Require",
+            )));
+        }
+        socket::check_socket_options(param_struct)?;
+        let tunnel = if Tunnel::is_needed(param_struct) {
+            let target = match (address, &param_struct.address, &param_struct.host) {
+                (Some(x), _, _) => x.to_string(),
+                (None, Some(x), _) | (None, None, Some(x)) => x.clone(),
+                (None, None, None) => {
+                    return Err(MgError::new(String::from(
+                        "host or address must be specified to use proxy",
+                    )))
+                }
+            };
+            Some(Tunnel::open(param_struct, &target)?)
+        } else {
+            None
+        };
+        // Socket options of a tunneled connection are set by the tunnel.
+        #[cfg(unix)]
+        let socket_finder = match tunnel.is_none() && socket::has_socket_options(param_struct) {
+            true => Some(SocketFinder::new(param_struct.port)),
+            false => None,
+        };
+        let mut verify_server = |info: &TrustInfo| {
+            let mut info = match &tunnel {
+                Some(tunnel) => tunnel.trust_info(info),
+                None => info.clone(),
+            };
+            info.port = param_struct.port;
+            if let Some(fingerprints) = &param_struct.trusted_fingerprints {
+                if !fingerprints
+                    .iter()
+                    .any(|x| x.eq_ignore_ascii_case(&info.fingerprint))
+                {
+                    return TrustDecision::Reject;
+                }
+            }
+            match &param_struct.trust_callback {
+                Some(callback) => callback.call(&info),
+                None => TrustDecision::Accept,
+            }
+        };
+
         Connection::init();
         let mg_session_params = unsafe { bindings::mg_session_params_make() };
-        let mut trust_callback_ptr = std::ptr::null_mut();
+        let mut trust_callback_ptr: *mut &mut dyn FnMut(&TrustInfo) -> TrustDecision =
+            std::ptr::null_mut();
         unsafe {
-            match &param_struct.host {
-                Some(x) => bindings::mg_session_params_set_host(mg_session_params, str_to_c_str(x)),
-                None => {}
-            }
-            bindings::mg_session_params_set_port(mg_session_params, param_struct.port);
-            match &param_struct.address {
-                Some(x) => {
-                    bindings::mg_session_params_set_address(mg_session_params, str_to_c_str(x))
+            if let Some(tunnel) = &tunnel {
+                bindings::mg_session_params_set_address(
+                    mg_session_params,
+                    str_to_c_str("127.0.0.1"),
+                );
+                bindings::mg_session_params_set_port(mg_session_params, tunnel.local_port());
+            } else {
+                match (&param_struct.host, address) {
+                    (_, Some(x)) => bindings::mg_session_params_set_address(
+                        mg_session_params,
+                        str_to_c_str(&x.to_string()),
+                    ),
+                    (Some(x), None) => {
+                        bindings::mg_session_params_set_host(mg_session_params, str_to_c_str(x))
+                    }
+                    (None, None) => {}
+                }
+                bindings::mg_session_params_set_port(mg_session_params, param_struct.port);
+                match &param_struct.address {
+                    Some(x) => {
+                        bindings::mg_session_params_set_address(mg_session_params, str_to_c_str(x))
+                    }
+                    None => {}
                 }
-                None => {}
             }
             match &param_struct.username {
                 Some(x) => {
@@ -350,25 +1325,28 @@ impl Connection {
                 }
                 None => {}
             }
-            match &param_struct.trust_callback {
-                Some(x) => {
-                    trust_callback_ptr = Box::into_raw(Box::new(*x));
+            if param_struct.trust_callback.is_some() || param_struct.trusted_fingerprints.is_some()
+            {
+                let callback: &mut dyn FnMut(&TrustInfo) -> TrustDecision = &mut verify_server;
+                trust_callback_ptr = Box::into_raw(Box::new(callback));
 
-                    bindings::mg_session_params_set_trust_data(
-                        mg_session_params,
-                        trust_callback_ptr as *mut ::std::os::raw::c_void,
-                    );
-                    bindings::mg_session_params_set_trust_callback(
-                        mg_session_params,
-                        Some(trust_callback_wrapper),
-                    );
-                }
-                None => {}
+                bindings::mg_session_params_set_trust_data(
+                    mg_session_params,
+                    trust_callback_ptr as *mut ::std::os::raw::c_void,
+                );
+                bindings::mg_session_params_set_trust_callback(
+                    mg_session_params,
+                    Some(trust_callback_wrapper),
+                );
             }
         }
 
         let mut mg_session: *mut bindings::mg_session = std::ptr::null_mut();
+        let start = Instant::now();
         let status = unsafe { bindings::mg_connect(mg_session_params, &mut mg_session) };
+        if wire_log_enabled(param_struct) {
+            write_wire_log(&format!("HELLO -> {}", response_name(status)), start);
+        }
         unsafe {
             bindings::mg_session_params_destroy(mg_session_params);
             if !trust_callback_ptr.is_null() {
@@ -377,64 +1355,517 @@ impl Connection {
         };
 
         if status != 0 {
-            return Err(MgError::new(read_error_message(mg_session)));
+            if let Some(tunnel) = &tunnel {
+                tunnel.close_unused();
+            }
+            let err = session_error(mg_session, status);
+            Connection::finalize();
+            return Err(err);
         }
 
-        Ok(Connection {
+        let mut connection = Connection {
             mg_session,
+            params: param_struct.clone(),
             lazy: param_struct.lazy,
             autocommit: param_struct.autocommit,
             status: ConnectionStatus::Ready,
             results_iter: None,
+            property_keys: KeyInterner::default(),
             arraysize: 1,
+            fetch_batch_size: DEFAULT_FETCH_BATCH_SIZE,
+            max_buffered_rows: None,
+            pull_size: None,
             summary: None,
-        })
+            columns: None,
+            query_policy: None,
+            param_limits: ParamLimits::default(),
+            strict_conversions: false,
+            elide_read_begin: false,
+            begin_elided: false,
+            statement_stats: None,
+            pending_statement: None,
+            conflict_stats: ConflictStats::default(),
+            current_query: None,
+            collect_query_timings: false,
+            query_timings: None,
+            drop_policy: DropPolicy::Rollback,
+            explicit_transaction: false,
+            cancellation: None,
+            prepared: HashMap::new(),
+            observer: None,
+            wire_log: wire_log_enabled(param_struct),
+            #[cfg(feature = "metrics")]
+            pulled_rows: 0,
+            client: None,
+        };
+        #[cfg(unix)]
+        if let Some(finder) = socket_finder {
+            finder.set_options(param_struct)?;
+        }
+        if let Some(database) = &param_struct.database {
+            connection.run_without_results(&use_database_query(database), None)?;
+        }
+        Ok(connection)
     }
 
     /// Fully Executes provided query but doesn't return any results even if they exist.
+    ///
+    /// Summary of the query is available using `summary` afterwards.
     pub fn execute_without_results(&mut self, query: &str) -> Result<(), MgError> {
-        match unsafe {
+        self.summary = None;
+        self.columns = None;
+        let result = match self.apply_query_policy(query, None)? {
+            Some((query, params)) => {
+                self.start_statement(&query, params.as_ref());
+                self.run_user_statement(&query, params.as_ref())
+            }
+            None => {
+                self.start_statement(query, None);
+                self.run_user_statement(query, None)
+            }
+        };
+        self.summary = result.as_ref().ok().cloned();
+        self.finish_statement(result.as_ref().err());
+        result.map(|_| ())
+    }
+
+    /// Executes provided query once for every parameter map, sending parameters in batches of
+    /// `batch_size` maps, and returns aggregated query statistics.
+    ///
+    /// Each batch is executed as `UNWIND $batch AS row <query>`, parameters of the current row
+    /// are therefore accessed through `row`, e.g. `CREATE (:Person {name: row.name})`. Results
+    /// of the query are discarded. Connection needs to be in status `Ready` or `InTransaction`.
+    /// If connection has autocommit set to false and is not in a transaction will also start a
+    /// transaction.
+    ///
+    /// Batches executed before an error occurred are not rolled back in autocommit mode.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rsmgclient::{params, Connection, MgError};
+    ///
+    /// # fn insert(connection: &mut Connection) -> Result<(), MgError> {
+    /// let rows = (0..10000).map(|id| params! { "id" => id });
+    /// let stats = connection.execute_many("CREATE (:Node {id: row.id})", rows, 1000)?;
+    /// assert_eq!(stats.nodes_created, 10000);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn execute_many<I>(
+        &mut self,
+        query: &str,
+        params: I,
+        batch_size: usize,
+    ) -> Result<QueryStats, MgError>
+    where
+        I: IntoIterator<Item = HashMap<String, QueryParam>>,
+    {
+        match self.status {
+            ConnectionStatus::Ready => {}
+            ConnectionStatus::InTransaction => {}
+            ConnectionStatus::Executing => {
+                return Err(MgError::new(String::from(
+                    "Can't call execute_many while already executing",
+                )))
+            }
+            ConnectionStatus::Fetching => {
+                return Err(MgError::new(String::from(
+                    "Can't call execute_many while fetching",
+                )))
+            }
+            ConnectionStatus::Closed => {
+                return Err(MgError::new(String::from(
+                    "Can't call execute_many if connection is closed",
+                )))
+            }
+            ConnectionStatus::Bad => {
+                return Err(MgError::new(String::from(
+                    "Can't call execute_many if connection is bad",
+                )))
+            }
+        }
+        if batch_size == 0 {
+            return Err(MgError::new(String::from("Batch size must be positive")));
+        }
+
+        if !self.autocommit && self.status == ConnectionStatus::Ready {
+            self.run_user_statement("BEGIN", None)?;
+            self.status = ConnectionStatus::InTransaction;
+        }
+        let status = self.status;
+
+        self.summary = None;
+        let query = format!("UNWIND $batch AS row {}", query);
+        let mut stats = QueryStats::default();
+        let mut params = params.into_iter().peekable();
+        while params.peek().is_some() {
+            let batch: Vec<QueryParam> = params
+                .by_ref()
+                .take(batch_size)
+                .map(QueryParam::Map)
+                .collect();
+            let mut batch_params = HashMap::new();
+            batch_params.insert(String::from("batch"), QueryParam::List(batch));
+            let summary = self.execute_batch(&query, &batch_params)?;
+            self.status = status;
+            if let Some(x) = &summary.stats {
+                stats += x;
+            }
+            self.summary = Some(summary);
+        }
+        Ok(stats)
+    }
+
+    /// Creates a node with the given label for every row of properties and returns aggregated
+    /// query statistics.
+    ///
+    /// Rows are sent in batches of 1000 using `UNWIND`. If the connection is not in a
+    /// transaction, all batches are executed in a new transaction which is committed at the end
+    /// or rolled back on error, regardless of `autocommit`. Otherwise the nodes are created as a
+    /// part of the current transaction.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rsmgclient::{params, Connection, MgError};
+    ///
+    /// # fn insert(connection: &mut Connection) -> Result<(), MgError> {
+    /// let rows = (0..10000).map(|id| params! { "id" => id });
+    /// let stats = connection.insert_nodes("Person", rows)?;
+    /// assert_eq!(stats.nodes_created, 10000);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn insert_nodes<I>(&mut self, label: &str, rows: I) -> Result<QueryStats, MgError>
+    where
+        I: IntoIterator<Item = HashMap<String, QueryParam>>,
+    {
+        let query = format!("CREATE (n:{}) SET n = row", escape_identifier(label)?);
+        self.insert_in_transaction(&query, rows)
+    }
+
+    /// Creates a relationship of the given type for every row of start node id, end node id and
+    /// properties, and returns aggregated query statistics.
+    ///
+    /// Works like `insert_nodes`. Rows referring to nodes which don't exist are skipped, which
+    /// can be detected by comparing `relationships_created` with the number of rows.
+    pub fn insert_relationships<I>(&mut self, type_: &str, rows: I) -> Result<QueryStats, MgError>
+    where
+        I: IntoIterator<Item = (i64, i64, HashMap<String, QueryParam>)>,
+    {
+        let query = format!(
+            "MATCH (a), (b) WHERE id(a) = row.start_id AND id(b) = row.end_id \
+             CREATE (a)-[r:{}]->(b) SET r = row.properties",
+            escape_identifier(type_)?
+        );
+        let rows = rows.into_iter().map(|(start_id, end_id, properties)| {
+            let mut row = HashMap::new();
+            row.insert(String::from("start_id"), QueryParam::Int(start_id));
+            row.insert(String::from("end_id"), QueryParam::Int(end_id));
+            row.insert(String::from("properties"), QueryParam::Map(properties));
+            row
+        });
+        self.insert_in_transaction(&query, rows)
+    }
+
+    fn insert_in_transaction<I>(&mut self, query: &str, rows: I) -> Result<QueryStats, MgError>
+    where
+        I: IntoIterator<Item = HashMap<String, QueryParam>>,
+    {
+        self.in_transaction(|x| x.execute_many(query, rows, INSERT_BATCH_SIZE))
+    }
+
+    /// Runs `f` as a part of the current transaction or, if the connection is not in a
+    /// transaction, in a new transaction which is committed if `f` succeeds and rolled back
+    /// otherwise.
+    fn in_transaction<T, F>(&mut self, f: F) -> Result<T, MgError>
+    where
+        F: FnOnce(&mut Connection) -> Result<T, MgError>,
+    {
+        let own_transaction = self.status == ConnectionStatus::Ready;
+        if own_transaction {
+            self.begin(&TxOptions::default())?;
+        }
+        match f(self) {
+            Ok(x) => {
+                if own_transaction {
+                    self.commit()?;
+                }
+                Ok(x)
+            }
+            Err(err) => {
+                if own_transaction && self.status == ConnectionStatus::InTransaction {
+                    let _ = self.rollback();
+                }
+                Err(err)
+            }
+        }
+    }
+
+    /// Deletes all nodes matched by `pattern` together with their relationships in batches of
+    /// `batch_size` nodes and returns aggregated query statistics.
+    ///
+    /// `pattern` is a `MATCH` pattern binding the nodes to delete to `n`, optionally followed by
+    /// a `WHERE` clause, e.g. `(n:Person)` or `(n:Person) WHERE n.age > 30`. Every batch is
+    /// deleted in a separate transaction, which keeps the memory usage of the server bounded
+    /// when deleting large amounts of data. Batches deleted before an error occurred are not
+    /// rolled back. Connection needs to be in status `Ready`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rsmgclient::{Connection, MgError};
+    ///
+    /// # fn cleanup(connection: &mut Connection) -> Result<(), MgError> {
+    /// let stats = connection.delete_all("(n:Temporary)", 10000)?;
+    /// println!("Deleted {} nodes", stats.nodes_deleted);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn delete_all(&mut self, pattern: &str, batch_size: usize) -> Result<QueryStats, MgError> {
+        self.check_ready("delete_all")?;
+        if batch_size == 0 {
+            return Err(MgError::new(String::from("Batch size must be positive")));
+        }
+
+        let query = format!(
+            "MATCH {} WITH n LIMIT {} DETACH DELETE n",
+            pattern, batch_size
+        );
+        let mut stats = QueryStats::default();
+        loop {
+            self.execute_without_results(&query)?;
+            let deleted = match self.summary.as_ref().and_then(|x| x.stats.as_ref()) {
+                Some(x) => {
+                    stats += x;
+                    x.nodes_deleted
+                }
+                None => 0,
+            };
+            if deleted == 0 {
+                return Ok(stats);
+            }
+        }
+    }
+
+    /// Merges a node with the given labels and `match_props` and sets `set_props` on it,
+    /// returning the matched or created node.
+    ///
+    /// The query is built from escaped labels and property keys, all values are passed as
+    /// parameters. Existing properties not contained in `set_props` are kept. The query is
+    /// executed like using `execute`, i.e. as a part of the current transaction if there is one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rsmgclient::{params, Connection, MgError};
+    ///
+    /// # fn upsert(connection: &mut Connection) -> Result<(), MgError> {
+    /// let node = connection.merge_node(
+    ///     &["Person"],
+    ///     &params! { "email" => "alice@example.com" },
+    ///     &params! { "name" => "Alice" },
+    /// )?;
+    /// println!("Merged node {}", node.id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn merge_node(
+        &mut self,
+        labels: &[&str],
+        match_props: &HashMap<String, QueryParam>,
+        set_props: &HashMap<String, QueryParam>,
+    ) -> Result<Node, MgError> {
+        let mut params = HashMap::new();
+        let mut pattern = String::from("n");
+        for label in labels {
+            pattern.push(':');
+            pattern.push_str(&escape_identifier(label)?);
+        }
+        let properties = merge_properties(match_props, &mut params)?;
+        let query = format!(
+            "MERGE ({}{}){} RETURN n",
+            pattern,
+            properties,
+            merge_set_clause("n", set_props, &mut params)
+        );
+        match self.merge(&query, &params)? {
+            Value::Node(x) => Ok(x),
+            x => Err(MgError::new(format!(
+                "Can't merge node, query returned {} value",
+                x.type_name()
+            ))),
+        }
+    }
+
+    /// Merges a relationship of the given type and `match_props` between nodes with ids
+    /// `start_id` and `end_id` and sets `set_props` on it, returning the matched or created
+    /// relationship.
+    ///
+    /// Works like `merge_node`. Returns error if any of the nodes doesn't exist.
+    pub fn merge_relationship(
+        &mut self,
+        start_id: i64,
+        end_id: i64,
+        type_: &str,
+        match_props: &HashMap<String, QueryParam>,
+        set_props: &HashMap<String, QueryParam>,
+    ) -> Result<Relationship, MgError> {
+        let mut params = HashMap::new();
+        params.insert(String::from("start_id"), QueryParam::Int(start_id));
+        params.insert(String::from("end_id"), QueryParam::Int(end_id));
+        let properties = merge_properties(match_props, &mut params)?;
+        let query = format!(
+            "MATCH (a), (b) WHERE id(a) = $start_id AND id(b) = $end_id \
+             MERGE (a)-[r:{}{}]->(b){} RETURN r",
+            escape_identifier(type_)?,
+            properties,
+            merge_set_clause("r", set_props, &mut params)
+        );
+        match self.merge(&query, &params)? {
+            Value::Relationship(x) => Ok(x),
+            x => Err(MgError::new(format!(
+                "Can't merge relationship, query returned {} value",
+                x.type_name()
+            ))),
+        }
+    }
+
+    fn merge(
+        &mut self,
+        query: &str,
+        params: &HashMap<String, QueryParam>,
+    ) -> Result<Value, MgError> {
+        self.execute(query, Some(params))?;
+        let records = self.fetchall()?;
+        match records
+            .into_iter()
+            .next()
+            .and_then(|x| x.values.into_iter().next())
+        {
+            Some(x) => Ok(x),
+            None => Err(MgError::new(String::from(
+                "Can't merge, matching nodes don't exist",
+            ))),
+        }
+    }
+
+    fn execute_batch(
+        &mut self,
+        query: &str,
+        params: &HashMap<String, QueryParam>,
+    ) -> Result<QuerySummary, MgError> {
+        let rewritten = self.apply_query_policy(query, Some(params))?;
+        let (query, params) = match &rewritten {
+            Some((query, params)) => (query.as_str(), params.as_ref()),
+            None => (query, Some(params)),
+        };
+        self.start_statement(query, params);
+        let result = self.run_user_statement(query, params);
+        self.summary = result.as_ref().ok().cloned();
+        self.finish_statement(result.as_ref().err());
+        result
+    }
+
+    fn run_without_results(
+        &mut self,
+        query: &str,
+        params: Option<&HashMap<String, QueryParam>>,
+    ) -> Result<QuerySummary, MgError> {
+        self.run_without_results_as(query, params, None, None)
+    }
+
+    /// Runs a statement of the user like `run_without_results`. The server applies the
+    /// impersonated user to the statement starting a transaction, it is therefore only sent if
+    /// there is no transaction yet.
+    fn run_user_statement(
+        &mut self,
+        query: &str,
+        params: Option<&HashMap<String, QueryParam>>,
+    ) -> Result<QuerySummary, MgError> {
+        let impersonated_user = match self.status {
+            ConnectionStatus::Ready => self.params.impersonated_user.clone(),
+            _ => None,
+        };
+        self.run_without_results_as(query, params, impersonated_user.as_deref(), None)
+    }
+
+    fn run_without_results_as(
+        &mut self,
+        query: &str,
+        params: Option<&HashMap<String, QueryParam>>,
+        impersonated_user: Option<&str>,
+        tx_extra: Option<&HashMap<String, QueryParam>>,
+    ) -> Result<QuerySummary, MgError> {
+        if self.status == ConnectionStatus::Closed {
+            return Err(MgError::new(String::from(
+                "Can't execute query while connection is closed",
+            )));
+        }
+        if let Some(x) = params {
+            self.param_limits.check(x)?;
+        }
+        let mg_params = match params {
+            Some(x) => hash_map_to_mg_map(x),
+            None => std::ptr::null_mut(),
+        };
+        let c_query = CString::new(query).unwrap();
+        let extra = make_run_extra(impersonated_user, tx_extra);
+        let start = Instant::now();
+        let status = unsafe {
             bindings::mg_session_run(
                 self.mg_session,
-                str_to_c_str(query),
-                std::ptr::null(),
-                std::ptr::null_mut(),
+                c_query.as_ptr(),
+                mg_params,
+                extra,
                 std::ptr::null_mut(),
                 std::ptr::null_mut(),
             )
-        } {
+        };
+        destroy_mg_map(extra);
+        destroy_mg_map(mg_params);
+        self.log_wire(|| run_message(query, params, status), start);
+        match status {
             0 => {
                 self.status = ConnectionStatus::Executing;
             }
-            _ => {
+            status => {
                 self.status = ConnectionStatus::Bad;
-                return Err(MgError::new(read_error_message(self.mg_session)));
+                return Err(self.statement_error(query, status));
             }
         }
 
-        match unsafe { bindings::mg_session_pull(self.mg_session, std::ptr::null_mut()) } {
+        let start = Instant::now();
+        let status = unsafe { bindings::mg_session_pull(self.mg_session, std::ptr::null_mut()) };
+        self.log_wire(|| String::from("PULL n=-1"), start);
+        match status {
             0 => {
                 self.status = ConnectionStatus::Fetching;
             }
-            _ => {
+            status => {
                 self.status = ConnectionStatus::Bad;
-                return Err(MgError::new(read_error_message(self.mg_session)));
+                return Err(self.statement_error(query, status));
             }
         }
 
         loop {
             let mut result = std::ptr::null_mut();
-            match unsafe { bindings::mg_session_fetch(self.mg_session, &mut result) } {
+            let start = Instant::now();
+            let status = unsafe { bindings::mg_session_fetch(self.mg_session, &mut result) };
+            self.log_wire(|| fetch_message(status), start);
+            match status {
                 1 => {
                     continue;
                 }
                 0 => {
                     self.status = ConnectionStatus::Ready;
-                    return Ok(());
+                    let mg_summary = unsafe { bindings::mg_result_summary(result) };
+                    return Ok(QuerySummary::from_map(mg_map_to_hash_map(mg_summary)));
                 }
-                _ => {
+                status => {
                     self.status = ConnectionStatus::Bad;
-                    return Err(MgError::new(read_error_message(self.mg_session)));
+                    return Err(self.statement_error(query, status));
                 }
             };
         }
@@ -452,6 +1883,52 @@ impl Connection {
         &mut self,
         query: &str,
         params: Option<&HashMap<String, QueryParam>>,
+    ) -> Result<Vec<String>, MgError> {
+        self.execute_with_options(query, params, &ExecuteOptions::default())
+    }
+
+    /// Executes provided query like `execute`, applying per-query `options`.
+    ///
+    /// # Examples
+    ///
+    /// Running a query with the privileges of another user, aborted by the server if it doesn't
+    /// finish in 30 seconds.
+    /// ```no_run
+    /// use rsmgclient::{ConnectParams, Connection, ExecuteOptions, MgError};
+    /// use std::time::Duration;
+    ///
+    /// # fn main() -> Result<(), MgError> {
+    /// let mut connection = Connection::connect(&ConnectParams {
+    ///     host: Some(String::from("localhost")),
+    ///     username: Some(String::from("admin")),
+    ///     password: Some(String::from("admin")),
+    ///     ..Default::default()
+    /// })?;
+    /// let options = ExecuteOptions {
+    ///     impersonated_user: Some(String::from("alice")),
+    ///     timeout: Some(Duration::from_secs(30)),
+    ///     ..Default::default()
+    /// };
+    /// connection.execute_with_options("MATCH (n) RETURN count(n)", None, &options)?;
+    /// connection.fetchall()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn execute_with_options(
+        &mut self,
+        query: &str,
+        params: Option<&HashMap<String, QueryParam>>,
+        options: &ExecuteOptions,
+    ) -> Result<Vec<String>, MgError> {
+        self.execute_inner(query, params, options, true)
+    }
+
+    fn execute_inner(
+        &mut self,
+        query: &str,
+        params: Option<&HashMap<String, QueryParam>>,
+        options: &ExecuteOptions,
+        apply_query_policy: bool,
     ) -> Result<Vec<String>, MgError> {
         match self.status {
             ConnectionStatus::Ready => {}
@@ -478,50 +1955,111 @@ impl Connection {
             }
         }
 
+        let rewritten = match apply_query_policy {
+            true => self.apply_query_policy(query, params)?,
+            false => None,
+        };
+        let (query, params) = match &rewritten {
+            Some((query, params)) => (query.as_str(), params.as_ref()),
+            None => (query, params),
+        };
+        if let Some(x) = params {
+            self.param_limits.check(x)?;
+        }
+
+        let impersonated_user = options
+            .impersonated_user
+            .clone()
+            .or_else(|| self.params.impersonated_user.clone());
+
+        // Transaction options are sent with the statement starting the transaction, i.e. with
+        // `BEGIN` or with the query itself if it runs in an implicit transaction.
+        let mut tx_extra = options.to_tx_extra();
+        self.add_cancellation_token(&mut tx_extra);
+        let mut query_tx_extra = match self.status {
+            ConnectionStatus::Ready => Some(&tx_extra),
+            _ => None,
+        };
+        self.begin_elided = false;
         if !self.autocommit && self.status == ConnectionStatus::Ready {
-            match self.execute_without_results("BEGIN") {
-                Ok(()) => self.status = ConnectionStatus::InTransaction,
-                Err(err) => return Err(err),
+            if self.elide_read_begin && is_read_only_query(query) {
+                self.begin_elided = true;
+            } else {
+                match self.run_without_results_as(
+                    "BEGIN",
+                    None,
+                    impersonated_user.as_deref(),
+                    Some(&tx_extra),
+                ) {
+                    Ok(_) => self.status = ConnectionStatus::InTransaction,
+                    Err(err) => return Err(err),
+                }
+                query_tx_extra = None;
             }
         }
+        self.pull_size = options.n_pull;
 
         self.summary = None;
+        self.columns = None;
+        self.current_query = Some(query.to_string());
+        self.track_cancellable_query(Some(query));
+        self.start_statement(query, params);
+        self.query_timings = match self.collect_query_timings {
+            true => Some(QueryTimings::default()),
+            false => None,
+        };
 
         let c_query = CString::new(query).unwrap();
         let mg_params = match params {
             Some(x) => hash_map_to_mg_map(x),
             None => std::ptr::null_mut(),
         };
+        // Like transaction options, the impersonated user is only sent with the statement
+        // starting the transaction.
+        let extra = make_run_extra(
+            query_tx_extra.and(impersonated_user.as_deref()),
+            query_tx_extra,
+        );
         let mut columns = std::ptr::null();
+        let start = Instant::now();
         let status = unsafe {
             bindings::mg_session_run(
                 self.mg_session,
                 c_query.as_ptr(),
                 mg_params,
-                std::ptr::null_mut(),
+                extra,
                 &mut columns,
                 std::ptr::null_mut(),
             )
         };
+        self.record_timing(|x| x.run += start.elapsed());
+        destroy_mg_map(extra);
+        destroy_mg_map(mg_params);
+        self.log_wire(|| run_message(query, params, status), start);
 
         if status != 0 {
             self.status = ConnectionStatus::Bad;
-            return Err(MgError::new(read_error_message(self.mg_session)));
+            let err = self.statement_error(query, status);
+            self.finish_statement(Some(&err));
+            return Err(err);
         }
 
         self.status = ConnectionStatus::Executing;
+        let columns = parse_columns(columns);
+        self.columns = Some(Arc::new(columns.clone()));
 
         if !self.lazy {
             match self.pull_and_fetch_all() {
                 Ok(x) => self.results_iter = Some(x.into_iter()),
                 Err(x) => {
                     self.status = ConnectionStatus::Bad;
+                    self.finish_statement(Some(&x));
                     return Err(x);
                 }
             }
         }
 
-        Ok(parse_columns(columns))
+        Ok(columns)
     }
 
     /// Returns next row of query results or None if there is no more data available.
@@ -529,73 +2067,106 @@ impl Connection {
     /// Returns error if connection is not in `Executing` status or if there was an error while
     /// pulling record from database.
     pub fn fetchone(&mut self) -> Result<Option<Record>, MgError> {
+        self.fetchone_pulling(self.pull_size.unwrap_or(self.fetch_batch_size))
+    }
+
+    /// Returns next row like `fetchone`, requesting `pull_size` records if a new batch of
+    /// records needs to be requested in lazy mode.
+    fn fetchone_pulling(&mut self, pull_size: u32) -> Result<Option<Record>, MgError> {
+        self.check_fetching("fetchone")?;
+        match self.lazy {
+            true => match self.next_row(pull_size)? {
+                Some(row) => Ok(Some(self.convert_row(row)?)),
+                None => Ok(None),
+            },
+            false => match self.next_record() {
+                Some(x) => Ok(Some(x)),
+                None => {
+                    self.status = self.finished_status();
+                    Ok(None)
+                }
+            },
+        }
+    }
+
+    /// Returns next row of query results borrowed from the connection, or None if there is no
+    /// more data available.
+    ///
+    /// Unlike `fetchone`, strings, lists and maps of the row aren't copied, see `RecordRef`. The
+    /// row can be used until the next call on the connection. Only available in lazy mode.
+    ///
+    /// Returns error if connection is not in `Executing` status, if it isn't lazy or if there was
+    /// an error while pulling record from database.
+    pub fn fetchone_ref(&mut self) -> Result<Option<RecordRef<'_>>, MgError> {
+        self.check_fetching("fetchone_ref")?;
+        if !self.lazy {
+            return Err(MgError::new(String::from(
+                "Can't call fetchone_ref if connection is not lazy",
+            )));
+        }
+        let row = match self.next_row(self.pull_size.unwrap_or(self.fetch_batch_size))? {
+            Some(x) => x,
+            None => return Ok(None),
+        };
+        let columns = self.columns.as_deref().map_or(&[][..], |x| x.as_slice());
+        Ok(Some(unsafe { RecordRef::new(columns, row) }))
+    }
+
+    fn check_fetching(&self, method: &str) -> Result<(), MgError> {
         match self.status {
             ConnectionStatus::Ready => {
-                return Err(MgError::new(String::from(
-                    "Can't call fetchone while ready",
-                )))
-            }
-            ConnectionStatus::InTransaction => {
-                return Err(MgError::new(String::from(
-                    "Can't call fetchone while in transaction",
-                )))
-            }
-            ConnectionStatus::Executing => {}
-            ConnectionStatus::Fetching => {}
-            ConnectionStatus::Closed => {
-                return Err(MgError::new(String::from(
-                    "Can't call fetchone if connection is closed",
-                )))
-            }
-            ConnectionStatus::Bad => {
-                return Err(MgError::new(String::from(
-                    "Can't call fetchone if connection is bad",
-                )))
+                Err(MgError::new(format!("Can't call {} while ready", method)))
             }
+            ConnectionStatus::InTransaction => Err(MgError::new(format!(
+                "Can't call {} while in transaction",
+                method
+            ))),
+            ConnectionStatus::Executing | ConnectionStatus::Fetching => Ok(()),
+            ConnectionStatus::Closed => Err(MgError::new(format!(
+                "Can't call {} if connection is closed",
+                method
+            ))),
+            ConnectionStatus::Bad => Err(MgError::new(format!(
+                "Can't call {} if connection is bad",
+                method
+            ))),
         }
+    }
 
-        match self.lazy {
-            true => {
-                if self.status == ConnectionStatus::Executing {
-                    match self.pull(1) {
-                        Ok(_) => {
-                            // The state update is alredy done in the pull.
-                        }
-                        Err(err) => {
-                            self.status = ConnectionStatus::Bad;
-                            return Err(err);
-                        }
-                    }
-                }
-                match self.fetch()? {
-                    (Some(x), None) => Ok(Some(x)),
-                    (Some(x), Some(has_more)) => {
-                        if has_more {
-                            self.status = ConnectionStatus::Executing;
-                        }
-                        Ok(Some(x))
+    /// Returns next received row in lazy mode, requesting `pull_size` records if a new batch of
+    /// records needs to be requested.
+    fn next_row(&mut self, pull_size: u32) -> Result<Option<*const bindings::mg_list>, MgError> {
+        loop {
+            if self.status == ConnectionStatus::Executing {
+                match self.pull(i64::from(pull_size)) {
+                    Ok(_) => {
+                        // The state update is alredy done in the pull.
                     }
-                    (None, _) => {
-                        self.status = if self.autocommit {
-                            ConnectionStatus::Ready
-                        } else {
-                            ConnectionStatus::InTransaction
-                        };
-                        Ok(None)
+                    Err(err) => {
+                        self.status = ConnectionStatus::Bad;
+                        return Err(err);
                     }
                 }
             }
-            false => match self.next_record() {
-                Some(x) => Ok(Some(x)),
-                None => {
-                    self.status = if self.autocommit {
-                        ConnectionStatus::Ready
-                    } else {
-                        ConnectionStatus::InTransaction
-                    };
-                    Ok(None)
+            match self.fetch_row()? {
+                (Some(x), _) => return Ok(Some(x)),
+                // The batch is exhausted, request the next one.
+                (None, Some(true)) => self.status = ConnectionStatus::Executing,
+                (None, _) => {
+                    self.status = self.finished_status();
+                    return Ok(None);
                 }
-            },
+            }
+        }
+    }
+
+    fn finished_status(&self) -> ConnectionStatus {
+        if self.explicit_transaction {
+            ConnectionStatus::InTransaction
+        } else if self.autocommit || self.begin_elided {
+            ConnectionStatus::Ready
+        } else {
+            ConnectionStatus::InTransaction
         }
     }
 
@@ -612,6 +2183,9 @@ impl Connection {
     /// The number of rows to fetch is specified either by `size` or `arraysize` attribute,
     /// `size`(if provided) overrides `arraysize`.
     ///
+    /// In lazy mode, records which weren't received yet are requested from the server at once,
+    /// regardless of `fetch_batch_size`.
+    ///
     /// Returns error if connection is not in `Executing` status or if there was an error while
     /// pulling record from database.
     pub fn fetchmany(&mut self, size: Option<u32>) -> Result<Vec<Record>, MgError> {
@@ -621,8 +2195,8 @@ impl Connection {
         };
 
         let mut vec = Vec::new();
-        for _i in 0..size {
-            match self.fetchone() {
+        for i in 0..size {
+            match self.fetchone_pulling(size - i) {
                 Ok(record) => match record {
                     Some(x) => vec.push(x),
                     None => break,
@@ -640,15 +2214,72 @@ impl Connection {
     /// pulling record from database.
     pub fn fetchall(&mut self) -> Result<Vec<Record>, MgError> {
         let mut vec = Vec::new();
-        loop {
-            match self.fetchone() {
-                Ok(record) => match record {
-                    Some(x) => vec.push(x),
-                    None => break,
-                },
-                Err(err) => return Err(err),
+        self.fetchall_into(&mut vec)?;
+        Ok(vec)
+    }
+
+    /// Appends all(remaining) rows of query results to `records` and returns the number of
+    /// appended rows.
+    ///
+    /// Unlike `fetchall`, this allows reusing the same buffer, and its capacity, for results of
+    /// many queries. In non-lazy mode the buffer is grown once to fit all the results. Rows
+    /// fetched before an error are kept in `records`.
+    ///
+    /// Returns error if connection is not in `Executing` status or if there was an error while
+    /// pulling record from database.
+    pub fn fetchall_into(&mut self, records: &mut Vec<Record>) -> Result<usize, MgError> {
+        if let Some(iter) = self.results_iter.as_ref() {
+            records.reserve(iter.len());
+        }
+        let mut count = 0;
+        while let Some(record) = self.fetchone()? {
+            records.push(record);
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Returns all(remaining) rows of query results as JSON, see `export::records_to_json` for
+    /// the format.
+    ///
+    /// Returns error if connection is not in `Executing` status or if there was an error while
+    /// pulling record from database.
+    #[cfg(feature = "json")]
+    pub fn fetchall_json(&mut self) -> Result<serde_json::Value, MgError> {
+        let records = self.fetchall()?;
+        let columns = self.columns.clone().unwrap_or_default();
+        Ok(crate::export::records_to_json(&columns, &records))
+    }
+
+    /// Returns all(remaining) rows of query results while reporting progress.
+    ///
+    /// `progress` is called with the number of fetched rows and the time elapsed since the call
+    /// after every `every` rows and once more after the last row, unless it was just reported.
+    /// If `every` is 0 progress is only reported at the end.
+    ///
+    /// Returns error if connection is not in `Executing` status or if there was an error while
+    /// pulling record from database.
+    pub fn fetch_with_progress<F>(
+        &mut self,
+        every: u64,
+        mut progress: F,
+    ) -> Result<Vec<Record>, MgError>
+    where
+        F: FnMut(u64, Duration),
+    {
+        let start = Instant::now();
+        let mut vec = Vec::new();
+        let mut fetched = 0;
+        while let Some(x) = self.fetchone()? {
+            vec.push(x);
+            fetched += 1;
+            if every != 0 && fetched % every == 0 {
+                progress(fetched, start.elapsed());
             }
         }
+        if every == 0 || fetched % every != 0 {
+            progress(fetched, start.elapsed());
+        }
         Ok(vec)
     }
 
@@ -678,6 +2309,7 @@ impl Connection {
             }
         }
 
+        let start = Instant::now();
         let pull_status = match n {
             0 => unsafe { bindings::mg_session_pull(self.mg_session, std::ptr::null_mut()) },
             _ => unsafe {
@@ -694,7 +2326,7 @@ impl Connection {
                         "Unable to make pull map integer value.",
                     )));
                 }
-                if bindings::mg_map_insert(mg_map, "n".as_ptr() as *const c_char, mg_int) != 0 {
+                if bindings::mg_map_insert(mg_map, b"n\0".as_ptr() as *const c_char, mg_int) != 0 {
                     self.status = ConnectionStatus::Bad;
                     bindings::mg_map_destroy(mg_map);
                     bindings::mg_value_destroy(mg_int);
@@ -707,21 +2339,32 @@ impl Connection {
                 status
             },
         };
+        self.record_timing(|x| {
+            x.pull += start.elapsed();
+            x.pulls += 1;
+        });
+        self.log_wire(
+            || match n {
+                0 => String::from("PULL n=-1"),
+                n => format!("PULL n={}", n),
+            },
+            start,
+        );
 
         match pull_status {
             0 => {
                 self.status = ConnectionStatus::Fetching;
                 Ok(())
             }
-            _ => {
+            status => {
                 self.status = ConnectionStatus::Bad;
-                Err(MgError::new(read_error_message(self.mg_session)))
+                Err(self.current_statement_error(status))
             }
         }
     }
 
-    /// Maybe returns Record and has_more flag.
-    fn fetch(&mut self) -> Result<(Option<Record>, Option<bool>), MgError> {
+    /// Maybe returns received row, valid until the next fetch, and has_more flag.
+    fn fetch_row(&mut self) -> Result<(Option<*const bindings::mg_list>, Option<bool>), MgError> {
         match self.status {
             ConnectionStatus::Ready => {
                 return Err(MgError::new(String::from("Can't call fetch while ready")))
@@ -750,35 +2393,88 @@ impl Connection {
         }
 
         let mut mg_result: *mut bindings::mg_result = std::ptr::null_mut();
+        let start = Instant::now();
         let fetch_status = unsafe { bindings::mg_session_fetch(self.mg_session, &mut mg_result) };
+        self.record_timing(|x| {
+            x.fetch += start.elapsed();
+            x.fetches += 1;
+        });
+        self.log_wire(|| fetch_message(fetch_status), start);
         match fetch_status {
-            1 => unsafe {
-                let row = bindings::mg_result_row(mg_result);
-                Ok((
-                    Some(Record {
-                        values: mg_list_to_vec(row),
-                    }),
-                    None,
-                ))
-            },
+            1 => {
+                #[cfg(feature = "metrics")]
+                {
+                    self.pulled_rows += 1;
+                }
+                Ok((Some(unsafe { bindings::mg_result_row(mg_result) }), None))
+            }
             0 => unsafe {
+                #[cfg(feature = "metrics")]
+                self.report_pulled_rows();
                 let mg_summary = bindings::mg_result_summary(mg_result);
                 let mg_has_more = bindings::mg_map_at(mg_summary, str_to_c_str("has_more"));
                 let has_more = bindings::mg_value_bool(mg_has_more) != 0;
-                self.summary = Some(mg_map_to_hash_map(mg_summary));
+                self.summary = Some(QuerySummary::from_map(mg_map_to_hash_map(mg_summary)));
+                if !has_more {
+                    self.current_query = None;
+                    self.track_cancellable_query(None);
+                    self.finish_statement(None);
+                }
                 Ok((None, Some(has_more)))
             },
-            _ => Err(MgError::new(read_error_message(self.mg_session))),
+            status => {
+                #[cfg(feature = "metrics")]
+                self.report_pulled_rows();
+                let err = self.current_statement_error(status);
+                self.finish_statement(Some(&err));
+                Err(err)
+            }
+        }
+    }
+
+    #[cfg(feature = "metrics")]
+    fn report_pulled_rows(&mut self) {
+        instrumentation::rows_fetched(std::mem::take(&mut self.pulled_rows));
+    }
+
+    fn convert_row(&mut self, row: *const bindings::mg_list) -> Result<Record, MgError> {
+        let start = Instant::now();
+        let values = unsafe {
+            match self.strict_conversions {
+                true => try_mg_list_to_vec(row, &mut self.property_keys),
+                false => Ok(mg_list_to_vec(row, &mut self.property_keys)),
+            }
+        };
+        self.record_timing(|x| x.conversion += start.elapsed());
+        Ok(Record::new(
+            self.columns.clone().unwrap_or_default(),
+            values?,
+        ))
+    }
+
+    /// Returns error if another record can't be buffered after `buffered` records, see
+    /// `set_max_buffered_rows`.
+    fn check_buffered_rows(&self, buffered: usize) -> Result<(), MgError> {
+        match self.max_buffered_rows {
+            Some(max) if buffered >= max => Err(MgError::new(format!(
+                "Query returned more than {} records, which can't be buffered in non-lazy mode",
+                max
+            ))),
+            _ => Ok(()),
         }
     }
 
+    #[cfg(not(feature = "rayon"))]
     fn pull_and_fetch_all(&mut self) -> Result<Vec<Record>, MgError> {
         let mut res = Vec::new();
         match self.pull(0) {
             Ok(_) => loop {
-                let x = self.fetch()?;
+                let x = self.fetch_row()?;
                 match x {
-                    (Some(x), _) => res.push(x),
+                    (Some(row), _) => {
+                        self.check_buffered_rows(res.len())?;
+                        res.push(self.convert_row(row)?);
+                    }
                     (None, _) => break,
                 }
             },
@@ -787,11 +2483,179 @@ impl Connection {
         Ok(res)
     }
 
+    /// Returns the database selected using `ConnectParams::database` or `use_database`, `None`
+    /// if the default database of the server is used.
+    pub fn database(&self) -> Option<&str> {
+        self.params.database.as_deref()
+    }
+
+    /// Returns information about the server, which can be used to detect supported features.
+    ///
+    /// The version is queried using `SHOW VERSION` outside of a transaction. The server agent,
+    /// negotiated Bolt version and connection id are not available, mgclient doesn't expose
+    /// them. Connection needs to be in status `Ready`.
+    pub fn server_info(&mut self) -> Result<ServerInfo, MgError> {
+        let value = self
+            .read_all_autocommit("server_info", "SHOW VERSION")?
+            .into_iter()
+            .next()
+            .and_then(|x| x.values.into_iter().next());
+        match value {
+            Some(Value::String(version)) => Ok(ServerInfo { version }),
+            _ => Err(MgError::new(String::from(
+                "Unexpected result of SHOW VERSION",
+            ))),
+        }
+    }
+
+    /// Switches to another database of a multi-tenant Memgraph instance by running
+    /// `USE DATABASE`.
+    ///
+    /// Connection needs to be in status `Ready`. The statement is passed to the query policy.
+    /// Connections created using `try_clone` afterwards use the same database.
+    pub fn use_database(&mut self, name: &str) -> Result<(), MgError> {
+        self.check_ready("use_database")?;
+        self.execute_without_results(&use_database_query(name))?;
+        self.params.database = Some(name.to_string());
+        Ok(())
+    }
+
+    /// Sets isolation level of transactions executed on this connection by running
+    /// `SET SESSION TRANSACTION ISOLATION LEVEL`.
+    ///
+    /// Connection needs to be in status `Ready`. The statement is passed to the query policy.
+    pub fn set_isolation_level(&mut self, level: IsolationLevel) -> Result<(), MgError> {
+        self.check_ready("set_isolation_level")?;
+        self.execute_without_results(&format!(
+            "SET SESSION TRANSACTION ISOLATION LEVEL {}",
+            level.to_cypher()
+        ))
+    }
+
+    /// Sets storage mode of the database by running `STORAGE MODE`.
+    ///
+    /// Connection needs to be in status `Ready`. The statement is passed to the query policy.
+    /// The server refuses to change the storage mode while there are other active transactions.
+    pub fn set_storage_mode(&mut self, mode: StorageMode) -> Result<(), MgError> {
+        self.check_ready("set_storage_mode")?;
+        self.execute_without_results(&format!("STORAGE MODE {}", mode.to_cypher()))
+    }
+
+    fn check_ready(&self, method: &str) -> Result<(), MgError> {
+        let reason = match self.status {
+            ConnectionStatus::Ready => return Ok(()),
+            ConnectionStatus::InTransaction => "while in transaction",
+            ConnectionStatus::Executing => "while executing",
+            ConnectionStatus::Fetching => "while fetching",
+            ConnectionStatus::Closed => "while connection is closed",
+            ConnectionStatus::Bad => "while connection is bad",
+        };
+        Err(MgError::new(format!("Can't call {} {}", method, reason)))
+    }
+
+    /// Starts a transaction configured by `options`.
+    ///
+    /// Queries executed afterwards are part of the transaction until it is finished using
+    /// `commit` or `rollback`, regardless of `autocommit`. If autocommit is set to false,
+    /// `execute` otherwise starts a transaction with server defaults before running a query.
+    /// Connection needs to be in status `Ready`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rsmgclient::{params, AccessMode, Connection, MgError, TxOptions};
+    /// use std::time::Duration;
+    ///
+    /// # fn run(connection: &mut Connection) -> Result<(), MgError> {
+    /// connection.begin(&TxOptions {
+    ///     timeout: Some(Duration::from_secs(10)),
+    ///     metadata: Some(params! { "job" => "nightly-import" }),
+    ///     mode: Some(AccessMode::Write),
+    /// })?;
+    /// connection.execute("CREATE (:Import)", None)?;
+    /// connection.fetchall()?;
+    /// connection.commit()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn begin(&mut self, options: &TxOptions) -> Result<(), MgError> {
+        match self.status {
+            ConnectionStatus::Ready => {}
+            ConnectionStatus::InTransaction => {
+                return Err(MgError::new(String::from(
+                    "Can't begin while already in transaction",
+                )))
+            }
+            ConnectionStatus::Executing => {
+                return Err(MgError::new(String::from("Can't begin while executing")))
+            }
+            ConnectionStatus::Fetching => {
+                return Err(MgError::new(String::from("Can't begin while fetching")))
+            }
+            ConnectionStatus::Closed => {
+                return Err(MgError::new(String::from(
+                    "Can't begin while connection is closed",
+                )))
+            }
+            ConnectionStatus::Bad => {
+                return Err(MgError::new(String::from(
+                    "Can't begin while connection is bad",
+                )))
+            }
+        }
+
+        let mut extra = options.to_extra();
+        self.add_cancellation_token(&mut extra);
+        if let Some(x) = &self.params.impersonated_user {
+            extra.insert(String::from("imp_user"), QueryParam::String(x.clone()));
+        }
+        let extra = hash_map_to_mg_map(&extra);
+        let start = Instant::now();
+        let status = unsafe { bindings::mg_session_begin_transaction(self.mg_session, extra) };
+        unsafe { bindings::mg_map_destroy(extra) };
+        self.log_wire(|| format!("BEGIN -> {}", response_name(status)), start);
+        if status != 0 {
+            self.status = ConnectionStatus::Bad;
+            return Err(session_error(self.mg_session, status));
+        }
+        self.status = ConnectionStatus::InTransaction;
+        self.explicit_transaction = true;
+        Ok(())
+    }
+
+    /// Finishes a transaction started using `begin`.
+    fn finish_explicit_transaction(&mut self, commit: bool) -> Result<(), MgError> {
+        let mut result = std::ptr::null_mut();
+        let start = Instant::now();
+        let status = unsafe {
+            match commit {
+                true => bindings::mg_session_commit_transaction(self.mg_session, &mut result),
+                false => bindings::mg_session_rollback_transaction(self.mg_session, &mut result),
+            }
+        };
+        self.log_wire(
+            || {
+                let message = if commit { "COMMIT" } else { "ROLLBACK" };
+                format!("{} -> {}", message, response_name(status))
+            },
+            start,
+        );
+        self.explicit_transaction = false;
+        if status != 0 {
+            self.status = ConnectionStatus::Bad;
+            let query = if commit { "COMMIT" } else { "ROLLBACK" };
+            return Err(self.statement_error(query, status));
+        }
+        self.status = ConnectionStatus::Ready;
+        Ok(())
+    }
+
     /// Commit any pending transaction to the database.
     ///
     /// Returns error if there are queries that didn't finish executing.
     ///
     /// If `autocommit` is set to true or there is no pending transaction this method does nothing.
+    /// Transactions started using `begin` are committed regardless of `autocommit`.
     pub fn commit(&mut self) -> Result<(), MgError> {
         match self.status {
             ConnectionStatus::Ready => {}
@@ -814,13 +2678,19 @@ impl Connection {
             }
         }
 
+        if self.explicit_transaction {
+            self.finish_explicit_transaction(true)?;
+            self.conflict_stats.consecutive_conflicts = 0;
+            return Ok(());
+        }
         if self.autocommit || self.status != ConnectionStatus::InTransaction {
             return Ok(());
         }
 
-        match self.execute_without_results("COMMIT") {
-            Ok(()) => {
+        match self.run_without_results("COMMIT", None) {
+            Ok(_) => {
                 self.status = ConnectionStatus::Ready;
+                self.conflict_stats.consecutive_conflicts = 0;
                 Ok(())
             }
             Err(err) => Err(err),
@@ -832,6 +2702,7 @@ impl Connection {
     /// Returns error if there are queries that didn't finish executing.
     ///
     /// If `autocommit` is set to true or there is no pending transaction this method does nothing.
+    /// Transactions started using `begin` are rolled back regardless of `autocommit`.
     pub fn rollback(&mut self) -> Result<(), MgError> {
         match self.status {
             ConnectionStatus::Ready => {
@@ -858,12 +2729,15 @@ impl Connection {
             }
         }
 
+        if self.explicit_transaction {
+            return self.finish_explicit_transaction(false);
+        }
         if self.autocommit {
             return Ok(());
         }
 
-        match self.execute_without_results("ROLLBACK") {
-            Ok(()) => {
+        match self.run_without_results("ROLLBACK", None) {
+            Ok(_) => {
                 self.status = ConnectionStatus::Ready;
                 Ok(())
             }
@@ -871,22 +2745,367 @@ impl Connection {
         }
     }
 
+    /// Opens a new connection using the same `ConnectParams` this connection was created with.
+    ///
+    /// The new connection has its own session and shares no state with this connection, it can
+    /// be used for parallel queries, e.g. reads while this connection is in a transaction.
+    /// Current values of `lazy`, `autocommit`, `arraysize`, `fetch_batch_size`, parameter limits,
+    /// strict conversions, drop policy and prepared queries are copied, while the query policy
+    /// and collected statistics are not.
+    ///
+    /// Note that `ConnectParams::trust_callback` is called again while connecting.
+    pub fn try_clone(&self) -> Result<Connection, MgError> {
+        let mut connection = Connection::connect(&self.params)?;
+        connection.lazy = self.lazy;
+        connection.autocommit = self.autocommit;
+        connection.arraysize = self.arraysize;
+        connection.fetch_batch_size = self.fetch_batch_size;
+        connection.param_limits = self.param_limits;
+        connection.strict_conversions = self.strict_conversions;
+        connection.elide_read_begin = self.elide_read_begin;
+        connection.drop_policy = self.drop_policy;
+        connection.prepared = self.prepared.clone();
+        connection.client = self.client.clone();
+        Ok(connection)
+    }
+
+    /// Executes a read-only query, fetches all of its records and retries on transient errors.
+    ///
+    /// The query is retried if it fails due to a conflict with a concurrent transaction or if
+    /// the connection to the server is lost. Before every retry the session is replaced with a
+    /// new one, connected using the same `ConnectParams`, so the connection is always left in
+    /// `Ready` status when the read succeeds. If autocommit is set to false the read is
+    /// committed. Other errors, and the last error after `policy.max_retries` retries, are
+    /// returned to the caller.
+    ///
+    /// Connection needs to be in status `Ready` since a retry can't be done inside of a
+    /// transaction. Since retrying queries which modify data is not safe, queries not
+    /// recognized as read-only (see `set_elide_read_begin`) are rejected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rsmgclient::{Connection, MgError, RetryPolicy};
+    ///
+    /// # fn count(connection: &mut Connection) -> Result<(), MgError> {
+    /// let records = connection.execute_read_with_retry(
+    ///     "MATCH (n) RETURN count(n)",
+    ///     None,
+    ///     &RetryPolicy::default(),
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn execute_read_with_retry(
+        &mut self,
+        query: &str,
+        params: Option<&HashMap<String, QueryParam>>,
+        policy: &RetryPolicy,
+    ) -> Result<Vec<Record>, MgError> {
+        match self.status {
+            ConnectionStatus::Ready => {}
+            ConnectionStatus::InTransaction => {
+                return Err(MgError::new(String::from(
+                    "Can't call execute_read_with_retry while in transaction",
+                )))
+            }
+            ConnectionStatus::Executing => {
+                return Err(MgError::new(String::from(
+                    "Can't call execute_read_with_retry while already executing",
+                )))
+            }
+            ConnectionStatus::Fetching => {
+                return Err(MgError::new(String::from(
+                    "Can't call execute_read_with_retry while fetching",
+                )))
+            }
+            ConnectionStatus::Closed => {
+                return Err(MgError::new(String::from(
+                    "Can't call execute_read_with_retry while connection is closed",
+                )))
+            }
+            ConnectionStatus::Bad => {
+                return Err(MgError::new(String::from(
+                    "Can't call execute_read_with_retry while connection is bad",
+                )))
+            }
+        }
+        if !is_read_only_query(query) {
+            return Err(MgError::new(format!(
+                "Query `{}` is not recognized as read-only and can't be retried",
+                query
+            )));
+        }
+
+        let mut retries = 0;
+        loop {
+            let reconnected = match retries {
+                0 => Ok(()),
+                _ => self.reconnect(),
+            };
+            let err = match reconnected {
+                // Failing to connect is also considered transient.
+                Err(err) => err,
+                Ok(()) => match self.read_all(query, params) {
+                    Ok(records) => return Ok(records),
+                    Err(err) if err.is_conflict() || self.is_session_broken() => err,
+                    Err(err) => return Err(err),
+                },
+            };
+            if retries == policy.max_retries {
+                return Err(err);
+            }
+            retries += 1;
+            thread::sleep(policy.backoff(retries));
+        }
+    }
+
+    fn read_all(
+        &mut self,
+        query: &str,
+        params: Option<&HashMap<String, QueryParam>>,
+    ) -> Result<Vec<Record>, MgError> {
+        self.execute(query, params)?;
+        let records = self.fetchall()?;
+        self.commit()?;
+        Ok(records)
+    }
+
+    /// Runs a query which can't be executed in a transaction, e.g. `SHOW VERSION`, with
+    /// autocommit temporarily enabled. Connection needs to be in status `Ready`.
+    fn read_all_autocommit(&mut self, method: &str, query: &str) -> Result<Vec<Record>, MgError> {
+        self.check_ready(method)?;
+        let autocommit = self.autocommit;
+        self.autocommit = true;
+        let records = self.read_all(query, None);
+        self.autocommit = autocommit;
+        records
+    }
+
+    fn is_session_broken(&self) -> bool {
+        unsafe { bindings::mg_session_status(self.mg_session) == MG_SESSION_BAD }
+    }
+
+    /// Checks that the connection is usable by running `RETURN 1` and returns the round-trip time.
+    ///
+    /// Connection needs to be in status `Ready` or `InTransaction`, its status and open
+    /// transaction are not changed. The query is not passed to the query policy nor included in
+    /// statistics. Returns error if the connection is not in one of these statuses or if the
+    /// server doesn't respond, in which case the connection becomes `Bad`.
+    pub fn ping(&mut self) -> Result<Duration, MgError> {
+        match self.status {
+            ConnectionStatus::Ready => {}
+            ConnectionStatus::InTransaction => {}
+            ConnectionStatus::Executing => {
+                return Err(MgError::new(String::from("Can't ping while executing")))
+            }
+            ConnectionStatus::Fetching => {
+                return Err(MgError::new(String::from("Can't ping while fetching")))
+            }
+            ConnectionStatus::Closed => {
+                return Err(MgError::new(String::from(
+                    "Can't ping while connection is closed",
+                )))
+            }
+            ConnectionStatus::Bad => {
+                return Err(MgError::new(String::from(
+                    "Can't ping while connection is bad",
+                )))
+            }
+        }
+
+        let status = self.status;
+        let start = Instant::now();
+        self.run_without_results("RETURN 1", None)?;
+        self.status = status;
+        Ok(start.elapsed())
+    }
+
+    /// Recovers the connection so it can be used again, e.g. after a failed query made it `Bad`.
+    ///
+    /// After a query fails on the server, the session is already reset by mgclient and only the
+    /// status of the connection is restored. Pending transaction of a connection in status
+    /// `InTransaction` is rolled back. Otherwise, e.g. if the connection to the server was lost or
+    /// there are records which were not fetched, the session is replaced with a new one connected
+    /// using the same `ConnectParams`. In all cases the connection is left in status `Ready`,
+    /// discarding any unfetched records and open transaction.
+    ///
+    /// Returns error if the connection is closed or if connecting to the server fails.
+    pub fn reset(&mut self) -> Result<(), MgError> {
+        match self.status {
+            ConnectionStatus::Ready => Ok(()),
+            ConnectionStatus::InTransaction => match self.rollback() {
+                Ok(()) => Ok(()),
+                Err(_) => self.reconnect(),
+            },
+            // The session is only checked for bad connections, a closed connection has none.
+            ConnectionStatus::Bad
+                if unsafe { bindings::mg_session_status(self.mg_session) } == MG_SESSION_READY =>
+            {
+                self.status = ConnectionStatus::Ready;
+                self.clear_statement();
+                Ok(())
+            }
+            ConnectionStatus::Executing | ConnectionStatus::Fetching | ConnectionStatus::Bad => {
+                self.reconnect()
+            }
+            ConnectionStatus::Closed => Err(MgError::new(String::from(
+                "Can't reset a closed connection",
+            ))),
+        }
+    }
+
+    /// Replaces the session with a new one, connected using the same parameters.
+    fn reconnect(&mut self) -> Result<(), MgError> {
+        let mut connection = Connection::connect(&self.params)?;
+        // The old session is destroyed together with `connection`.
+        std::mem::swap(&mut self.mg_session, &mut connection.mg_session);
+        self.status = ConnectionStatus::Ready;
+        self.clear_statement();
+        // Nothing from the old session describes the new one.
+        self.summary = None;
+        self.columns = None;
+        self.conflict_stats = ConflictStats::default();
+        Ok(())
+    }
+
+    fn clear_statement(&mut self) {
+        self.explicit_transaction = false;
+        self.results_iter = None;
+        self.begin_elided = false;
+        self.pending_statement = None;
+        self.current_query = None;
+        self.track_cancellable_query(None);
+        #[cfg(feature = "metrics")]
+        self.report_pulled_rows();
+    }
+
     /// Closes the connection.
     ///
-    /// The connection will be unusable from this point forward. Any operation on connection will
-    /// return error.
+    /// The session with the server is terminated and its resources are released immediately.
+    /// Pending results of an executing query are discarded and an open transaction is rolled back
+    /// by the server. The connection will be unusable from this point forward. Any operation on
+    /// connection will return error.
     pub fn close(&mut self) {
-        match self.status {
-            ConnectionStatus::Ready => self.status = ConnectionStatus::Closed,
-            ConnectionStatus::InTransaction => self.status = ConnectionStatus::Closed,
-            ConnectionStatus::Executing => panic!("Can't close while executing"),
-            ConnectionStatus::Fetching => panic!("Can't close while fetching"),
-            ConnectionStatus::Closed => {}
-            ConnectionStatus::Bad => panic!("Can't closed a bad connection"),
+        if let Err(err) = self.try_close() {
+            panic!("{}", err)
+        }
+    }
+
+    /// Closes the connection like `close`.
+    ///
+    /// Closing succeeds in every status, closing an already closed connection does nothing.
+    pub fn try_close(&mut self) -> Result<(), MgError> {
+        if self.status == ConnectionStatus::Closed {
+            return Ok(());
         }
+        self.clear_statement();
+        unsafe { bindings::mg_session_destroy(self.mg_session) };
+        self.mg_session = std::ptr::null_mut();
+        self.status = ConnectionStatus::Closed;
+        Ok(())
+    }
+}
+
+/// Returns `USE DATABASE` query for the database, escaping its name.
+fn use_database_query(name: &str) -> String {
+    format!("USE DATABASE `{}`", name.replace('`', "``"))
+}
+
+/// Clauses a read-only query can start with, after an optional `EXPLAIN` or `PROFILE`.
+const READ_CLAUSES: &[&str] = &["MATCH", "OPTIONAL", "WITH", "UNWIND", "RETURN", "SHOW"];
+
+/// Clauses and keywords which can make a query modify data or schema.
+const WRITE_KEYWORDS: &[&str] = &[
+    "CREATE",
+    "MERGE",
+    "SET",
+    "DELETE",
+    "DETACH",
+    "REMOVE",
+    "DROP",
+    "LOAD",
+    "FOREACH",
+    "CALL",
+    "BEGIN",
+    "COMMIT",
+    "ROLLBACK",
+    "GRANT",
+    "REVOKE",
+    "DENY",
+    "TRIGGER",
+    "STREAM",
+    "STORAGE",
+    "FREE",
+    "LOCK",
+    "UNLOCK",
+    "ANALYZE",
+    "DUMP",
+    "SNAPSHOT",
+    "REGISTER",
+    "REPLICA",
+    "TERMINATE",
+    "USE",
+];
+
+/// Returns whether the query is known to only read data.
+///
+/// Queries are treated as writes unless they are positively identified as reads, i.e. they
+/// start with a clause from `READ_CLAUSES` and contain no keyword from `WRITE_KEYWORDS`.
+/// Literals and comments are ignored, any other occurrence of a write keyword, even as an
+/// identifier, makes the query not read-only.
+fn is_read_only_query(query: &str) -> bool {
+    let normalized = normalize_query(query);
+    let is_keyword = |keywords: &[&str], word: &str| {
+        keywords
+            .iter()
+            .any(|keyword| word.eq_ignore_ascii_case(keyword))
+    };
+    let mut words = normalized
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|x| !x.is_empty())
+        .skip_while(|x| is_keyword(&["EXPLAIN", "PROFILE"], x));
+    match words.next() {
+        Some(x) if is_keyword(READ_CLAUSES, x) => !words.any(|x| is_keyword(WRITE_KEYWORDS, x)),
+        _ => false,
     }
 }
 
+/// Returns property map of a `MERGE` pattern, e.g. ` {`name`: $match_0}`, adding the values to
+/// `params`, or nothing if there are no properties. Properties are sorted by their keys so that
+/// the query text doesn't depend on the order of the map.
+fn merge_properties(
+    props: &HashMap<String, QueryParam>,
+    params: &mut HashMap<String, QueryParam>,
+) -> Result<String, MgError> {
+    let mut props: Vec<_> = props.iter().collect();
+    props.sort_by(|x, y| x.0.cmp(y.0));
+    let mut entries = Vec::with_capacity(props.len());
+    for (i, (key, value)) in props.into_iter().enumerate() {
+        let param = format!("match_{}", i);
+        entries.push(format!("{}: ${}", escape_identifier(key)?, param));
+        params.insert(param, value.clone());
+    }
+    if entries.is_empty() {
+        return Ok(String::new());
+    }
+    Ok(format!(" {{{}}}", entries.join(", ")))
+}
+
+/// Returns ` SET variable += $set` adding the properties to `params`, or nothing if there are no
+/// properties to set.
+fn merge_set_clause(
+    variable: &str,
+    props: &HashMap<String, QueryParam>,
+    params: &mut HashMap<String, QueryParam>,
+) -> String {
+    if props.is_empty() {
+        return String::new();
+    }
+    params.insert(String::from("set"), QueryParam::Map(props.clone()));
+    format!(" SET {} += $set", variable)
+}
+
 fn parse_columns(mg_list: *const bindings::mg_list) -> Vec<String> {
     let size = unsafe { bindings::mg_list_size(mg_list) };
     let mut columns: Vec<String> = Vec::new();
@@ -904,25 +3123,98 @@ extern "C" fn trust_callback_wrapper(
     fingerprint: *const ::std::os::raw::c_char,
     fun_raw: *mut ::std::os::raw::c_void,
 ) -> ::std::os::raw::c_int {
-    let fun: &mut &mut dyn Fn(&String, &String, &String, &String) -> i32 = unsafe {
-        &mut *(fun_raw
-            as *mut &mut dyn for<'r, 's, 't0, 't1> std::ops::Fn(
-                &'r std::string::String,
-                &'s std::string::String,
-                &'t0 std::string::String,
-                &'t1 std::string::String,
-            ) -> i32)
+    let fun = unsafe { &mut *(fun_raw as *mut &mut dyn FnMut(&TrustInfo) -> TrustDecision) };
+    let info = unsafe {
+        TrustInfo {
+            hostname: c_string_to_string(host, None),
+            ip: c_string_to_string(ip_address, None),
+            // mgclient doesn't pass the port, it is set by `Connection::connect_to`.
+            port: 0,
+            key_type: c_string_to_string(key_type, None),
+            fingerprint: c_string_to_string(fingerprint, None),
+        }
     };
-
-    unsafe {
-        fun(
-            &c_string_to_string(host, None),
-            &c_string_to_string(ip_address, None),
-            &c_string_to_string(key_type, None),
-            &c_string_to_string(fingerprint, None),
-        ) as std::os::raw::c_int
+    match fun(&info) {
+        TrustDecision::Accept => 0,
+        TrustDecision::Reject => 1,
     }
 }
 
+mod script;
+
+mod stream;
+
+mod sync;
+pub use sync::SyncConnection;
+
+mod tofu;
+pub use tofu::SSLTrust;
+
+mod cancel;
+pub use cancel::CancellationHandle;
+use cancel::CancellationState;
+
+#[cfg(feature = "metrics")]
+mod instrumentation;
+
+#[cfg(feature = "rayon")]
+mod parallel;
+
+mod introspection;
+pub use introspection::{ConfigEntry, StorageInfo};
+
+mod observer;
+#[cfg(feature = "log")]
+pub use observer::LogObserver;
+pub use observer::QueryObserver;
+
+mod plan;
+pub use plan::{OperatorProfile, PlanOperator, ProfiledPlan, QueryPlan};
+
+mod wire_log;
+pub use wire_log::WIRE_LOG_ENV;
+use wire_log::{redact_query, response_name, wire_log_enabled, write_wire_log};
+
+mod prepared;
+use prepared::PreparedQuery;
+
+mod procedure;
+pub use procedure::ProcedureCall;
+
+mod replication;
+pub use replication::{ReplicaInfo, ReplicaSpec, ReplicationMode, ReplicationRole};
+
+mod transactions;
+pub use transactions::TransactionInfo;
+
+mod hosts;
+use hosts::{resolve_system, rotate};
+pub use hosts::{HostStrategy, RoundRobinCounter};
+
+mod env;
+
+#[cfg(feature = "config")]
+mod config;
+
+mod client;
+pub use client::Client;
+use client::ClientState;
+
+mod routing;
+pub use routing::{ReplicaAddress, RoutedConnection};
+
+mod proxy;
+use proxy::Tunnel;
+pub use proxy::{ProxyConfig, ProxyKind};
+
+mod socket;
+#[cfg(unix)]
+use socket::SocketFinder;
+
+#[cfg(feature = "iterator")]
+mod rows;
+#[cfg(feature = "iterator")]
+pub use rows::Rows;
+
 #[cfg(test)]
 mod tests;
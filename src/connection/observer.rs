@@ -0,0 +1,136 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::Connection;
+use crate::error::MgError;
+use crate::summary::QuerySummary;
+use crate::value::QueryParam;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Observer notified about queries executed on a connection, e.g. to log them or measure
+/// their execution time, see `Connection::set_observer`.
+///
+/// All methods do nothing by default.
+///
+/// # Examples
+///
+/// ```
+/// use rsmgclient::{QueryObserver, QuerySummary};
+/// use std::time::Duration;
+///
+/// struct SlowQueryObserver {
+///     query: String,
+/// }
+///
+/// impl QueryObserver for SlowQueryObserver {
+///     fn on_execute(
+///         &mut self,
+///         query: &str,
+///         _params: Option<&std::collections::HashMap<String, rsmgclient::QueryParam>>,
+///     ) {
+///         self.query = query.to_string();
+///     }
+///
+///     fn on_complete(&mut self, duration: Duration, _summary: Option<&QuerySummary>) {
+///         if duration > Duration::from_secs(1) {
+///             eprintln!("Slow query ({:?}): {}", duration, self.query);
+///         }
+///     }
+/// }
+/// ```
+pub trait QueryObserver: Send {
+    /// Called before a query is sent to the server, after the query policy is applied.
+    fn on_execute(&mut self, _query: &str, _params: Option<&HashMap<String, QueryParam>>) {}
+
+    /// Called when a query finishes, i.e. all of its records are received, with the time
+    /// elapsed since the query was sent.
+    fn on_complete(&mut self, _duration: Duration, _summary: Option<&QuerySummary>) {}
+
+    /// Called when a query fails.
+    fn on_error(&mut self, _error: &MgError) {}
+}
+
+/// Observer logging queries using the `log` crate, enabled by the `log` feature.
+///
+/// Executed queries, their execution times and `QuerySummary::run_id`s are logged with the
+/// configured level, failures with level `Warn`, all with target `rsmgclient`. Query parameters
+/// are not logged since they may contain sensitive data, neither are literals of queries setting
+/// passwords, which are replaced by `?`.
+#[cfg(feature = "log")]
+pub struct LogObserver {
+    level: log::Level,
+    query: Option<String>,
+}
+
+#[cfg(feature = "log")]
+impl LogObserver {
+    /// Makes an observer logging queries with the given level.
+    pub fn new(level: log::Level) -> LogObserver {
+        LogObserver { level, query: None }
+    }
+}
+
+#[cfg(feature = "log")]
+impl Default for LogObserver {
+    /// Makes an observer logging queries with level `Debug`.
+    fn default() -> Self {
+        LogObserver::new(log::Level::Debug)
+    }
+}
+
+#[cfg(feature = "log")]
+impl QueryObserver for LogObserver {
+    fn on_execute(&mut self, query: &str, _params: Option<&HashMap<String, QueryParam>>) {
+        let query = super::wire_log::redact_query(query);
+        log::log!(target: "rsmgclient", self.level, "Executing query: {}", query);
+        self.query = Some(query.into_owned());
+    }
+
+    fn on_complete(&mut self, duration: Duration, summary: Option<&QuerySummary>) {
+        let query = self.query.take().unwrap_or_default();
+        match summary.and_then(|x| x.run_id.as_deref()) {
+            Some(run_id) => log::log!(
+                target: "rsmgclient",
+                self.level,
+                "Query {} finished in {:?}: {}",
+                run_id,
+                duration,
+                query
+            ),
+            None => log::log!(
+                target: "rsmgclient",
+                self.level,
+                "Query finished in {:?}: {}",
+                duration,
+                query
+            ),
+        }
+    }
+
+    fn on_error(&mut self, error: &MgError) {
+        let query = self.query.take().unwrap_or_default();
+        log::warn!(target: "rsmgclient", "Query failed: {}: {}", error, query);
+    }
+}
+
+impl Connection {
+    /// Sets the observer notified about every query executed using this connection, replacing
+    /// the previous one. `None` removes the observer.
+    ///
+    /// Internal queries, e.g. `BEGIN` and `COMMIT`, are not observed.
+    pub fn set_observer(&mut self, observer: Option<Box<dyn QueryObserver>>) {
+        self.observer = observer;
+    }
+}
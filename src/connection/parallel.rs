@@ -0,0 +1,78 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parallel conversion of results in non-lazy mode, enabled by the `rayon` feature.
+
+use super::Connection;
+use crate::bindings;
+use crate::error::MgError;
+use crate::value::{mg_list_to_vec, try_mg_list_to_vec, KeyInterner, Record};
+use rayon::prelude::*;
+use std::time::Instant;
+
+/// Number of rows converted as soon as they are received. Rows after them are buffered and
+/// converted in parallel once all of them are received, which only pays off for large results.
+const SERIAL_ROWS: usize = 1024;
+
+/// Copy of a received row, which stays valid after the next fetch.
+struct BufferedRow(*mut bindings::mg_list);
+
+// The copy is owned by the row and only read while it's converted.
+unsafe impl Send for BufferedRow {}
+unsafe impl Sync for BufferedRow {}
+
+impl Drop for BufferedRow {
+    fn drop(&mut self) {
+        unsafe { bindings::mg_list_destroy(self.0) };
+    }
+}
+
+impl Connection {
+    pub(super) fn pull_and_fetch_all(&mut self) -> Result<Vec<Record>, MgError> {
+        self.pull(0)?;
+        let mut records = Vec::new();
+        let mut rows = Vec::new();
+        while let (Some(row), _) = self.fetch_row()? {
+            self.check_buffered_rows(records.len() + rows.len())?;
+            if records.len() < SERIAL_ROWS {
+                records.push(self.convert_row(row)?);
+            } else {
+                rows.push(BufferedRow(unsafe { bindings::mg_list_copy(row) }));
+            }
+        }
+        records.append(&mut self.convert_rows(&rows)?);
+        Ok(records)
+    }
+
+    /// Converts rows on the rayon thread pool. Every worker uses its own property key interner.
+    fn convert_rows(&mut self, rows: &[BufferedRow]) -> Result<Vec<Record>, MgError> {
+        let start = Instant::now();
+        let strict = self.strict_conversions;
+        let columns = self.columns.clone().unwrap_or_default();
+        let records = rows
+            .par_iter()
+            .map_init(KeyInterner::default, |keys, row| {
+                let values = unsafe {
+                    match strict {
+                        true => try_mg_list_to_vec(row.0, keys)?,
+                        false => mg_list_to_vec(row.0, keys),
+                    }
+                };
+                Ok(Record::new(columns.clone(), values))
+            })
+            .collect();
+        self.record_timing(|x| x.conversion += start.elapsed());
+        records
+    }
+}
@@ -0,0 +1,234 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::Connection;
+use crate::error::MgError;
+use crate::value::{QueryParam, Record, Value};
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
+
+/// Execution plan of a query, see `Connection::explain` and `Connection::profile`.
+///
+/// Displaying the plan renders the operator tree with one operator per line, children indented
+/// below their parent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryPlan {
+    /// The last operator of the plan, producing the results, e.g. `Produce`.
+    pub root: PlanOperator,
+}
+
+/// Execution plan of a query with profiling information, see `Connection::profile`. Every
+/// operator has `profile` set.
+pub type ProfiledPlan = QueryPlan;
+
+/// Operator of a query plan.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanOperator {
+    /// Name of the operator, e.g. `ScanAllByLabel`.
+    pub name: String,
+    /// Details of the operator as printed by the server, e.g. `(n :Person)`.
+    pub details: String,
+    /// Profiling information, only available in plans returned by `Connection::profile`.
+    pub profile: Option<OperatorProfile>,
+    /// Operators producing the input of this operator.
+    pub children: Vec<PlanOperator>,
+}
+
+/// Profiling information of an operator.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OperatorProfile {
+    /// Number of times the operator produced a row.
+    pub hits: u64,
+    /// Share of the total execution time spent in the operator, in percent.
+    pub relative_time: f64,
+    /// Time spent in the operator.
+    pub absolute_time: Duration,
+}
+
+impl QueryPlan {
+    /// Parses rows returned by `EXPLAIN`, with the operator in the first column, or by
+    /// `PROFILE`, with the operator followed by hits, relative time and absolute time.
+    pub(crate) fn from_records(records: &[Record]) -> Result<QueryPlan, MgError> {
+        // Operators are listed from the root, inputs of an operator follow it. Operators of
+        // additional inputs, e.g. of `Cartesian`, are listed in a branch indented by `|`.
+        let mut operators: Vec<(usize, bool, PlanOperator)> = Vec::new();
+        let mut new_branch = false;
+        for record in records {
+            let text = match record.values.first() {
+                Some(Value::String(x)) => x,
+                _ => return Err(MgError::new(String::from("Unexpected query plan row"))),
+            };
+            let text = text.trim();
+            let depth = text.chars().take_while(|c| *c == '|' || *c == ' ').count();
+            let rest = &text[depth..];
+            let rest = match rest.strip_prefix('*') {
+                Some(x) => x.trim(),
+                // Start of a branch, e.g. `|\`.
+                None => {
+                    new_branch = true;
+                    continue;
+                }
+            };
+            let depth = text[..depth].matches('|').count();
+            let (name, details) = match rest.find(' ') {
+                Some(i) => (&rest[..i], rest[i..].trim()),
+                None => (rest, ""),
+            };
+            let profile = match record.values.len() {
+                1 => None,
+                _ => Some(parse_profile(&record.values[1..])?),
+            };
+            operators.push((
+                depth,
+                new_branch,
+                PlanOperator {
+                    name: name.to_string(),
+                    details: details.to_string(),
+                    profile,
+                    children: Vec::new(),
+                },
+            ));
+            new_branch = false;
+        }
+
+        let mut iter = operators.into_iter().peekable();
+        match build_operator(&mut iter) {
+            Some(root) => Ok(QueryPlan { root }),
+            None => Err(MgError::new(String::from("Query plan is empty"))),
+        }
+    }
+}
+
+/// Builds operator from the next row and its inputs from the following rows: first the
+/// branches one level deeper, then the rest of the chain on the same level. A chain ends where
+/// a new branch starts.
+fn build_operator<I>(operators: &mut std::iter::Peekable<I>) -> Option<PlanOperator>
+where
+    I: Iterator<Item = (usize, bool, PlanOperator)>,
+{
+    let (depth, _, mut operator) = operators.next()?;
+    while matches!(operators.peek(), Some((x, _, _)) if *x > depth) {
+        if let Some(child) = build_operator(operators) {
+            operator.children.push(child);
+        }
+    }
+    if matches!(operators.peek(), Some((x, false, _)) if *x == depth) {
+        if let Some(child) = build_operator(operators) {
+            operator.children.push(child);
+        }
+    }
+    Some(operator)
+}
+
+fn parse_profile(values: &[Value]) -> Result<OperatorProfile, MgError> {
+    let number = |value: Option<&Value>| -> Option<f64> {
+        match value? {
+            Value::Int(x) => Some(*x as f64),
+            Value::Float(x) => Some(*x),
+            Value::String(x) => x
+                .trim()
+                .trim_end_matches(|c: char| c.is_alphabetic() || c == '%')
+                .trim()
+                .parse()
+                .ok(),
+            _ => None,
+        }
+    };
+    let (hits, relative_time, absolute_time) = match (
+        number(values.first()),
+        number(values.get(1)),
+        // Times out of range of `Duration`, e.g. `inf ms`, are rejected like unparsable ones.
+        number(values.get(2)).and_then(|x| Duration::try_from_secs_f64(x.max(0.0) / 1000.0).ok()),
+    ) {
+        (Some(x), Some(y), Some(z)) => (x, y, z),
+        _ => return Err(MgError::new(String::from("Unexpected query profile row"))),
+    };
+    Ok(OperatorProfile {
+        hits: hits as u64,
+        relative_time,
+        absolute_time,
+    })
+}
+
+impl fmt::Display for QueryPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn write_operator(
+            f: &mut fmt::Formatter<'_>,
+            operator: &PlanOperator,
+            depth: usize,
+        ) -> fmt::Result {
+            write!(f, "{:indent$}* {}", "", operator.name, indent = depth * 2)?;
+            if !operator.details.is_empty() {
+                write!(f, " {}", operator.details)?;
+            }
+            if let Some(profile) = &operator.profile {
+                write!(
+                    f,
+                    " [hits: {}, {:.2} %, {:.3} ms]",
+                    profile.hits,
+                    profile.relative_time,
+                    profile.absolute_time.as_secs_f64() * 1000.0
+                )?;
+            }
+            writeln!(f)?;
+            for child in &operator.children {
+                write_operator(f, child, depth + 1)?;
+            }
+            Ok(())
+        }
+        write_operator(f, &self.root, 0)
+    }
+}
+
+impl Connection {
+    /// Returns execution plan of the query by running it prefixed with `EXPLAIN`.
+    ///
+    /// Query plans can't be requested in a transaction so the query is run with autocommit
+    /// temporarily enabled. Connection needs to be in status `Ready`.
+    pub fn explain(
+        &mut self,
+        query: &str,
+        params: Option<&HashMap<String, QueryParam>>,
+    ) -> Result<QueryPlan, MgError> {
+        self.read_plan("explain", &format!("EXPLAIN {}", query), params)
+    }
+
+    /// Runs the query prefixed with `PROFILE` and returns its execution plan with the number
+    /// of hits and time spent in every operator. Results of the query are discarded.
+    ///
+    /// Like `explain`, the query is run with autocommit temporarily enabled so any changes it
+    /// makes are committed. Connection needs to be in status `Ready`.
+    pub fn profile(
+        &mut self,
+        query: &str,
+        params: Option<&HashMap<String, QueryParam>>,
+    ) -> Result<ProfiledPlan, MgError> {
+        self.read_plan("profile", &format!("PROFILE {}", query), params)
+    }
+
+    fn read_plan(
+        &mut self,
+        method: &str,
+        query: &str,
+        params: Option<&HashMap<String, QueryParam>>,
+    ) -> Result<QueryPlan, MgError> {
+        self.check_ready(method)?;
+        let autocommit = self.autocommit;
+        self.autocommit = true;
+        let records = self.read_all(query, params);
+        self.autocommit = autocommit;
+        QueryPlan::from_records(&records?)
+    }
+}
@@ -0,0 +1,136 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{Connection, ExecuteOptions};
+use crate::cypher::{query_parameters, split_statements};
+use crate::error::MgError;
+use crate::value::QueryParam;
+use std::collections::HashMap;
+
+/// Query prepared using `Connection::prepare`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PreparedQuery {
+    query: String,
+    parameters: Vec<String>,
+}
+
+impl PreparedQuery {
+    pub(crate) fn new(name: &str, query: &str) -> Result<PreparedQuery, MgError> {
+        if split_statements(query).len() != 1 {
+            return Err(MgError::new(format!(
+                "Prepared query {} needs to contain exactly one statement",
+                name
+            )));
+        }
+        Ok(PreparedQuery {
+            query: query.to_string(),
+            parameters: query_parameters(query),
+        })
+    }
+
+    /// Checks that all parameters used by the query are provided.
+    pub(crate) fn check_params(
+        &self,
+        name: &str,
+        params: Option<&HashMap<String, QueryParam>>,
+    ) -> Result<(), MgError> {
+        let missing: Vec<&str> = self
+            .parameters
+            .iter()
+            .filter(|x| !matches!(params, Some(params) if params.contains_key(*x)))
+            .map(|x| x.as_str())
+            .collect();
+        match missing.is_empty() {
+            true => Ok(()),
+            false => Err(MgError::new(format!(
+                "Parameters ${} of prepared query {} are not provided",
+                missing.join(", $"),
+                name
+            ))),
+        }
+    }
+}
+
+impl Connection {
+    /// Prepares a query which can then be executed by its name using `execute_prepared`.
+    ///
+    /// The query needs to contain a single statement. Parameters used by the query are recorded
+    /// so that executions missing some of them fail without contacting the server. Preparing a
+    /// query under an existing name replaces it. The query policy, see `set_query_policy`, is
+    /// called on every execution with its parameters.
+    ///
+    /// Queries are cached on the client only, the server caches plans of queries by their
+    /// text, which stays the same for all executions of a prepared query.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rsmgclient::{params, Connection, MgError};
+    ///
+    /// # fn run(connection: &mut Connection) -> Result<(), MgError> {
+    /// connection.prepare("person_by_name", "MATCH (n:Person {name: $name}) RETURN n")?;
+    /// for name in ["Alice", "Bob"].iter() {
+    ///     let params = params! { "name" => *name };
+    ///     connection.execute_prepared("person_by_name", Some(&params))?;
+    ///     connection.fetchall()?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn prepare(&mut self, name: &str, query: &str) -> Result<(), MgError> {
+        let prepared = PreparedQuery::new(name, query)?;
+        self.prepared.insert(name.to_string(), prepared);
+        Ok(())
+    }
+
+    /// Executes a query prepared using `prepare` like `execute`.
+    ///
+    /// Returns error if there is no query prepared under `name`, if some of its parameters are
+    /// not provided or if the query policy denies or rewrites the query, since the prepared
+    /// query would then no longer be the one executed.
+    pub fn execute_prepared(
+        &mut self,
+        name: &str,
+        params: Option<&HashMap<String, QueryParam>>,
+    ) -> Result<Vec<String>, MgError> {
+        let prepared = match self.prepared.get(name) {
+            Some(x) => x,
+            None => {
+                return Err(MgError::new(format!(
+                    "There is no prepared query named {}",
+                    name
+                )))
+            }
+        };
+        prepared.check_params(name, params)?;
+        let query = prepared.query.clone();
+        if self.apply_query_policy(&query, params)?.is_some() {
+            return Err(MgError::new(format!(
+                "Prepared query {} can't be rewritten by the query policy",
+                name
+            )));
+        }
+        self.execute_inner(&query, params, &ExecuteOptions::default(), false)
+    }
+
+    /// Removes a query prepared using `prepare` and returns whether it existed.
+    pub fn unprepare(&mut self, name: &str) -> bool {
+        self.prepared.remove(name).is_some()
+    }
+
+    /// Returns text of the query prepared under `name`.
+    pub fn prepared_query(&self, name: &str) -> Option<&str> {
+        self.prepared.get(name).map(|x| x.query.as_str())
+    }
+}
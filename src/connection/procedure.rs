@@ -0,0 +1,137 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::Connection;
+use crate::cypher::escape_identifier;
+use crate::error::MgError;
+use crate::value::{FromRecord, QueryParam, Record};
+use std::collections::HashMap;
+
+/// Call of a query procedure prepared using `Connection::call`.
+///
+/// The procedure is executed when the yielded columns are chosen using one of the `yield_*`
+/// methods.
+pub struct ProcedureCall<'a> {
+    connection: &'a mut Connection,
+    procedure: String,
+    params: HashMap<String, QueryParam>,
+}
+
+impl<'a> ProcedureCall<'a> {
+    /// Executes the procedure yielding all of its columns.
+    pub fn yield_all(self) -> Result<Vec<Record>, MgError> {
+        self.run(&[])
+    }
+
+    /// Executes the procedure yielding the given columns, in the given order.
+    pub fn yield_columns(self, columns: &[&str]) -> Result<Vec<Record>, MgError> {
+        self.run(columns)
+    }
+
+    /// Executes the procedure yielding `T::COLUMNS` and converts every yielded row into `T`.
+    pub fn yield_as<T: FromRecord>(self) -> Result<Vec<T>, MgError> {
+        self.run(T::COLUMNS)?
+            .into_iter()
+            .map(T::from_record)
+            .collect()
+    }
+
+    fn run(self, columns: &[&str]) -> Result<Vec<Record>, MgError> {
+        let query = call_query(&self.procedure, self.params.len(), columns)?;
+        self.connection.execute(&query, Some(&self.params))?;
+        self.connection.fetchall()
+    }
+}
+
+/// Returns `CALL` query of the procedure with arguments `$arg0`, `$arg1`, ... yielding the
+/// given columns or all columns if there are none.
+pub(crate) fn call_query(
+    procedure: &str,
+    arg_count: usize,
+    columns: &[&str],
+) -> Result<String, MgError> {
+    let procedure = procedure
+        .split('.')
+        .map(escape_identifier)
+        .collect::<Result<Vec<String>, MgError>>()?
+        .join(".");
+    let args: Vec<String> = (0..arg_count).map(|i| format!("$arg{}", i)).collect();
+    let columns = match columns.is_empty() {
+        true => String::from("*"),
+        false => columns
+            .iter()
+            .map(|x| escape_identifier(x))
+            .collect::<Result<Vec<String>, MgError>>()?
+            .join(", "),
+    };
+    Ok(format!(
+        "CALL {}({}) YIELD {}",
+        procedure,
+        args.join(", "),
+        columns
+    ))
+}
+
+impl Connection {
+    /// Prepares a call of the query procedure with the given name, e.g. `mg.procedures`.
+    ///
+    /// Arguments are passed as query parameters. The procedure is executed like using `execute`
+    /// once the yielded columns are chosen on the returned `ProcedureCall`, i.e. as a part of
+    /// the current transaction if there is one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rsmgclient::{Connection, FromRecord, MgError, QueryParam, Record};
+    /// use std::convert::TryFrom;
+    ///
+    /// struct Procedure {
+    ///     name: String,
+    /// }
+    ///
+    /// impl FromRecord for Procedure {
+    ///     const COLUMNS: &'static [&'static str] = &["name"];
+    ///
+    ///     fn from_record(record: Record) -> Result<Self, MgError> {
+    ///         let (name,) = <(String,)>::try_from(record)?;
+    ///         Ok(Procedure { name })
+    ///     }
+    /// }
+    ///
+    /// # fn run(connection: &mut Connection) -> Result<(), MgError> {
+    /// let procedures: Vec<Procedure> = connection.call("mg.procedures", vec![])?.yield_as()?;
+    /// let scores = connection
+    ///     .call("pagerank.get", vec![QueryParam::Int(100)])?
+    ///     .yield_columns(&["node", "rank"])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn call(
+        &mut self,
+        procedure: &str,
+        args: Vec<QueryParam>,
+    ) -> Result<ProcedureCall<'_>, MgError> {
+        call_query(procedure, 0, &[])?;
+        let params = args
+            .into_iter()
+            .enumerate()
+            .map(|(i, x)| (format!("arg{}", i), x))
+            .collect();
+        Ok(ProcedureCall {
+            connection: self,
+            procedure: procedure.to_string(),
+            params,
+        })
+    }
+}
@@ -0,0 +1,395 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tunneling connections through SOCKS5 and HTTP proxies.
+//!
+//! mgclient opens its own TCP connection to the address it is given and can't use a proxy.
+//! When the connection goes through a proxy, it is therefore opened by the driver and mgclient
+//! connects to a local port forwarding to it, like `ssh -L`.
+
+#[cfg(unix)]
+use super::socket::is_own_connection;
+use super::socket::set_socket_options;
+use super::{ConnectParams, TrustInfo};
+use crate::error::MgError;
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::net::{IpAddr, Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::thread;
+
+/// Protocol spoken with a proxy, see `ProxyConfig`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProxyKind {
+    /// SOCKS5 proxy, host names are resolved by the proxy.
+    Socks5,
+    /// HTTP proxy supporting the `CONNECT` method.
+    HttpConnect,
+}
+
+/// Proxy through which the connection is tunneled, see `ConnectParams::proxy`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use rsmgclient::{ConnectParams, Connection, ProxyConfig};
+///
+/// let connect_params = ConnectParams {
+///     host: Some(String::from("memgraph.internal")),
+///     proxy: Some(ProxyConfig::socks5("bastion.example.com", 1080)),
+///     ..Default::default()
+/// };
+/// let connection = Connection::connect(&connect_params);
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct ProxyConfig {
+    pub kind: ProxyKind,
+    /// Host name or IP address of the proxy.
+    pub host: String,
+    pub port: u16,
+    /// User name used to authenticate to the proxy, with SOCKS5 username/password
+    /// authentication or HTTP basic authentication.
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+impl ProxyConfig {
+    /// Creates configuration of a SOCKS5 proxy without authentication.
+    pub fn socks5(host: &str, port: u16) -> ProxyConfig {
+        ProxyConfig {
+            kind: ProxyKind::Socks5,
+            host: host.to_string(),
+            port,
+            username: None,
+            password: None,
+        }
+    }
+
+    /// Creates configuration of an HTTP `CONNECT` proxy without authentication.
+    pub fn http(host: &str, port: u16) -> ProxyConfig {
+        ProxyConfig {
+            kind: ProxyKind::HttpConnect,
+            ..ProxyConfig::socks5(host, port)
+        }
+    }
+
+    /// Sets credentials used to authenticate to the proxy.
+    pub fn with_credentials(mut self, username: &str, password: &str) -> ProxyConfig {
+        self.username = Some(username.to_string());
+        self.password = Some(password.to_string());
+        self
+    }
+}
+
+impl fmt::Debug for ProxyConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProxyConfig")
+            .field("kind", &self.kind)
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| "***"))
+            .finish()
+    }
+}
+
+/// Connection to the server opened by the driver instead of mgclient, reachable on a local port.
+pub(crate) struct Tunnel {
+    host: String,
+    ip: String,
+    local_address: SocketAddr,
+}
+
+impl Tunnel {
+    /// Returns `true` if the connection to the server can't be left to mgclient, because it
+    /// goes through a proxy.
+    pub(crate) fn is_needed(params: &ConnectParams) -> bool {
+        params.proxy.is_some()
+    }
+
+    /// Connects to `host` on the port from `params`, through the proxy if there is one, and
+    /// forwards the first connection made by this process to a local port to it.
+    ///
+    /// The listener only accepts connections from the loopback interface. Connections of other
+    /// local processes are closed, which needs listing sockets of this process, only supported
+    /// on Unix.
+    pub(crate) fn open(params: &ConnectParams, host: &str) -> Result<Tunnel, MgError> {
+        if cfg!(not(unix)) {
+            return Err(MgError::new(String::from(
+                "Connecting through a proxy is only supported on Unix",
+            )));
+        }
+        let port = params.port;
+        let connect_error = |err: io::Error| match &params.proxy {
+            Some(proxy) => MgError::new(format!(
+                "Unable to connect to {}:{} through proxy {}:{}: {}",
+                host, port, proxy.host, proxy.port, err
+            )),
+            None => MgError::new(format!("Unable to connect to {}:{}: {}", host, port, err)),
+        };
+        let remote = match &params.proxy {
+            Some(proxy) => {
+                let mut remote =
+                    TcpStream::connect((proxy.host.as_str(), proxy.port)).map_err(connect_error)?;
+                match proxy.kind {
+                    ProxyKind::Socks5 => socks5_connect(&mut remote, proxy, host, port),
+                    ProxyKind::HttpConnect => http_connect(&mut remote, proxy, host, port),
+                }
+                .map_err(connect_error)?;
+                remote
+            }
+            None => TcpStream::connect((host, port)).map_err(connect_error)?,
+        };
+        set_socket_options(&remote, params).map_err(connect_error)?;
+        // The address of the server is only known if the driver connected to it directly.
+        let ip = match (&params.proxy, host.parse::<IpAddr>()) {
+            (_, Ok(_)) => host.to_string(),
+            (None, Err(_)) => remote
+                .peer_addr()
+                .map(|x| x.ip().to_string())
+                .unwrap_or_default(),
+            (Some(_), Err(_)) => String::new(),
+        };
+
+        let listener = TcpListener::bind(("127.0.0.1", 0)).map_err(connect_error)?;
+        let local_address = listener.local_addr().map_err(connect_error)?;
+        let nodelay = params.tcp_nodelay;
+        thread::spawn(move || forward(listener, remote, nodelay));
+        Ok(Tunnel {
+            host: host.to_string(),
+            ip,
+            local_address,
+        })
+    }
+
+    pub(crate) fn local_port(&self) -> u16 {
+        self.local_address.port()
+    }
+
+    /// Returns certificate information describing the server at the other end of the tunnel
+    /// instead of the local end.
+    pub(crate) fn trust_info(&self, info: &TrustInfo) -> TrustInfo {
+        let hostname = match self.host.parse::<IpAddr>() {
+            Ok(_) => String::new(),
+            Err(_) => self.host.clone(),
+        };
+        TrustInfo {
+            hostname,
+            ip: self.ip.clone(),
+            ..info.clone()
+        }
+    }
+
+    /// Stops the forwarding thread if mgclient failed before connecting to the local port.
+    pub(crate) fn close_unused(&self) {
+        let _ = TcpStream::connect(self.local_address);
+    }
+}
+
+/// Forwards the first connection made by this process to `listener` to `remote` using two
+/// threads, one for each direction.
+fn forward(listener: TcpListener, remote: TcpStream, nodelay: bool) {
+    let address = match listener.local_addr() {
+        Ok(x) => x,
+        Err(_) => return,
+    };
+    let local = loop {
+        match listener.accept() {
+            Ok((x, peer)) if is_own_connection(peer, address) => break x,
+            Ok(_) => continue,
+            Err(_) => return,
+        }
+    };
+    drop(listener);
+    // Small writes of mgclient would otherwise be delayed on the local hop before being
+    // forwarded.
+    if nodelay && local.set_nodelay(true).is_err() {
+        return;
+    }
+    let (mut local_read, mut remote_write) = match (local.try_clone(), remote.try_clone()) {
+        (Ok(x), Ok(y)) => (x, y),
+        _ => return,
+    };
+    let upstream = thread::spawn(move || {
+        let _ = io::copy(&mut local_read, &mut remote_write);
+        let _ = remote_write.shutdown(Shutdown::Both);
+    });
+    let (mut remote_read, mut local_write) = (remote, local);
+    let _ = io::copy(&mut remote_read, &mut local_write);
+    let _ = local_write.shutdown(Shutdown::Both);
+    let _ = upstream.join();
+}
+
+#[cfg(not(unix))]
+fn is_own_connection(_: SocketAddr, _: SocketAddr) -> bool {
+    false
+}
+
+fn protocol_error(message: String) -> io::Error {
+    io::Error::other(message)
+}
+
+/// Performs the SOCKS5 handshake (RFC 1928 and RFC 1929) requesting a connection to
+/// `host`:`port`.
+pub(crate) fn socks5_connect<S: Read + Write>(
+    stream: &mut S,
+    proxy: &ProxyConfig,
+    host: &str,
+    port: u16,
+) -> io::Result<()> {
+    let credentials = match (&proxy.username, &proxy.password) {
+        (Some(username), password) => Some((username.as_str(), password.as_deref().unwrap_or(""))),
+        (None, _) => None,
+    };
+    match credentials {
+        Some(_) => stream.write_all(&[5, 2, 0, 2])?,
+        None => stream.write_all(&[5, 1, 0])?,
+    }
+    let mut reply = [0u8; 2];
+    stream.read_exact(&mut reply)?;
+    match (reply, credentials) {
+        ([5, 0], _) => {}
+        ([5, 2], Some((username, password))) => {
+            if username.len() > 255 || password.len() > 255 {
+                return Err(protocol_error(String::from(
+                    "proxy username and password can't be longer than 255 bytes",
+                )));
+            }
+            let mut request = vec![1, username.len() as u8];
+            request.extend_from_slice(username.as_bytes());
+            request.push(password.len() as u8);
+            request.extend_from_slice(password.as_bytes());
+            stream.write_all(&request)?;
+            stream.read_exact(&mut reply)?;
+            if reply[1] != 0 {
+                return Err(protocol_error(String::from("proxy authentication failed")));
+            }
+        }
+        _ => {
+            return Err(protocol_error(String::from(
+                "proxy doesn't support any offered authentication method",
+            )))
+        }
+    }
+
+    let mut request = vec![5, 1, 0];
+    match host.parse::<IpAddr>() {
+        Ok(IpAddr::V4(x)) => {
+            request.push(1);
+            request.extend_from_slice(&x.octets());
+        }
+        Ok(IpAddr::V6(x)) => {
+            request.push(4);
+            request.extend_from_slice(&x.octets());
+        }
+        Err(_) => {
+            if host.len() > 255 {
+                return Err(protocol_error(format!("host name {} is too long", host)));
+            }
+            request.push(3);
+            request.push(host.len() as u8);
+            request.extend_from_slice(host.as_bytes());
+        }
+    }
+    request.extend_from_slice(&port.to_be_bytes());
+    stream.write_all(&request)?;
+
+    let mut reply = [0u8; 4];
+    stream.read_exact(&mut reply)?;
+    if reply[1] != 0 {
+        return Err(protocol_error(format!(
+            "proxy refused the connection with SOCKS5 reply {}",
+            reply[1]
+        )));
+    }
+    // Skips the bound address and port, which aren't needed.
+    let address_size = match reply[3] {
+        1 => 4,
+        4 => 16,
+        3 => {
+            let mut size = [0u8; 1];
+            stream.read_exact(&mut size)?;
+            size[0] as usize
+        }
+        x => return Err(protocol_error(format!("unknown SOCKS5 address type {}", x))),
+    };
+    let mut bound = vec![0u8; address_size + 2];
+    stream.read_exact(&mut bound)?;
+    Ok(())
+}
+
+/// Requests a tunnel to `host`:`port` using the HTTP `CONNECT` method.
+pub(crate) fn http_connect<S: Read + Write>(
+    stream: &mut S,
+    proxy: &ProxyConfig,
+    host: &str,
+    port: u16,
+) -> io::Result<()> {
+    let authority = match host.parse::<IpAddr>() {
+        Ok(IpAddr::V6(_)) => format!("[{}]:{}", host, port),
+        _ => format!("{}:{}", host, port),
+    };
+    let mut request = format!("CONNECT {} HTTP/1.1\r\nHost: {}\r\n", authority, authority);
+    if let Some(username) = &proxy.username {
+        let credentials = format!("{}:{}", username, proxy.password.as_deref().unwrap_or(""));
+        request.push_str(&format!(
+            "Proxy-Authorization: Basic {}\r\n",
+            base64_encode(credentials.as_bytes())
+        ));
+    }
+    request.push_str("\r\n");
+    stream.write_all(request.as_bytes())?;
+
+    // Reads byte by byte so that nothing after the response header is consumed.
+    let mut response = Vec::new();
+    let mut byte = [0u8; 1];
+    while !response.ends_with(b"\r\n\r\n") {
+        if response.len() > 8192 {
+            return Err(protocol_error(String::from(
+                "proxy response header is too long",
+            )));
+        }
+        stream.read_exact(&mut byte)?;
+        response.push(byte[0]);
+    }
+    let response = String::from_utf8_lossy(&response);
+    let status_line = response.lines().next().unwrap_or("");
+    match status_line.split_whitespace().nth(1) {
+        Some(status) if status.starts_with('2') => Ok(()),
+        _ => Err(protocol_error(format!(
+            "proxy refused the connection: {}",
+            status_line
+        ))),
+    }
+}
+
+pub(crate) fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
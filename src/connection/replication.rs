@@ -0,0 +1,140 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::Connection;
+use crate::cypher::{escape_identifier, escape_string_literal};
+use crate::error::MgError;
+use crate::value::{Record, Value};
+use std::collections::HashMap;
+
+/// Replication mode of a replica.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ReplicationMode {
+    /// Transactions are committed on main only after the replica confirms receiving them.
+    Sync,
+    /// Transactions are committed on main without waiting for the replica.
+    Async,
+}
+
+/// Replica registered on main using `Connection::register_replica`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplicaSpec {
+    /// Name identifying the replica.
+    pub name: String,
+    pub mode: ReplicationMode,
+    /// Replication address of the replica, `host:port`, where the port is the one set using
+    /// `ReplicationRole::Replica`.
+    pub address: String,
+}
+
+/// Replication role of an instance, see `Connection::set_replication_role`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ReplicationRole {
+    Main,
+    /// Replica listening for replicated data on `port`.
+    Replica {
+        port: u16,
+    },
+}
+
+/// Replica as reported by `SHOW REPLICAS`, see `Connection::show_replicas`.
+///
+/// Columns reported by the server differ between Memgraph versions, all of them are available
+/// in `raw`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplicaInfo {
+    pub name: String,
+    pub socket_address: String,
+    /// Replication mode, e.g. `sync` or `async`.
+    pub sync_mode: String,
+    /// All values as returned by the server, keyed by column names.
+    pub raw: HashMap<String, Value>,
+}
+
+impl ReplicaInfo {
+    fn from_record(record: Record) -> ReplicaInfo {
+        let raw = record.into_map();
+        let field = |name: &str| match raw.get(name) {
+            Some(Value::String(x)) => x.clone(),
+            _ => String::new(),
+        };
+        ReplicaInfo {
+            name: field("name"),
+            socket_address: field("socket_address"),
+            sync_mode: field("sync_mode"),
+            raw,
+        }
+    }
+}
+
+pub(crate) fn register_replica_query(spec: &ReplicaSpec) -> Result<String, MgError> {
+    let mode = match spec.mode {
+        ReplicationMode::Sync => "SYNC",
+        ReplicationMode::Async => "ASYNC",
+    };
+    Ok(format!(
+        "REGISTER REPLICA {} {} TO {}",
+        escape_identifier(&spec.name)?,
+        mode,
+        escape_string_literal(&spec.address)?
+    ))
+}
+
+pub(crate) fn replication_role_query(role: &ReplicationRole) -> String {
+    match role {
+        ReplicationRole::Main => String::from("SET REPLICATION ROLE TO MAIN"),
+        ReplicationRole::Replica { port } => {
+            format!("SET REPLICATION ROLE TO REPLICA WITH PORT {}", port)
+        }
+    }
+}
+
+impl Connection {
+    /// Registers a replica on this instance, which needs to have the main role, by running
+    /// `REGISTER REPLICA`.
+    ///
+    /// Connection needs to be in status `Ready`. Replication queries can't be executed in
+    /// transactions and are therefore executed regardless of `autocommit`.
+    pub fn register_replica(&mut self, spec: &ReplicaSpec) -> Result<(), MgError> {
+        let query = register_replica_query(spec)?;
+        self.read_all_autocommit("register_replica", &query)?;
+        Ok(())
+    }
+
+    /// Unregisters replica with the given name by running `DROP REPLICA`.
+    ///
+    /// Connection needs to be in status `Ready`.
+    pub fn drop_replica(&mut self, name: &str) -> Result<(), MgError> {
+        let query = format!("DROP REPLICA {}", escape_identifier(name)?);
+        self.read_all_autocommit("drop_replica", &query)?;
+        Ok(())
+    }
+
+    /// Sets replication role of this instance by running `SET REPLICATION ROLE`.
+    ///
+    /// Connection needs to be in status `Ready`.
+    pub fn set_replication_role(&mut self, role: ReplicationRole) -> Result<(), MgError> {
+        let query = replication_role_query(&role);
+        self.read_all_autocommit("set_replication_role", &query)?;
+        Ok(())
+    }
+
+    /// Returns replicas registered on this instance by running `SHOW REPLICAS`.
+    ///
+    /// Connection needs to be in status `Ready`.
+    pub fn show_replicas(&mut self) -> Result<Vec<ReplicaInfo>, MgError> {
+        let records = self.read_all_autocommit("show_replicas", "SHOW REPLICAS")?;
+        Ok(records.into_iter().map(ReplicaInfo::from_record).collect())
+    }
+}
@@ -0,0 +1,238 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::hosts::endpoint_params;
+use super::{ConnectParams, Connection, ConnectionStatus, ReplicaInfo};
+use crate::error::MgError;
+use crate::value::{QueryParam, Record};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Function returning the Bolt host and port of a replica, see
+/// `RoutedConnection::connect_with`. Replicas for which it returns `None` are not used.
+pub type ReplicaAddress = dyn Fn(&ReplicaInfo) -> Option<(String, u16)> + Send + Sync;
+
+struct Replica {
+    name: String,
+    address: (String, u16),
+    /// `None` if the replica was unreachable, connecting is retried on the next refresh.
+    connection: Option<Connection>,
+}
+
+/// Connections to the main instance and read replicas of a replicated Memgraph, routing reads
+/// to the replicas and writes to main.
+///
+/// Replicas are discovered by running `SHOW REPLICAS` on main. Reads are spread over the
+/// reachable replicas in turns and executed on main if none of them is reachable.
+///
+/// # Examples
+///
+/// ```no_run
+/// use rsmgclient::{ConnectParams, MgError, RoutedConnection};
+///
+/// # fn run() -> Result<(), MgError> {
+/// let mut connection = RoutedConnection::connect(&ConnectParams {
+///     host: Some(String::from("memgraph-main")),
+///     ..Default::default()
+/// })?;
+/// connection.execute_write("CREATE (:Event {id: 1})", None)?;
+/// let records = connection.execute_read("MATCH (e:Event) RETURN e.id", None)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct RoutedConnection {
+    main: Connection,
+    params: ConnectParams,
+    replica_address: Arc<ReplicaAddress>,
+    replicas: Vec<Replica>,
+    next_replica: usize,
+    refresh_interval: Option<Duration>,
+    refreshed_at: Instant,
+}
+
+/// Returns host of a replica from its replication socket address and the Bolt port of main.
+fn default_replica_address(params: &ConnectParams, replica: &ReplicaInfo) -> Option<(String, u16)> {
+    let (host, _) = split_socket_address(&replica.socket_address)?;
+    Some((host, params.port))
+}
+
+/// Splits `host:port` into its parts, removing brackets around IPv6 addresses.
+pub(crate) fn split_socket_address(address: &str) -> Option<(String, u16)> {
+    let (host, port) = address.rsplit_once(':')?;
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    if host.is_empty() {
+        return None;
+    }
+    Some((host.to_string(), port.parse().ok()?))
+}
+
+impl RoutedConnection {
+    /// Connects to main using `param_struct` and to its replicas.
+    ///
+    /// Replicas are connected to using the same parameters, at the host of their replication
+    /// address and the port of main, i.e. all instances are expected to listen for Bolt
+    /// connections on the same port. Use `connect_with` if that's not the case.
+    pub fn connect(param_struct: &ConnectParams) -> Result<RoutedConnection, MgError> {
+        let params = param_struct.clone();
+        RoutedConnection::connect_with(param_struct, move |replica| {
+            default_replica_address(&params, replica)
+        })
+    }
+
+    /// Connects to main using `param_struct` and to its replicas at addresses returned by
+    /// `replica_address`.
+    pub fn connect_with<F>(
+        param_struct: &ConnectParams,
+        replica_address: F,
+    ) -> Result<RoutedConnection, MgError>
+    where
+        F: Fn(&ReplicaInfo) -> Option<(String, u16)> + Send + Sync + 'static,
+    {
+        let mut connection = RoutedConnection {
+            main: Connection::connect(param_struct)?,
+            params: param_struct.clone(),
+            replica_address: Arc::new(replica_address),
+            replicas: Vec::new(),
+            next_replica: 0,
+            refresh_interval: None,
+            refreshed_at: Instant::now(),
+        };
+        connection.refresh_replicas()?;
+        Ok(connection)
+    }
+
+    /// Returns connection to main, e.g. for running transactions spanning multiple queries.
+    pub fn main(&mut self) -> &mut Connection {
+        &mut self.main
+    }
+
+    /// Returns names of the replicas reads are routed to, i.e. which are currently reachable.
+    pub fn replicas(&self) -> Vec<&str> {
+        self.replicas
+            .iter()
+            .filter(|x| x.connection.is_some())
+            .map(|x| x.name.as_str())
+            .collect()
+    }
+
+    /// Returns interval after which the replicas are refreshed before routing a read, `None`
+    /// if they are only refreshed by `refresh_replicas`.
+    pub fn refresh_interval(&self) -> Option<Duration> {
+        self.refresh_interval
+    }
+
+    /// Sets interval after which the replicas are refreshed before routing a read, defaults to
+    /// `None`.
+    ///
+    /// The refresh isn't done in the background: the first `execute_read` after the interval
+    /// elapses runs `SHOW REPLICAS` on main and connects to new replicas before executing its
+    /// query, so its latency includes the refresh. To keep refreshes off the read path, leave
+    /// the interval unset and call `refresh_replicas` at a convenient time instead.
+    pub fn set_refresh_interval(&mut self, interval: Option<Duration>) {
+        self.refresh_interval = interval;
+    }
+
+    /// Updates the replicas from `SHOW REPLICAS` on main.
+    ///
+    /// Connections to replicas whose address didn't change are kept if they are in status
+    /// `Ready`, other ones are replaced with new connections. Unregistered replicas are
+    /// disconnected and new or previously unreachable ones are connected to. Replicas which
+    /// can't be connected to are skipped until the next refresh. Main connection needs to be
+    /// in status `Ready`.
+    pub fn refresh_replicas(&mut self) -> Result<(), MgError> {
+        let infos = self.main.show_replicas()?;
+        let mut previous = std::mem::take(&mut self.replicas);
+        for info in infos {
+            let address = match (self.replica_address)(&info) {
+                Some(x) => x,
+                None => continue,
+            };
+            let existing = previous
+                .iter()
+                .position(|x| x.name == info.name && x.address == address)
+                .map(|i| previous.swap_remove(i));
+            let existing = existing
+                .and_then(|x| x.connection)
+                .filter(|x| x.status() == ConnectionStatus::Ready);
+            let connection = match existing {
+                Some(x) => Some(x),
+                None => {
+                    let params = endpoint_params(&self.params, address.0.clone(), address.1);
+                    Connection::connect(&params).ok()
+                }
+            };
+            self.replicas.push(Replica {
+                name: info.name,
+                address,
+                connection,
+            });
+        }
+        self.refreshed_at = Instant::now();
+        Ok(())
+    }
+
+    /// Executes a read-only query on one of the replicas and fetches all of its records.
+    ///
+    /// Replicas are used in turns. If a replica is lost, the query is executed on the next one
+    /// and on main if no replica is reachable. If the query fails on a replica, its connection
+    /// is reset for the next read, or dropped until the next refresh if that fails too. See
+    /// `set_refresh_interval` for refreshes done before the read. The query is executed in its
+    /// own transaction, which is committed if autocommit is false. Since replicas reject writes,
+    /// queries which modify data should be executed using `execute_write`. Main connection needs
+    /// to be in status `Ready`.
+    pub fn execute_read(
+        &mut self,
+        query: &str,
+        params: Option<&HashMap<String, QueryParam>>,
+    ) -> Result<Vec<Record>, MgError> {
+        if matches!(self.refresh_interval, Some(x) if self.refreshed_at.elapsed() >= x) {
+            self.refresh_replicas()?;
+        }
+        self.main.check_ready("execute_read")?;
+        for _ in 0..self.replicas.len() {
+            let index = self.next_replica % self.replicas.len();
+            self.next_replica = index + 1;
+            let replica = &mut self.replicas[index];
+            let connection = match replica.connection.as_mut() {
+                Some(x) => x,
+                None => continue,
+            };
+            match connection.read_all(query, params) {
+                Ok(records) => return Ok(records),
+                Err(_) if connection.is_session_broken() => replica.connection = None,
+                Err(err) => {
+                    if connection.reset().is_err() {
+                        replica.connection = None;
+                    }
+                    return Err(err);
+                }
+            }
+        }
+        self.main.read_all(query, params)
+    }
+
+    /// Executes a query on main and fetches all of its records.
+    ///
+    /// The query is executed in its own transaction, which is committed if autocommit is false.
+    /// Main connection needs to be in status `Ready`.
+    pub fn execute_write(
+        &mut self,
+        query: &str,
+        params: Option<&HashMap<String, QueryParam>>,
+    ) -> Result<Vec<Record>, MgError> {
+        self.main.check_ready("execute_write")?;
+        self.main.read_all(query, params)
+    }
+}
@@ -0,0 +1,89 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Iterator over query results, enabled by the `iterator` feature.
+
+use super::Connection;
+use crate::error::MgError;
+use crate::value::{QueryParam, Record};
+use std::collections::HashMap;
+
+/// Iterator over records of a query, created by `Connection::query`.
+///
+/// Records are fetched one by one using `Connection::fetchone`. Iteration stops after the last
+/// record or after the first error. If the iterator is dropped before all records are fetched,
+/// the remaining records can still be fetched from the connection.
+pub struct Rows<'a> {
+    connection: &'a mut Connection,
+    columns: Vec<String>,
+    done: bool,
+}
+
+impl<'a> Rows<'a> {
+    /// Returns names of the columns of the query.
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+}
+
+impl<'a> Iterator for Rows<'a> {
+    type Item = Result<Record, MgError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.connection.fetchone() {
+            Ok(Some(x)) => Some(Ok(x)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+impl Connection {
+    /// Executes provided query like `execute` and returns an iterator over its records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rsmgclient::{Connection, MgError};
+    ///
+    /// # fn print_names(connection: &mut Connection) -> Result<(), MgError> {
+    /// for record in connection.query("MATCH (n:Person) RETURN n.name", None)? {
+    ///     println!("{}", record?.values[0]);
+    /// }
+    /// connection.commit()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn query(
+        &mut self,
+        query: &str,
+        params: Option<&HashMap<String, QueryParam>>,
+    ) -> Result<Rows<'_>, MgError> {
+        let columns = self.execute(query, params)?;
+        Ok(Rows {
+            connection: self,
+            columns,
+            done: false,
+        })
+    }
+}
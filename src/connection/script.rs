@@ -0,0 +1,85 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{Connection, ConnectionStatus};
+use crate::cypher::split_statements;
+use crate::error::MgError;
+use crate::summary::QueryStats;
+
+impl Connection {
+    /// Executes a script of statements separated by semicolons and returns aggregated query
+    /// statistics.
+    ///
+    /// The script is split using `cypher::split_statements`, so semicolons in string literals
+    /// and comments are handled correctly. Statements are executed one by one and their results
+    /// are discarded. If the connection is not in a transaction, all statements are executed in a
+    /// new transaction which is committed at the end or rolled back on error, regardless of
+    /// `autocommit`. Otherwise they are executed as a part of the current transaction. Queries
+    /// which can't be executed in a transaction, e.g. index creation, are therefore not
+    /// supported.
+    ///
+    /// Error of a failed statement states its number and the line of the script it starts on.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rsmgclient::{Connection, MgError};
+    ///
+    /// # fn migrate(connection: &mut Connection) -> Result<(), MgError> {
+    /// let stats = connection.execute_script(
+    ///     "CREATE (:Version {number: 1});
+    ///      // Rename the property.
+    ///      MATCH (n:Person) SET n.full_name = n.name REMOVE n.name;",
+    /// )?;
+    /// println!("Updated {} properties", stats.properties_set);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn execute_script(&mut self, script: &str) -> Result<QueryStats, MgError> {
+        let reason = match self.status {
+            ConnectionStatus::Ready | ConnectionStatus::InTransaction => None,
+            ConnectionStatus::Executing => Some("while executing"),
+            ConnectionStatus::Fetching => Some("while fetching"),
+            ConnectionStatus::Closed => Some("while connection is closed"),
+            ConnectionStatus::Bad => Some("while connection is bad"),
+        };
+        if let Some(x) = reason {
+            return Err(MgError::new(format!("Can't call execute_script {}", x)));
+        }
+        let statements = split_statements(script);
+        self.in_transaction(|connection| {
+            let mut stats = QueryStats::default();
+            for (i, statement) in statements.iter().enumerate() {
+                let result = connection
+                    .execute(&statement.text, None)
+                    .and_then(|_| connection.fetchall());
+                if let Err(err) = result {
+                    return Err(MgError::with_code(
+                        format!(
+                            "Statement {} at line {} failed: {}",
+                            i + 1,
+                            statement.line,
+                            err
+                        ),
+                        err.code(),
+                    ));
+                }
+                if let Some(x) = connection.summary_ref().and_then(|x| x.stats.as_ref()) {
+                    stats += x;
+                }
+            }
+            Ok(stats)
+        })
+    }
+}
@@ -0,0 +1,166 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Sockets of this process, including the one mgclient connects with.
+//!
+//! mgclient doesn't expose its socket. It can still be found among the open file descriptors of
+//! the process by the addresses it connects, which is only implemented on Unix.
+
+use super::ConnectParams;
+use crate::error::MgError;
+use std::io;
+use std::net::TcpStream;
+#[cfg(unix)]
+use std::{
+    collections::HashSet,
+    mem::ManuallyDrop,
+    net::SocketAddr,
+    os::unix::io::{FromRawFd, RawFd},
+    sync::{Mutex, MutexGuard},
+};
+
+/// Serializes connecting of connections with socket options, so that their sockets can be told
+/// apart.
+#[cfg(unix)]
+static SOCKET_OPTIONS_LOCK: Mutex<()> = Mutex::new(());
+
+/// Returns `true` if `ConnectParams::tcp_keepalive` or `ConnectParams::tcp_nodelay` is set.
+pub(crate) fn has_socket_options(params: &ConnectParams) -> bool {
+    params.tcp_keepalive.is_some() || params.tcp_nodelay
+}
+
+/// Returns error if the socket options of `params` can't be set in this build or on this
+/// platform.
+pub(crate) fn check_socket_options(params: &ConnectParams) -> Result<(), MgError> {
+    if cfg!(not(feature = "socket2")) && params.tcp_keepalive.is_some() {
+        return Err(MgError::new(String::from(
+            "tcp_keepalive requires the socket2 feature",
+        )));
+    }
+    if cfg!(not(unix)) && has_socket_options(params) {
+        return Err(MgError::new(String::from(
+            "tcp_keepalive and tcp_nodelay are only supported on Unix",
+        )));
+    }
+    Ok(())
+}
+
+/// Applies `ConnectParams::tcp_keepalive` and `ConnectParams::tcp_nodelay` to `stream`.
+pub(crate) fn set_socket_options(stream: &TcpStream, params: &ConnectParams) -> io::Result<()> {
+    if params.tcp_nodelay {
+        stream.set_nodelay(true)?;
+    }
+    #[cfg(feature = "socket2")]
+    if let Some(time) = params.tcp_keepalive {
+        use socket2::{SockRef, TcpKeepalive};
+        SockRef::from(stream).set_tcp_keepalive(&TcpKeepalive::new().with_time(time))?;
+    }
+    Ok(())
+}
+
+/// Connected TCP socket of this process.
+#[cfg(unix)]
+pub(crate) struct TcpSocket {
+    pub(crate) fd: RawFd,
+    pub(crate) local: SocketAddr,
+    pub(crate) peer: SocketAddr,
+}
+
+/// Returns connected TCP sockets of this process, found by listing `/dev/fd`.
+#[cfg(unix)]
+pub(crate) fn tcp_sockets() -> Vec<TcpSocket> {
+    let entries = match std::fs::read_dir("/dev/fd") {
+        Ok(x) => x,
+        Err(_) => return Vec::new(),
+    };
+    entries
+        .filter_map(|entry| {
+            let fd: RawFd = entry.ok()?.file_name().to_str()?.parse().ok()?;
+            let stream = borrow_stream(fd);
+            // Descriptors which aren't TCP sockets fail one of the calls.
+            stream.nodelay().ok()?;
+            Some(TcpSocket {
+                fd,
+                local: stream.local_addr().ok()?,
+                peer: stream.peer_addr().ok()?,
+            })
+        })
+        .collect()
+}
+
+/// Returns a stream using `fd` without taking its ownership, dropping the stream would close it.
+#[cfg(unix)]
+fn borrow_stream(fd: RawFd) -> ManuallyDrop<TcpStream> {
+    ManuallyDrop::new(unsafe { TcpStream::from_raw_fd(fd) })
+}
+
+/// Returns `true` if a socket of this process is connected from `local` to `peer`.
+#[cfg(unix)]
+pub(crate) fn is_own_connection(local: SocketAddr, peer: SocketAddr) -> bool {
+    tcp_sockets()
+        .iter()
+        .any(|x| x.local == local && x.peer == peer)
+}
+
+/// Finds the socket mgclient connects to the server with, which is connected to the server port
+/// and didn't exist before connecting.
+///
+/// Connections with socket options are connected one at a time. Another connection to the same
+/// port made by this process at the same time without socket options, e.g. by another driver,
+/// can't be told apart and gets the options too.
+#[cfg(unix)]
+pub(crate) struct SocketFinder {
+    port: u16,
+    existing: HashSet<(SocketAddr, SocketAddr)>,
+    _guard: MutexGuard<'static, ()>,
+}
+
+#[cfg(unix)]
+impl SocketFinder {
+    /// Remembers connections to `port` which exist before connecting.
+    pub(crate) fn new(port: u16) -> SocketFinder {
+        let guard = match SOCKET_OPTIONS_LOCK.lock() {
+            Ok(x) => x,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let existing = tcp_sockets()
+            .into_iter()
+            .filter(|x| x.peer.port() == port)
+            .map(|x| (x.local, x.peer))
+            .collect();
+        SocketFinder {
+            port,
+            existing,
+            _guard: guard,
+        }
+    }
+
+    /// Sets the socket options of `params` on the connections made since `new`.
+    pub(crate) fn set_options(self, params: &ConnectParams) -> Result<(), MgError> {
+        let sockets: Vec<TcpSocket> = tcp_sockets()
+            .into_iter()
+            .filter(|x| x.peer.port() == self.port && !self.existing.contains(&(x.local, x.peer)))
+            .collect();
+        if sockets.is_empty() {
+            return Err(MgError::new(String::from(
+                "Unable to find the socket of the connection to set socket options",
+            )));
+        }
+        for socket in sockets {
+            set_socket_options(&borrow_stream(socket.fd), params)
+                .map_err(|err| MgError::new(format!("Unable to set socket options: {}", err)))?;
+        }
+        Ok(())
+    }
+}
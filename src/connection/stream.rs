@@ -0,0 +1,85 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::Connection;
+use crate::error::MgError;
+use crate::value::{QueryParam, Record};
+use std::collections::HashMap;
+use std::ops::ControlFlow;
+
+impl Connection {
+    /// Executes query and passes its records to `f` one by one, returning the number of
+    /// records passed.
+    ///
+    /// Records are requested in batches of `fetch_batch_size` regardless of whether the
+    /// connection is lazy, so memory usage doesn't grow with the size of the result. If `f`
+    /// returns `ControlFlow::Break`, the remaining records are received and dropped without
+    /// being converted. Connection needs to be in status `Ready` or `InTransaction`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rsmgclient::{ConnectParams, Connection, Value};
+    /// use std::ops::ControlFlow;
+    ///
+    /// # fn main() -> Result<(), rsmgclient::MgError> {
+    /// let mut connection = Connection::connect(&ConnectParams::default())?;
+    /// let mut total = 0;
+    /// connection.for_each_row("MATCH (n:Account) RETURN n.balance", None, |record| {
+    ///     if let Value::Int(x) = record.values[0] {
+    ///         total += x;
+    ///     }
+    ///     ControlFlow::Continue(())
+    /// })?;
+    /// # Ok(()) }
+    /// ```
+    pub fn for_each_row<F>(
+        &mut self,
+        query: &str,
+        params: Option<&HashMap<String, QueryParam>>,
+        f: F,
+    ) -> Result<u64, MgError>
+    where
+        F: FnMut(Record) -> ControlFlow<()>,
+    {
+        let lazy = self.lazy;
+        self.lazy = true;
+        let result = self.for_each_row_lazy(query, params, f);
+        self.lazy = lazy;
+        result
+    }
+
+    fn for_each_row_lazy<F>(
+        &mut self,
+        query: &str,
+        params: Option<&HashMap<String, QueryParam>>,
+        mut f: F,
+    ) -> Result<u64, MgError>
+    where
+        F: FnMut(Record) -> ControlFlow<()>,
+    {
+        self.execute(query, params)?;
+        let pull_size = self.pull_size.unwrap_or(self.fetch_batch_size);
+        let mut count = 0;
+        while let Some(row) = self.next_row(pull_size)? {
+            let record = self.convert_row(row)?;
+            count += 1;
+            if f(record).is_break() {
+                while self.next_row(pull_size)?.is_some() {}
+                break;
+            }
+        }
+        Ok(count)
+    }
+}
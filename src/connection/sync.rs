@@ -0,0 +1,143 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{ConnectParams, Connection, ConnectionStatus};
+use crate::error::MgError;
+use crate::summary::QuerySummary;
+use crate::value::{QueryParam, Record};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Connection which can be shared between threads.
+///
+/// Cloning a `SyncConnection` creates another handle to the same connection. Every method
+/// locks the connection for the duration of the call, calls from other threads are blocked
+/// until it returns. Since query results are fetched by separate calls, threads executing
+/// queries in parallel should hold the lock for the whole query using `lock`, otherwise they
+/// may fetch each other's records.
+///
+/// # Examples
+///
+/// ```
+/// use rsmgclient::{params, ConnectParams, MgError, SyncConnection};
+/// use std::thread;
+///
+/// # fn run() -> Result<(), MgError> {
+/// let connection = SyncConnection::connect(&ConnectParams {
+///     host: Some(String::from("localhost")),
+///     autocommit: true,
+///     ..Default::default()
+/// })?;
+///
+/// let handles: Vec<_> = (0..4)
+///     .map(|i| {
+///         let connection = connection.clone();
+///         thread::spawn(move || -> Result<(), MgError> {
+///             let mut connection = connection.lock();
+///             let params = params! { "id" => i };
+///             connection.execute("CREATE (:Worker {id: $id})", Some(&params))?;
+///             connection.fetchall()?;
+///             Ok(())
+///         })
+///     })
+///     .collect();
+/// for handle in handles {
+///     handle.join().unwrap()?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct SyncConnection {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl SyncConnection {
+    /// Wraps `connection` so that it can be shared between threads.
+    pub fn new(connection: Connection) -> SyncConnection {
+        SyncConnection {
+            connection: Arc::new(Mutex::new(connection)),
+        }
+    }
+
+    /// Creates a connection using `Connection::connect` and wraps it.
+    pub fn connect(param_struct: &ConnectParams) -> Result<SyncConnection, MgError> {
+        Ok(SyncConnection::new(Connection::connect(param_struct)?))
+    }
+
+    /// Locks the connection, blocking until no other thread holds the lock.
+    ///
+    /// If a thread panicked while holding the lock, the connection is returned anyway, its
+    /// status reflects whether it is still usable.
+    pub fn lock(&self) -> MutexGuard<'_, Connection> {
+        match self.connection.lock() {
+            Ok(x) => x,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// See `Connection::status`.
+    pub fn status(&self) -> ConnectionStatus {
+        self.lock().status()
+    }
+
+    /// See `Connection::summary`.
+    pub fn summary(&self) -> Option<QuerySummary> {
+        self.lock().summary()
+    }
+
+    /// See `Connection::execute_without_results`.
+    pub fn execute_without_results(&self, query: &str) -> Result<(), MgError> {
+        self.lock().execute_without_results(query)
+    }
+
+    /// See `Connection::execute`.
+    pub fn execute(
+        &self,
+        query: &str,
+        params: Option<&HashMap<String, QueryParam>>,
+    ) -> Result<Vec<String>, MgError> {
+        self.lock().execute(query, params)
+    }
+
+    /// See `Connection::fetchone`.
+    pub fn fetchone(&self) -> Result<Option<Record>, MgError> {
+        self.lock().fetchone()
+    }
+
+    /// See `Connection::fetchmany`.
+    pub fn fetchmany(&self, size: Option<u32>) -> Result<Vec<Record>, MgError> {
+        self.lock().fetchmany(size)
+    }
+
+    /// See `Connection::fetchall`.
+    pub fn fetchall(&self) -> Result<Vec<Record>, MgError> {
+        self.lock().fetchall()
+    }
+
+    /// See `Connection::commit`.
+    pub fn commit(&self) -> Result<(), MgError> {
+        self.lock().commit()
+    }
+
+    /// See `Connection::rollback`.
+    pub fn rollback(&self) -> Result<(), MgError> {
+        self.lock().rollback()
+    }
+
+    /// See `Connection::close`.
+    pub fn close(&self) {
+        self.lock().close()
+    }
+}
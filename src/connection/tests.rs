@@ -1,5 +1,5 @@
 use super::*;
-use crate::{Node, Value};
+use crate::{FromRecord, Node, Properties, QueryType, Value, ValueRef};
 use serial_test::serial;
 
 fn get_connection(prms: &ConnectParams) -> Connection {
@@ -70,7 +70,7 @@ fn initialize() -> Connection {
     get_connection(&connect_prms)
 }
 
-fn create_node(labels: Vec<String>, properties: HashMap<String, Value>) -> Node {
+fn create_node(labels: Vec<String>, properties: Properties) -> Node {
     Node {
         id: 0,
         label_count: labels.len() as u32,
@@ -92,13 +92,13 @@ fn create_params(key: String, value: String) -> HashMap<String, QueryParam> {
 }
 
 #[allow(clippy::ptr_arg)]
-fn my_callback(host: &String, ip_address: &String, key_type: &String, fingerprint: &String) -> i32 {
-    assert_eq!(host, "localhost");
-    assert_eq!(ip_address, "127.0.0.1");
-    assert_eq!(key_type, "rsaEncryption");
-    assert_eq!(fingerprint.len(), 128);
+fn my_callback(info: &TrustInfo) -> TrustDecision {
+    assert_eq!(info.hostname, "localhost");
+    assert_eq!(info.ip, "127.0.0.1");
+    assert_eq!(info.key_type, "rsaEncryption");
+    assert_eq!(info.fingerprint.len(), 128);
 
-    0
+    TrustDecision::Accept
 }
 
 #[test]
@@ -108,7 +108,7 @@ fn panic_sslcert() {
     initialize();
     let connect_prms = ConnectParams {
         address: Some(String::from("127.0.0.1")),
-        trust_callback: Some(&my_callback),
+        trust_callback: Some(TrustCallback::new(my_callback)),
         lazy: false,
         sslcert: Some(String::from("test_sslcert")),
         ..Default::default()
@@ -123,7 +123,7 @@ fn panic_sslkey() {
     initialize();
     let connect_prms = ConnectParams {
         address: Some(String::from("127.0.0.1")),
-        trust_callback: Some(&my_callback),
+        trust_callback: Some(TrustCallback::new(my_callback)),
         lazy: false,
         sslkey: Some(String::from("test_sslkey")),
         ..Default::default()
@@ -131,6 +131,19 @@ fn panic_sslkey() {
     let _connection = get_connection(&connect_prms);
 }
 
+#[test]
+fn trusted_fingerprints_require_ssl() {
+    let connect_prms = ConnectParams {
+        address: Some(String::from("127.0.0.1")),
+        trusted_fingerprints: Some(vec![String::from("ab12")]),
+        ..Default::default()
+    };
+    match Connection::connect(&connect_prms) {
+        Ok(_) => panic!("Checking fingerprints without SSL should fail"),
+        Err(err) => assert!(format!("{}", err).contains("SSLMode::Require")),
+    }
+}
+
 fn test_execute_error(connection: &mut Connection, error: &str) {
     let result = connection.execute("RETURN 1;", None);
     assert!(result.is_err());
@@ -220,7 +233,7 @@ fn test_fetchone_person_alice(connection: &mut Connection) {
                 n,
                 &create_node(
                     vec!["Person".to_string()],
-                    hashmap! {"name".to_string() => Value::String("Alice".to_string())},
+                    hashmap! {Arc::from("name") => Value::String("Alice".to_string())},
                 ),
             );
         }
@@ -282,6 +295,15 @@ fn fetchone_summary() {
     }
 
     let summary = connection.summary().unwrap();
+    assert!(!summary.has_more);
+    assert_eq!(summary.query_type, Some(QueryType::Read));
+    assert!(summary.parsing_time.is_some());
+    assert!(summary.planning_time.is_some());
+    assert!(summary.plan_execution_time.is_some());
+    assert!(summary.cost_estimate.is_some());
+    assert!(summary.run_id.is_some());
+
+    let summary = summary.raw;
     assert_eq!(9, summary.len());
     for key in &[
         "cost_estimate",
@@ -419,6 +441,155 @@ fn fetchall_not_lazy() {
     test_fetchmany_empty_nodes(&mut connection);
 }
 
+#[test]
+#[serial]
+fn fetchall_into_reuses_buffer() {
+    let mut connection = initialize();
+    execute_query_and_fetchall("CREATE (), (), ();");
+
+    let mut records = Vec::new();
+    execute_query(&mut connection, "MATCH (n) RETURN n;");
+    assert_eq!(connection.fetchall_into(&mut records).unwrap(), 3);
+    assert_eq!(connection.status, ConnectionStatus::InTransaction);
+
+    execute_query(&mut connection, "MATCH (n) RETURN n LIMIT 2;");
+    assert_eq!(connection.fetchall_into(&mut records).unwrap(), 2);
+    assert_eq!(records.len(), 5);
+
+    records.clear();
+    let capacity = records.capacity();
+    execute_query(&mut connection, "MATCH (n) RETURN n;");
+    assert_eq!(connection.fetchall_into(&mut records).unwrap(), 3);
+    assert_eq!(records.capacity(), capacity);
+}
+
+#[test]
+#[serial]
+fn fetchone_ref_borrows_values() {
+    let mut connection = initialize();
+
+    execute_query(
+        &mut connection,
+        "UNWIND range(1, 2) AS x RETURN 'row' AS name, [x, 'a'] AS list, {x: x} AS map;",
+    );
+    let mut rows = 0;
+    while let Some(record) = connection.fetchone_ref().unwrap() {
+        rows += 1;
+        assert_eq!(record.columns(), ["name", "list", "map"]);
+        assert!(matches!(
+            record.get_by_name("name"),
+            Some(ValueRef::String("row"))
+        ));
+        match record.get(1) {
+            Some(ValueRef::List(list)) => {
+                assert_eq!(list.len(), 2);
+                assert_eq!(list.to_vec()[0], Value::Int(rows));
+                assert!(matches!(list.get(1), Some(ValueRef::String("a"))));
+            }
+            x => panic!("Expected list, got {:?}", x),
+        }
+        match record.get(2) {
+            Some(ValueRef::Map(map)) => {
+                assert!(matches!(map.get("x"), Some(ValueRef::Int(x)) if x == rows))
+            }
+            x => panic!("Expected map, got {:?}", x),
+        }
+    }
+    assert_eq!(rows, 2);
+    assert_eq!(connection.status, ConnectionStatus::InTransaction);
+
+    connection.rollback().unwrap();
+    connection.set_lazy(false);
+    execute_query(&mut connection, "RETURN 1;");
+    assert!(connection.fetchone_ref().is_err());
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+#[serial]
+fn fetchall_not_lazy_converts_large_results_in_order() {
+    let mut connection = initialize();
+    connection.set_lazy(false);
+
+    execute_query(
+        &mut connection,
+        "UNWIND range(1, 5000) AS x RETURN x, {x: x} AS map;",
+    );
+    let records = connection.fetchall().unwrap();
+    assert_eq!(records.len(), 5000);
+    for (i, record) in records.iter().enumerate() {
+        let x = Value::Int(i as i64 + 1);
+        assert_eq!(record.values[0], x);
+        assert_eq!(
+            record.values[1],
+            Value::Map(hashmap! { String::from("x") => x })
+        );
+    }
+}
+
+#[test]
+#[serial]
+fn for_each_row_streams_records() {
+    let mut connection = initialize();
+    connection.set_lazy(false);
+    connection.set_fetch_batch_size(10);
+
+    let mut sum = 0;
+    let count = connection
+        .for_each_row("UNWIND range(1, 100) AS x RETURN x;", None, |record| {
+            if let Value::Int(x) = record.values[0] {
+                sum += x;
+            }
+            std::ops::ControlFlow::Continue(())
+        })
+        .unwrap();
+    assert_eq!(count, 100);
+    assert_eq!(sum, 5050);
+    assert!(!connection.lazy());
+    assert_eq!(connection.status, ConnectionStatus::InTransaction);
+
+    let count = connection
+        .for_each_row(
+            "UNWIND range(1, 100) AS x RETURN x;",
+            None,
+            |record| match record.values[0] {
+                Value::Int(3) => std::ops::ControlFlow::Break(()),
+                _ => std::ops::ControlFlow::Continue(()),
+            },
+        )
+        .unwrap();
+    assert_eq!(count, 3);
+    assert_eq!(connection.status, ConnectionStatus::InTransaction);
+}
+
+#[test]
+#[serial]
+fn max_buffered_rows() {
+    let mut connection = initialize();
+    connection.set_lazy(false);
+    connection.set_max_buffered_rows(Some(10));
+
+    execute_query(&mut connection, "UNWIND range(1, 10) AS x RETURN x;");
+    assert_eq!(connection.fetchall().unwrap().len(), 10);
+
+    let err = connection
+        .execute("UNWIND range(1, 11) AS x RETURN x;", None)
+        .unwrap_err();
+    assert!(err.to_string().contains("more than 10 records"));
+    assert_eq!(connection.status, ConnectionStatus::Bad);
+}
+
+#[cfg(feature = "raw")]
+#[test]
+#[serial]
+fn as_raw_session() {
+    let mut connection = initialize();
+
+    let session = connection.as_raw_session();
+    assert!(!session.is_null());
+    assert_eq!(unsafe { bindings::mg_session_status(session) }, 0);
+}
+
 fn test_commit_error(connection: &mut Connection, error: &str) {
     let commit_res = connection.commit();
     assert!(commit_res.is_err());
@@ -639,20 +810,27 @@ fn close() {
 
 #[test]
 #[serial]
-#[should_panic(expected = "Can't close while executing")]
-fn executing_close_panic() {
+fn executing_close() {
     let mut connection = initialize();
-    connection.status = ConnectionStatus::Executing;
+    execute_query(&mut connection, "UNWIND range(1, 10) AS x RETURN x;");
+    assert_eq!(connection.status(), ConnectionStatus::Executing);
     connection.close();
+    assert_eq!(connection.status(), ConnectionStatus::Closed);
+    assert!(connection.mg_session.is_null());
+    assert!(connection.fetchone().is_err());
+    assert!(connection.execute_without_results("RETURN 1;").is_err());
 }
 
 #[test]
 #[serial]
-#[should_panic(expected = "Can't close while fetching")]
-fn fetching_close_panic() {
+fn fetching_close() {
     let mut connection = initialize();
-    connection.status = ConnectionStatus::Fetching;
+    execute_query(&mut connection, "UNWIND range(1, 10) AS x RETURN x;");
+    assert!(connection.fetchone().unwrap().is_some());
     connection.close();
+    assert_eq!(connection.status(), ConnectionStatus::Closed);
+    connection.close();
+    assert_eq!(connection.status(), ConnectionStatus::Closed);
 }
 
 #[test]
@@ -673,3 +851,1976 @@ fn execute_without_results() {
     }
     assert_eq!(ConnectionStatus::InTransaction, connection.status());
 }
+
+#[test]
+#[serial]
+fn execute_without_results_stats() {
+    let mut connection = initialize();
+
+    assert!(connection
+        .execute_without_results("CREATE (:Person {name: 'Alice'})-[:KNOWS]->(:Person);")
+        .is_ok());
+    let stats = connection.summary().unwrap().stats.unwrap();
+    assert_eq!(stats.nodes_created, 2);
+    assert_eq!(stats.relationships_created, 1);
+    assert_eq!(stats.properties_set, 1);
+    assert_eq!(stats.labels_added, 2);
+    assert_eq!(stats.nodes_deleted, 0);
+}
+
+#[test]
+fn read_only_query_detection() {
+    assert!(is_read_only_query("MATCH (n) RETURN n"));
+    assert!(is_read_only_query(
+        "MATCH (n:Person) WHERE n.name = 'CREATE' RETURN n // SET"
+    ));
+    assert!(is_read_only_query("UNWIND [1, 2] AS x RETURN x"));
+    assert!(is_read_only_query("SHOW INDEX INFO"));
+    assert!(!is_read_only_query("MATCH (n) SET n.x = 1"));
+    assert!(!is_read_only_query("match (n) detach delete n"));
+    assert!(!is_read_only_query("CALL my.procedure() YIELD x RETURN x"));
+    assert!(!is_read_only_query("MERGE (n:Person {id: 1})"));
+    assert!(!is_read_only_query("  "));
+
+    assert!(is_read_only_query("PROFILE MATCH (n) RETURN count(n)"));
+    assert!(is_read_only_query(
+        "/* report */ OPTIONAL MATCH (n) RETURN n"
+    ));
+    assert!(!is_read_only_query(
+        "REGISTER REPLICA replica1 SYNC TO '10.0.0.2'"
+    ));
+    assert!(!is_read_only_query("DROP REPLICA replica1"));
+    assert!(!is_read_only_query("TERMINATE TRANSACTIONS '1'"));
+    assert!(!is_read_only_query("USE DATABASE memgraph"));
+    assert!(!is_read_only_query(
+        "SET DATABASE SETTING 'log.level' TO 'TRACE'"
+    ));
+    assert!(!is_read_only_query("SHOW USERS; CREATE USER alice"));
+    assert!(!is_read_only_query("SET PASSWORD FOR alice TO 'secret'"));
+    assert!(!is_read_only_query("EXPLAIN"));
+}
+
+#[test]
+fn retry_policy_backoff() {
+    let policy = RetryPolicy {
+        max_retries: 10,
+        initial_backoff: Duration::from_millis(100),
+        max_backoff: Duration::from_secs(1),
+    };
+    assert_eq!(policy.backoff(1), Duration::from_millis(100));
+    assert_eq!(policy.backoff(2), Duration::from_millis(200));
+    assert_eq!(policy.backoff(4), Duration::from_millis(800));
+    assert_eq!(policy.backoff(5), Duration::from_secs(1));
+    assert_eq!(policy.backoff(100), Duration::from_secs(1));
+}
+
+#[test]
+#[serial]
+fn execute_read_with_retry() {
+    let mut connection = initialize();
+    let policy = RetryPolicy::default();
+
+    let records =
+        match connection.execute_read_with_retry("UNWIND [1, 2, 3] AS x RETURN x", None, &policy) {
+            Ok(x) => x,
+            Err(err) => panic!("Read failed: {}", err),
+        };
+    assert_eq!(records.len(), 3);
+    assert_eq!(ConnectionStatus::Ready, connection.status());
+
+    let result = connection.execute_read_with_retry("CREATE (n) RETURN n", None, &policy);
+    assert!(result.is_err());
+    assert!(format!("{}", result.err().unwrap()).contains("not recognized as read-only"));
+
+    assert!(connection
+        .execute_read_with_retry("MATCH (n RETURN n", None, &policy)
+        .is_err());
+    assert_eq!(ConnectionStatus::Bad, connection.status());
+}
+
+#[test]
+#[serial]
+fn query_timings() {
+    let mut connection = initialize();
+    execute_query(&mut connection, "UNWIND range(1, 3) AS x RETURN x;");
+    assert!(connection.fetchall().is_ok());
+    assert_eq!(connection.query_timings(), None);
+
+    connection.set_collect_query_timings(true);
+    execute_query(&mut connection, "UNWIND range(1, 3) AS x RETURN x;");
+    assert!(connection.fetchall().is_ok());
+    let timings = connection.query_timings().unwrap();
+    assert!(timings.pulls > 0);
+    assert!(timings.fetches > timings.pulls);
+    assert!(timings.run > Duration::from_secs(0));
+
+    connection.set_collect_query_timings(false);
+    assert_eq!(connection.query_timings(), None);
+}
+
+#[test]
+#[serial]
+fn drop_policy_rollback() {
+    let mut connection = initialize();
+    assert_eq!(connection.drop_policy(), DropPolicy::Rollback);
+    execute_query(&mut connection, "CREATE (:Dropped);");
+    assert!(connection.fetchall().is_ok());
+    assert_eq!(ConnectionStatus::InTransaction, connection.status());
+    drop(connection);
+
+    let mut connection = initialize();
+    execute_query(&mut connection, "MATCH (n:Dropped) RETURN n;");
+    assert_eq!(connection.fetchall().unwrap().len(), 0);
+}
+
+#[test]
+#[serial]
+#[should_panic(expected = "Connection dropped with an open transaction")]
+fn drop_policy_panic() {
+    let mut connection = initialize();
+    connection.set_drop_policy(DropPolicy::Panic);
+    execute_query(&mut connection, "CREATE (:Dropped);");
+    assert!(connection.fetchall().is_ok());
+    drop(connection);
+}
+
+#[test]
+#[serial]
+fn leak_transaction() {
+    let mut connection = initialize();
+    connection.set_drop_policy(DropPolicy::Panic);
+    execute_query(&mut connection, "CREATE (:Leaked);");
+    assert!(connection.fetchall().is_ok());
+    assert_eq!(ConnectionStatus::InTransaction, connection.status());
+    connection.leak_transaction();
+}
+
+#[test]
+#[serial]
+fn begin_with_options() {
+    initialize();
+    let mut connection = get_connection(&ConnectParams {
+        address: Some(String::from("127.0.0.1")),
+        autocommit: true,
+        ..Default::default()
+    });
+    let options = TxOptions {
+        timeout: Some(Duration::from_secs(10)),
+        metadata: Some(hashmap! {
+            String::from("job") => QueryParam::String(String::from("test")),
+        }),
+        mode: Some(AccessMode::Write),
+    };
+    assert!(connection.begin(&options).is_ok());
+    assert_eq!(ConnectionStatus::InTransaction, connection.status());
+    assert!(connection.begin(&options).is_err());
+
+    execute_query(&mut connection, "CREATE (:Begin);");
+    assert!(connection.fetchall().is_ok());
+    assert_eq!(ConnectionStatus::InTransaction, connection.status());
+    assert!(connection.rollback().is_ok());
+    assert_eq!(ConnectionStatus::Ready, connection.status());
+
+    assert!(connection.begin(&TxOptions::default()).is_ok());
+    execute_query(&mut connection, "MATCH (n:Begin) RETURN n;");
+    assert_eq!(connection.fetchall().unwrap().len(), 0);
+    assert!(connection.commit().is_ok());
+    assert_eq!(ConnectionStatus::Ready, connection.status());
+}
+
+#[cfg(feature = "iterator")]
+#[test]
+#[serial]
+fn query_rows() {
+    let mut connection = initialize();
+    let rows = match connection.query("UNWIND [1, 2, 3] AS x RETURN x", None) {
+        Ok(x) => x,
+        Err(err) => panic!("Query failed: {}", err),
+    };
+    assert_eq!(rows.columns(), &[String::from("x")]);
+    let values: Vec<Value> = rows.map(|x| x.unwrap().values[0].clone()).collect();
+    assert_eq!(values, vec![Value::Int(1), Value::Int(2), Value::Int(3)]);
+    assert_eq!(ConnectionStatus::InTransaction, connection.status());
+    assert!(connection.commit().is_ok());
+
+    assert!(connection.query("MATCH (n RETURN n", None).is_err());
+}
+
+#[test]
+#[serial]
+fn isolation_level_and_storage_mode() {
+    let mut connection = initialize();
+    assert!(connection
+        .set_isolation_level(IsolationLevel::ReadCommitted)
+        .is_ok());
+    assert!(connection
+        .set_isolation_level(IsolationLevel::SnapshotIsolation)
+        .is_ok());
+    assert!(connection
+        .set_storage_mode(StorageMode::InMemoryAnalytical)
+        .is_ok());
+    assert!(connection
+        .set_storage_mode(StorageMode::InMemoryTransactional)
+        .is_ok());
+    assert_eq!(ConnectionStatus::Ready, connection.status());
+
+    execute_query(&mut connection, "RETURN 1;");
+    assert!(connection.fetchall().is_ok());
+    let result = connection.set_isolation_level(IsolationLevel::ReadUncommitted);
+    assert!(result.is_err());
+    assert_eq!(
+        format!("{}", result.err().unwrap()),
+        "Can't call set_isolation_level while in transaction"
+    );
+    assert!(connection.rollback().is_ok());
+}
+
+#[test]
+fn use_database_query_escaping() {
+    assert_eq!(use_database_query("tenant"), "USE DATABASE `tenant`");
+    assert_eq!(use_database_query("a`b"), "USE DATABASE `a``b`");
+}
+
+#[test]
+#[serial]
+fn connect_to_missing_database() {
+    let connect_prms = ConnectParams {
+        address: Some(String::from("127.0.0.1")),
+        database: Some(String::from("missing_database")),
+        ..Default::default()
+    };
+    assert!(Connection::connect(&connect_prms).is_err());
+}
+
+#[test]
+#[serial]
+fn ping() {
+    let mut connection = initialize();
+    assert!(connection.ping().is_ok());
+    assert_eq!(ConnectionStatus::Ready, connection.status());
+
+    execute_query(&mut connection, "CREATE (:Ping);");
+    let result = connection.ping();
+    assert!(result.is_err());
+    assert!(format!("{}", result.err().unwrap()).contains("while executing"));
+    assert!(connection.fetchall().is_ok());
+
+    assert!(connection.ping().is_ok());
+    assert_eq!(ConnectionStatus::InTransaction, connection.status());
+    execute_query(&mut connection, "MATCH (n:Ping) RETURN n;");
+    assert_eq!(connection.fetchall().unwrap().len(), 1);
+    assert!(connection.rollback().is_ok());
+}
+
+#[test]
+#[serial]
+fn reset() {
+    let mut connection = initialize();
+    assert!(connection.reset().is_ok());
+    assert_eq!(ConnectionStatus::Ready, connection.status());
+
+    assert!(connection.execute("MATCH (n RETURN n;", None).is_err());
+    assert_eq!(ConnectionStatus::Bad, connection.status());
+    assert!(connection.reset().is_ok());
+    assert_eq!(ConnectionStatus::Ready, connection.status());
+
+    execute_query(&mut connection, "CREATE (:Reset);");
+    assert_eq!(ConnectionStatus::Executing, connection.status());
+    assert!(connection.reset().is_ok());
+    assert_eq!(ConnectionStatus::Ready, connection.status());
+
+    execute_query(&mut connection, "MATCH (n:Reset) RETURN n;");
+    assert_eq!(connection.fetchall().unwrap().len(), 0);
+    assert_eq!(ConnectionStatus::InTransaction, connection.status());
+    assert!(connection.reset().is_ok());
+    assert_eq!(ConnectionStatus::Ready, connection.status());
+
+    connection.close();
+    assert!(connection.reset().is_err());
+}
+
+#[test]
+#[serial]
+fn elide_read_begin() {
+    let mut connection = initialize();
+    assert!(!connection.elide_read_begin());
+    connection.set_elide_read_begin(true);
+
+    execute_query(&mut connection, "MATCH (n) RETURN n;");
+    assert!(connection.fetchall().is_ok());
+    assert_eq!(ConnectionStatus::Ready, connection.status());
+
+    execute_query(&mut connection, "CREATE (n) RETURN n;");
+    assert!(connection.fetchall().is_ok());
+    assert_eq!(ConnectionStatus::InTransaction, connection.status());
+
+    execute_query(&mut connection, "MATCH (n) RETURN n;");
+    assert_eq!(connection.fetchall().unwrap().len(), 1);
+    assert_eq!(ConnectionStatus::InTransaction, connection.status());
+    assert!(connection.rollback().is_ok());
+}
+
+#[test]
+#[serial]
+fn try_clone() {
+    let mut connection = initialize();
+    connection.set_arraysize(5);
+    execute_query(&mut connection, "CREATE (:Clone);");
+    assert!(connection.fetchall().is_ok());
+    assert_eq!(ConnectionStatus::InTransaction, connection.status());
+
+    let mut cloned = match connection.try_clone() {
+        Ok(x) => x,
+        Err(err) => panic!("Cloning connection failed: {}", err),
+    };
+    assert_eq!(ConnectionStatus::Ready, cloned.status());
+    assert_eq!(cloned.arraysize(), 5);
+    assert_eq!(cloned.lazy(), connection.lazy());
+    assert_eq!(cloned.autocommit(), connection.autocommit());
+
+    execute_query(&mut cloned, "MATCH (n:Clone) RETURN count(n);");
+    let records = cloned.fetchall().unwrap();
+    assert_eq!(records[0].values[0], Value::Int(0));
+
+    assert!(connection.commit().is_ok());
+}
+
+#[test]
+#[serial]
+fn execute_many() {
+    let mut connection = initialize();
+
+    let rows = (0..25).map(|id| {
+        hashmap! {
+            String::from("id") => QueryParam::Int(id),
+            String::from("name") => QueryParam::String(format!("name_{}", id)),
+        }
+    });
+    let stats =
+        match connection.execute_many("CREATE (:Person {id: row.id, name: row.name})", rows, 10) {
+            Ok(x) => x,
+            Err(err) => panic!("Execute many failed: {}", err),
+        };
+    assert_eq!(stats.nodes_created, 25);
+    assert_eq!(stats.properties_set, 50);
+    assert_eq!(ConnectionStatus::InTransaction, connection.status());
+    assert!(connection.commit().is_ok());
+
+    let records = execute_query_and_fetchall("MATCH (n:Person) RETURN count(n)");
+    assert_eq!(records[0].values[0], Value::Int(25));
+
+    assert!(connection
+        .execute_many("CREATE ()", Vec::new(), 0)
+        .err()
+        .unwrap()
+        .to_string()
+        .contains("Batch size"));
+}
+
+#[test]
+#[serial]
+fn transaction_conflict_stats() {
+    let mut connection = initialize();
+    let mut other = get_connection(&ConnectParams {
+        address: Some(String::from("127.0.0.1")),
+        ..Default::default()
+    });
+    execute_query_and_fetchall("CREATE (:Counter {value: 0});");
+
+    let query = "MATCH (n:Counter) SET n.value = n.value + 1;";
+    execute_query(&mut connection, query);
+    assert!(connection.fetchall().is_ok());
+
+    let err = match other.execute(query, None) {
+        Ok(_) => other.fetchall().err().unwrap(),
+        Err(err) => err,
+    };
+    assert!(err.is_conflict());
+    assert!(format!("{}", err).contains("conflict 1 in a row"));
+    let stats = other.conflict_stats();
+    assert_eq!(stats.conflicts, 1);
+    assert_eq!(stats.consecutive_conflicts, 1);
+    assert_eq!(stats.last_conflicting_query, Some(String::from(query)));
+
+    assert!(connection.commit().is_ok());
+    other.reset_conflict_stats();
+    assert_eq!(other.conflict_stats().conflicts, 0);
+}
+
+#[test]
+#[serial]
+fn query_policy_deny() {
+    let mut connection = initialize();
+    connection.set_query_policy(Some(Box::new(|query, _params| {
+        if query.contains("DELETE") {
+            PolicyDecision::Deny(String::from("Deleting is not allowed"))
+        } else {
+            PolicyDecision::Allow
+        }
+    })));
+
+    let result = connection.execute("MATCH (n) DETACH DELETE n;", None);
+    assert!(result.is_err());
+    assert!(format!("{}", result.err().unwrap()).contains("Deleting is not allowed"));
+    assert_eq!(ConnectionStatus::Ready, connection.status());
+
+    assert!(connection
+        .execute_without_results("MATCH (n) DETACH DELETE n;")
+        .is_err());
+    assert!(connection.execute("RETURN 1;", None).is_ok());
+}
+
+#[test]
+#[serial]
+fn query_policy_rewrite() {
+    let mut connection = initialize();
+    execute_query_and_fetchall("CREATE (:Person {tenant: 'a'}), (:Person {tenant: 'b'});");
+    connection.set_query_policy(Some(Box::new(|_query, params| {
+        let mut params = params.cloned().unwrap_or_default();
+        params.insert(
+            String::from("tenant"),
+            QueryParam::String(String::from("a")),
+        );
+        PolicyDecision::Rewrite(
+            String::from("MATCH (n:Person) WHERE n.tenant = $tenant RETURN n;"),
+            Some(params),
+        )
+    })));
+
+    execute_query(&mut connection, "MATCH (n:Person) RETURN n;");
+    let records = connection.fetchall().unwrap();
+    assert_eq!(records.len(), 1);
+}
+
+#[test]
+#[serial]
+fn statement_stats() {
+    let mut connection = initialize();
+    assert!(connection.statement_stats().is_empty());
+    connection.set_collect_statement_stats(true);
+
+    for i in 0..3 {
+        execute_query(&mut connection, &format!("RETURN {};", i));
+        connection.fetchall().unwrap();
+    }
+    assert!(connection.execute("RETURN $missing;", None).is_err());
+
+    let stats = connection.statement_stats();
+    assert_eq!(stats.len(), 2);
+    let return_stats = stats.iter().find(|x| x.query == "RETURN ?").unwrap();
+    assert_eq!(return_stats.calls, 3);
+    assert_eq!(return_stats.errors, 0);
+    let error_stats = stats.iter().find(|x| x.query == "RETURN $missing").unwrap();
+    assert_eq!(error_stats.errors, 1);
+
+    connection.reset_statement_stats();
+    assert!(connection.statement_stats().is_empty());
+}
+
+#[test]
+#[serial]
+fn connect_with_resolver() {
+    let connect_prms = ConnectParams {
+        host: Some(String::from("memgraph.test")),
+        resolver: Some(Arc::new(|host: &str| {
+            assert_eq!(host, "memgraph.test");
+            Ok(vec!["127.0.0.1".parse().unwrap()])
+        })),
+        ..Default::default()
+    };
+    let connection = get_connection(&connect_prms);
+    assert_eq!(connection.status(), ConnectionStatus::Ready);
+}
+
+#[test]
+#[serial]
+fn connect_with_resolver_error() {
+    let connect_prms = ConnectParams {
+        host: Some(String::from("memgraph.test")),
+        resolver: Some(Arc::new(|host: &str| {
+            Err(MgError::new(format!("Unknown host {}", host)))
+        })),
+        ..Default::default()
+    };
+    match Connection::connect(&connect_prms) {
+        Ok(_) => panic!("Connecting to an unresolvable host should fail"),
+        Err(err) => assert_eq!(format!("{}", err), "Unknown host memgraph.test"),
+    }
+}
+
+#[test]
+#[serial]
+fn fetch_with_progress() {
+    let mut connection = initialize();
+
+    execute_query(&mut connection, "UNWIND range(1, 5) AS x RETURN x;");
+    let mut reported = Vec::new();
+    let records = connection
+        .fetch_with_progress(2, |fetched, _| reported.push(fetched))
+        .unwrap();
+    assert_eq!(records.len(), 5);
+    assert_eq!(reported, vec![2, 4, 5]);
+    connection.commit().unwrap();
+
+    execute_query(&mut connection, "UNWIND range(1, 4) AS x RETURN x;");
+    let mut reported = Vec::new();
+    connection
+        .fetch_with_progress(2, |fetched, _| reported.push(fetched))
+        .unwrap();
+    assert_eq!(reported, vec![2, 4]);
+}
+
+#[test]
+#[serial]
+fn impersonated_user() {
+    let connect_params = ConnectParams {
+        address: Some(String::from("127.0.0.1")),
+        impersonated_user: Some(String::from("rsmgclient_unknown_user")),
+        ..Default::default()
+    };
+    let mut connection = get_connection(&connect_params);
+    // Statements run by the driver itself are not impersonated.
+    assert!(connection.ping().is_ok());
+    // The server refuses to run a user query as a user which doesn't exist.
+    assert!(connection.execute("RETURN 1", None).is_err());
+}
+
+#[test]
+#[serial]
+fn execute_with_options() {
+    assert!(make_run_extra(None, None).is_null());
+    assert!(make_run_extra(None, Some(&HashMap::new())).is_null());
+    let extra = make_run_extra(Some("alice"), None);
+    assert!(!extra.is_null());
+    destroy_mg_map(extra);
+
+    let options = ExecuteOptions {
+        timeout: Some(Duration::from_secs(2)),
+        tx_metadata: Some(hashmap! {
+            String::from("job") => QueryParam::String(String::from("report")),
+        }),
+        ..Default::default()
+    };
+    let tx_extra = options.to_tx_extra();
+    assert_eq!(tx_extra.len(), 2);
+    assert!(matches!(
+        tx_extra.get("tx_timeout"),
+        Some(QueryParam::Int(2000))
+    ));
+    assert!(ExecuteOptions::default().to_tx_extra().is_empty());
+
+    let mut connection = initialize();
+    let columns = connection
+        .execute_with_options("RETURN 1 AS x;", None, &ExecuteOptions::default())
+        .unwrap();
+    assert_eq!(columns, vec![String::from("x")]);
+    assert_eq!(connection.fetchall().unwrap().len(), 1);
+    connection.commit().unwrap();
+
+    connection.set_autocommit(true);
+    connection
+        .execute_with_options("SHOW TRANSACTIONS", None, &options)
+        .unwrap();
+    let transactions = connection.fetchall().unwrap();
+    assert!(transactions.iter().any(|x| {
+        TransactionInfo::from_record(x).metadata.get("job")
+            == Some(&Value::String(String::from("report")))
+    }));
+
+    connection.set_collect_query_timings(true);
+    let options = ExecuteOptions {
+        n_pull: Some(3),
+        ..Default::default()
+    };
+    connection
+        .execute_with_options("UNWIND range(1, 10) AS x RETURN x", None, &options)
+        .unwrap();
+    assert_eq!(connection.fetchall().unwrap().len(), 10);
+    assert_eq!(connection.query_timings().unwrap().pulls, 4);
+
+    let options = ExecuteOptions {
+        n_pull: Some(0),
+        ..Default::default()
+    };
+    connection
+        .execute_with_options("UNWIND range(1, 10) AS x RETURN x", None, &options)
+        .unwrap();
+    assert_eq!(connection.fetchall().unwrap().len(), 10);
+    assert_eq!(connection.query_timings().unwrap().pulls, 1);
+}
+
+#[test]
+#[serial]
+fn delete_all() {
+    let mut connection = initialize();
+    connection.set_autocommit(true);
+    connection
+        .execute_without_results(
+            "UNWIND range(1, 25) AS x CREATE (:Person {id: x})-[:Likes]->(:Software);",
+        )
+        .unwrap();
+
+    let stats = match connection.delete_all("(n:Person) WHERE n.id > 5", 10) {
+        Ok(x) => x,
+        Err(err) => panic!("Delete all failed: {}", err),
+    };
+    assert_eq!(stats.nodes_deleted, 20);
+    assert_eq!(stats.relationships_deleted, 20);
+
+    let records = execute_query_and_fetchall("MATCH (n:Person) RETURN count(n)");
+    assert_eq!(records[0].values[0], Value::Int(5));
+
+    assert!(connection.delete_all("(n)", 0).is_err());
+}
+
+#[test]
+#[serial]
+fn sync_connection() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<SyncConnection>();
+
+    let mut connection = initialize();
+    connection.set_autocommit(true);
+    let connection = SyncConnection::new(connection);
+
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            let connection = connection.clone();
+            std::thread::spawn(move || {
+                let mut connection = connection.lock();
+                let params = crate::params! { "id" => i };
+                connection
+                    .execute("CREATE (:Worker {id: $id});", Some(&params))
+                    .unwrap();
+                connection.fetchall().unwrap();
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    connection
+        .execute("MATCH (n:Worker) RETURN count(n);", None)
+        .unwrap();
+    let records = connection.fetchall().unwrap();
+    assert_eq!(records[0].values[0], Value::Int(4));
+    assert_eq!(connection.status(), ConnectionStatus::Ready);
+}
+
+#[test]
+#[serial]
+fn server_error_code() {
+    let mut connection = initialize();
+    let err = connection.execute("SELECT 1;", None).err().unwrap();
+    assert_eq!(err.code(), Some(crate::ServerErrorCode::ClientError));
+    assert!(!err.is_retryable());
+}
+
+#[test]
+#[serial]
+fn try_set_lazy_autocommit_close() {
+    let mut connection = initialize();
+    assert!(connection.try_set_lazy(false).is_ok());
+    assert!(!connection.lazy());
+
+    connection.status = ConnectionStatus::InTransaction;
+    let err = connection.try_set_lazy(true).err().unwrap();
+    assert_eq!(format!("{}", err), "Can't set lazy while in transaction");
+    assert!(!connection.lazy());
+    let err = connection.try_set_autocommit(true).err().unwrap();
+    assert_eq!(
+        format!("{}", err),
+        "Can't set autocommit while in transaction"
+    );
+    assert!(!connection.autocommit());
+
+    connection.status = ConnectionStatus::Bad;
+    assert!(connection.try_close().is_ok());
+    assert_eq!(connection.status(), ConnectionStatus::Closed);
+}
+
+#[test]
+fn tofu_known_hosts() {
+    let content = "# known hosts\nlocalhost AB:CD\n\n127.0.0.1 EF:01\n";
+    assert_eq!(tofu::find_known_host(content, "localhost"), Some("AB:CD"));
+    assert_eq!(tofu::find_known_host(content, "127.0.0.1"), Some("EF:01"));
+    assert_eq!(tofu::find_known_host(content, "memgraph"), None);
+
+    let store_path = std::env::temp_dir().join(format!("rsmgclient_tofu_{}", std::process::id()));
+    let _ = std::fs::remove_file(&store_path);
+    let callback = TrustCallback::from(SSLTrust::Tofu {
+        store_path: store_path.clone(),
+    });
+    let mut info = TrustInfo {
+        hostname: String::from("localhost"),
+        ip: String::from("127.0.0.1"),
+        port: 7687,
+        key_type: String::from("rsaEncryption"),
+        fingerprint: String::from("AB:CD"),
+    };
+    assert_eq!(callback.call(&info), TrustDecision::Accept);
+    assert_eq!(callback.call(&info), TrustDecision::Accept);
+    info.fingerprint = String::from("EF:01");
+    assert_eq!(callback.call(&info), TrustDecision::Reject);
+    info.port = 7688;
+    assert_eq!(callback.call(&info), TrustDecision::Accept);
+    info.hostname = String::new();
+    info.ip = String::from("::1");
+    assert_eq!(callback.call(&info), TrustDecision::Accept);
+    assert_eq!(
+        std::fs::read_to_string(&store_path).unwrap(),
+        "localhost:7687 AB:CD\nlocalhost:7688 EF:01\n[::1]:7688 EF:01\n"
+    );
+
+    // Entries without a port apply to every port.
+    std::fs::write(&store_path, "127.0.0.1 AB:CD").unwrap();
+    info.ip = String::from("127.0.0.1");
+    assert_eq!(callback.call(&info), TrustDecision::Reject);
+    info.fingerprint = String::from("AB:CD");
+    assert_eq!(callback.call(&info), TrustDecision::Accept);
+    std::fs::remove_file(&store_path).unwrap();
+}
+
+#[test]
+#[serial]
+fn server_info() {
+    let mut connection = initialize();
+    let info = connection.server_info().unwrap();
+    assert!(info.version.chars().next().unwrap().is_ascii_digit());
+    assert_eq!(connection.status(), ConnectionStatus::Ready);
+    assert!(!connection.autocommit());
+}
+
+#[test]
+#[serial]
+fn fetch_batch_size() {
+    let mut connection = initialize();
+    assert_eq!(connection.fetch_batch_size(), 1000);
+    connection.set_fetch_batch_size(2);
+    connection.set_collect_query_timings(true);
+
+    execute_query(&mut connection, "UNWIND range(1, 5) AS x RETURN x;");
+    let records = connection.fetchall().unwrap();
+    let values: Vec<Value> = records.into_iter().map(|x| x.values[0].clone()).collect();
+    assert_eq!(values, (1..=5).map(Value::Int).collect::<Vec<Value>>());
+    assert_eq!(connection.query_timings().unwrap().pulls, 3);
+    assert_eq!(connection.status(), ConnectionStatus::InTransaction);
+
+    connection.set_fetch_batch_size(0);
+    execute_query(&mut connection, "UNWIND range(1, 5) AS x RETURN x;");
+    assert_eq!(connection.fetchall().unwrap().len(), 5);
+    assert_eq!(connection.query_timings().unwrap().pulls, 1);
+}
+
+#[test]
+#[serial]
+fn fetchmany_single_pull() {
+    let mut connection = initialize();
+    connection.set_collect_query_timings(true);
+
+    execute_query(&mut connection, "UNWIND range(1, 10) AS x RETURN x;");
+    assert_eq!(connection.fetchmany(Some(3)).unwrap().len(), 3);
+    assert_eq!(connection.query_timings().unwrap().pulls, 1);
+    let records = connection.fetchmany(Some(3)).unwrap();
+    assert_eq!(records[0].values[0], Value::Int(4));
+    assert_eq!(connection.query_timings().unwrap().pulls, 2);
+    assert_eq!(connection.fetchall().unwrap().len(), 4);
+}
+
+#[test]
+#[serial]
+fn summary_ref_and_take() {
+    let mut connection = initialize();
+    assert!(connection.summary_ref().is_none());
+
+    execute_query(&mut connection, "RETURN 1;");
+    connection.fetchall().unwrap();
+    assert_eq!(
+        connection.summary_ref().unwrap().query_type,
+        Some(QueryType::Read)
+    );
+    assert_eq!(connection.summary_ref(), connection.summary().as_ref());
+
+    let summary = connection.take_summary().unwrap();
+    assert!(!summary.has_more);
+    assert!(connection.summary_ref().is_none());
+    assert!(connection.take_summary().is_none());
+}
+
+#[test]
+#[serial]
+fn columns() {
+    let mut connection = initialize();
+    assert!(connection.columns().is_none());
+
+    execute_query(&mut connection, "RETURN 1 AS a, 2 AS b;");
+    connection.fetchall().unwrap();
+    assert_eq!(
+        connection.columns(),
+        Some(&[String::from("a"), String::from("b")][..])
+    );
+
+    assert!(connection.execute("RETURN $missing;", None).is_err());
+    assert!(connection.columns().is_none());
+}
+
+#[test]
+fn record_index_by_column() {
+    let records = execute_query_and_fetchall("RETURN 1 AS a, 'x' AS b;");
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0]["a"], Value::Int(1));
+    assert_eq!(records[0]["b"], Value::String(String::from("x")));
+    assert_eq!(records[0][1], Value::String(String::from("x")));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn fetchall_json() {
+    let mut connection = initialize();
+    execute_query(&mut connection, "RETURN 1 AS a, 'x' AS b;");
+    assert_eq!(
+        connection.fetchall_json().unwrap(),
+        serde_json::json!({"columns": ["a", "b"], "records": [{"a": 1, "b": "x"}]})
+    );
+}
+
+#[test]
+#[serial]
+fn merge_node_and_relationship() {
+    let mut connection = initialize();
+    connection.set_autocommit(true);
+
+    let alice = connection
+        .merge_node(
+            &["Person"],
+            &crate::params! { "email" => "alice@example.com" },
+            &crate::params! { "name" => "Alice" },
+        )
+        .unwrap();
+    assert_eq!(alice.labels, vec![String::from("Person")]);
+    assert_eq!(
+        alice.properties.get("name"),
+        Some(&Value::String(String::from("Alice")))
+    );
+
+    let same = connection
+        .merge_node(
+            &["Person"],
+            &crate::params! { "email" => "alice@example.com" },
+            &crate::params! { "age" => 30 },
+        )
+        .unwrap();
+    assert_eq!(same.id, alice.id);
+    assert_eq!(same.properties.len(), 3);
+
+    let bob = connection
+        .merge_node(
+            &["Person", "Admin"],
+            &crate::params! { "name" => "Bob" },
+            &HashMap::new(),
+        )
+        .unwrap();
+    let knows = connection
+        .merge_relationship(
+            alice.id,
+            bob.id,
+            "KNOWS",
+            &HashMap::new(),
+            &crate::params! { "since" => 2020 },
+        )
+        .unwrap();
+    assert_eq!((knows.start_id, knows.end_id), (alice.id, bob.id));
+    assert_eq!(knows.type_, "KNOWS");
+    let again = connection
+        .merge_relationship(alice.id, bob.id, "KNOWS", &HashMap::new(), &HashMap::new())
+        .unwrap();
+    assert_eq!(again.id, knows.id);
+    assert_eq!(again.properties.get("since"), Some(&Value::Int(2020)));
+
+    assert!(connection
+        .merge_relationship(alice.id, -1, "KNOWS", &HashMap::new(), &HashMap::new())
+        .is_err());
+}
+
+#[test]
+#[serial]
+fn insert_nodes_and_relationships() {
+    let mut connection = initialize();
+    connection.set_autocommit(true);
+
+    let rows = (0..2500).map(|x| crate::params! { "id" => x });
+    let stats = connection.insert_nodes("Person", rows).unwrap();
+    assert_eq!(stats.nodes_created, 2500);
+    assert_eq!(connection.status(), ConnectionStatus::Ready);
+
+    execute_query(
+        &mut connection,
+        "MATCH (n:Person) RETURN id(n) ORDER BY n.id LIMIT 2",
+    );
+    let ids: Vec<i64> = connection
+        .fetchall()
+        .unwrap()
+        .into_iter()
+        .map(|x| match x.values[0] {
+            Value::Int(x) => x,
+            _ => panic!("Unexpected id"),
+        })
+        .collect();
+    let rows = vec![
+        (ids[0], ids[1], crate::params! { "since" => 2020 }),
+        (ids[0], -1, HashMap::new()),
+    ];
+    let stats = connection.insert_relationships("KNOWS", rows).unwrap();
+    assert_eq!(stats.relationships_created, 1);
+
+    let records = execute_query_and_fetchall("MATCH ()-[r:KNOWS]->() RETURN r.since");
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].values[0], Value::Int(2020));
+
+    connection.begin(&TxOptions::default()).unwrap();
+    let rows = (0..10).map(|x| crate::params! { "id" => x });
+    connection.insert_nodes("Temp", rows).unwrap();
+    assert_eq!(connection.status(), ConnectionStatus::InTransaction);
+    connection.rollback().unwrap();
+    let records = execute_query_and_fetchall("MATCH (n:Temp) RETURN n");
+    assert!(records.is_empty());
+}
+
+#[test]
+fn parse_memory_sizes() {
+    use super::introspection::parse_memory;
+
+    assert_eq!(parse_memory(&Value::Int(1024)), Some(1024));
+    assert_eq!(parse_memory(&Value::String(String::from("0B"))), Some(0));
+    assert_eq!(
+        parse_memory(&Value::String(String::from("1.50KiB"))),
+        Some(1536)
+    );
+    assert_eq!(
+        parse_memory(&Value::String(String::from("43.16MiB"))),
+        Some(45_256_540)
+    );
+    assert_eq!(
+        parse_memory(&Value::String(String::from("2GiB"))),
+        Some(2 << 30)
+    );
+    assert_eq!(parse_memory(&Value::String(String::from("lots"))), None);
+    assert_eq!(parse_memory(&Value::Null), None);
+}
+
+#[test]
+fn storage_info_from_records() {
+    let row = |name: &str, value: Value| {
+        Record::new(
+            Arc::default(),
+            vec![Value::String(String::from(name)), value],
+        )
+    };
+    let info = StorageInfo::from_records(vec![
+        row("vertex_count", Value::Int(10)),
+        row("edge_count", Value::Int(20)),
+        row("average_degree", Value::Float(4.0)),
+        row("memory_res", Value::String(String::from("1.00MiB"))),
+        row("disk_usage", Value::Int(2048)),
+        row(
+            "storage_mode",
+            Value::String(String::from("IN_MEMORY_TRANSACTIONAL")),
+        ),
+    ]);
+    assert_eq!(info.vertex_count, Some(10));
+    assert_eq!(info.edge_count, Some(20));
+    assert_eq!(info.average_degree, Some(4.0));
+    assert_eq!(info.memory_res, Some(1 << 20));
+    assert_eq!(info.disk_usage, Some(2048));
+    assert_eq!(info.peak_memory_res, None);
+    assert_eq!(
+        info.storage_mode.as_deref(),
+        Some("IN_MEMORY_TRANSACTIONAL")
+    );
+    assert_eq!(info.raw.len(), 6);
+}
+
+#[test]
+#[serial]
+fn storage_info_and_config() {
+    let mut connection = initialize();
+    connection.set_autocommit(true);
+    connection
+        .execute_without_results("CREATE (:Person)-[:KNOWS]->(:Person)")
+        .unwrap();
+
+    let info = connection.storage_info().unwrap();
+    assert_eq!(info.vertex_count, Some(2));
+    assert_eq!(info.edge_count, Some(1));
+    assert!(info.memory_res.is_some());
+
+    let config = connection.show_config().unwrap();
+    assert!(config.iter().any(|x| x.name == "bolt_port"));
+    assert_eq!(connection.status(), ConnectionStatus::Ready);
+}
+
+#[test]
+fn replication_queries() {
+    use super::replication::{register_replica_query, replication_role_query};
+
+    let spec = ReplicaSpec {
+        name: String::from("replica_1"),
+        mode: ReplicationMode::Sync,
+        address: String::from("10.0.0.2:10000"),
+    };
+    assert_eq!(
+        register_replica_query(&spec).unwrap(),
+        "REGISTER REPLICA `replica_1` SYNC TO '10.0.0.2:10000'"
+    );
+    let spec = ReplicaSpec {
+        name: String::from("replica`2"),
+        mode: ReplicationMode::Async,
+        address: String::from("host'; DROP"),
+    };
+    assert_eq!(
+        register_replica_query(&spec).unwrap(),
+        "REGISTER REPLICA `replica``2` ASYNC TO 'host\\'; DROP'"
+    );
+
+    assert_eq!(
+        replication_role_query(&ReplicationRole::Main),
+        "SET REPLICATION ROLE TO MAIN"
+    );
+    assert_eq!(
+        replication_role_query(&ReplicationRole::Replica { port: 10000 }),
+        "SET REPLICATION ROLE TO REPLICA WITH PORT 10000"
+    );
+}
+
+#[test]
+#[serial]
+fn show_replicas_on_main() {
+    let mut connection = initialize();
+    assert!(connection.show_replicas().unwrap().is_empty());
+    assert!(connection.drop_replica("missing").is_err());
+}
+
+#[test]
+fn procedure_call_query() {
+    use super::procedure::call_query;
+
+    assert_eq!(
+        call_query("mg.procedures", 0, &[]).unwrap(),
+        "CALL `mg`.`procedures`() YIELD *"
+    );
+    assert_eq!(
+        call_query("pagerank.get", 2, &["node", "rank"]).unwrap(),
+        "CALL `pagerank`.`get`($arg0, $arg1) YIELD `node`, `rank`"
+    );
+    assert!(call_query("mg..procedures", 0, &[]).is_err());
+}
+
+#[test]
+#[serial]
+fn call_procedure() {
+    let mut connection = initialize();
+    let records = connection
+        .call("mg.procedures", vec![])
+        .unwrap()
+        .yield_columns(&["name"])
+        .unwrap();
+    assert!(records
+        .iter()
+        .any(|x| x.values[0] == Value::String(String::from("mg.procedures"))));
+
+    struct Procedure {
+        name: String,
+        signature: String,
+    }
+    impl FromRecord for Procedure {
+        const COLUMNS: &'static [&'static str] = &["name", "signature"];
+
+        fn from_record(record: Record) -> Result<Self, MgError> {
+            let (name, signature) = <(String, String)>::try_from(record)?;
+            Ok(Procedure { name, signature })
+        }
+    }
+    let procedures: Vec<Procedure> = connection
+        .call("mg.procedures", vec![])
+        .unwrap()
+        .yield_as()
+        .unwrap();
+    assert!(procedures
+        .iter()
+        .any(|x| x.name == "mg.procedures" && x.signature.starts_with("mg.procedures(")));
+
+    assert!(connection.call("", vec![]).is_err());
+}
+
+#[test]
+fn query_plan_from_records() {
+    let row = |values: Vec<Value>| Record::new(Arc::default(), values);
+    let text = |x: &str| Value::String(String::from(x));
+    let plan = QueryPlan::from_records(&[
+        row(vec![text(" * Produce {n, m}")]),
+        row(vec![text(" * Cartesian {m : n}")]),
+        row(vec![text(" |\\")]),
+        row(vec![text(" | * ScanAllByLabel (n :Person)")]),
+        row(vec![text(" | * Once")]),
+        row(vec![text(" |\\")]),
+        row(vec![text(" | * Once")]),
+        row(vec![text(" * ScanAll (m)")]),
+        row(vec![text(" * Once")]),
+    ])
+    .unwrap();
+
+    let root = &plan.root;
+    assert_eq!(root.name, "Produce");
+    assert_eq!(root.details, "{n, m}");
+    assert!(root.profile.is_none());
+    let cartesian = &root.children[0];
+    assert_eq!(cartesian.name, "Cartesian");
+    let names: Vec<&str> = cartesian.children.iter().map(|x| x.name.as_str()).collect();
+    assert_eq!(names, vec!["ScanAllByLabel", "Once", "ScanAll"]);
+    assert_eq!(cartesian.children[0].details, "(n :Person)");
+    assert_eq!(cartesian.children[0].children[0].name, "Once");
+    assert!(cartesian.children[1].children.is_empty());
+    assert_eq!(cartesian.children[2].children[0].name, "Once");
+    assert_eq!(
+        plan.to_string(),
+        "* Produce {n, m}\n  * Cartesian {m : n}\n    * ScanAllByLabel (n :Person)\n      * Once\n    \
+         * Once\n    * ScanAll (m)\n      * Once\n"
+    );
+
+    let plan = QueryPlan::from_records(&[
+        row(vec![
+            text("* Produce"),
+            Value::Int(3),
+            text(" 25.00 %"),
+            text("  0.002 ms"),
+        ]),
+        row(vec![
+            text("* Once"),
+            Value::Int(2),
+            text(" 75.00 %"),
+            text("  0.006 ms"),
+        ]),
+    ])
+    .unwrap();
+    let profile = plan.root.profile.unwrap();
+    assert_eq!(profile.hits, 3);
+    assert_eq!(profile.relative_time, 25.0);
+    assert_eq!(profile.absolute_time, Duration::from_micros(2));
+    assert_eq!(plan.root.children[0].profile.unwrap().hits, 2);
+    assert_eq!(
+        plan.to_string(),
+        "* Produce [hits: 3, 25.00 %, 0.002 ms]\n  * Once [hits: 2, 75.00 %, 0.006 ms]\n"
+    );
+
+    assert!(QueryPlan::from_records(&[]).is_err());
+    assert!(QueryPlan::from_records(&[row(vec![Value::Int(1)])]).is_err());
+    assert!(QueryPlan::from_records(&[row(vec![text("* Once"), Value::Int(1)])]).is_err());
+    assert!(QueryPlan::from_records(&[row(vec![
+        text("* Once"),
+        Value::Int(1),
+        text(" 100.00 %"),
+        text("  inf ms"),
+    ])])
+    .is_err());
+}
+
+#[test]
+#[serial]
+fn explain_and_profile() {
+    let mut connection = initialize();
+    let plan = connection
+        .explain(
+            "MATCH (n) WHERE n.name = $name RETURN n",
+            Some(&create_params(String::from("name"), String::from("Alice"))),
+        )
+        .unwrap();
+    assert_eq!(plan.root.name, "Produce");
+    assert!(plan.root.profile.is_none());
+    assert!(!plan.root.children.is_empty());
+
+    let plan = connection.profile("MATCH (n) RETURN n", None).unwrap();
+    assert_eq!(plan.root.name, "Produce");
+    assert!(plan.root.profile.is_some());
+    assert_eq!(connection.status(), ConnectionStatus::Ready);
+
+    assert!(connection.explain("MATCH (n RETURN n", None).is_err());
+}
+
+#[test]
+fn transaction_info_from_record() {
+    use super::cancel::{transactions_to_terminate, CANCELLATION_TOKEN_KEY};
+
+    let columns = Arc::new(vec![
+        String::from("username"),
+        String::from("transaction_id"),
+        String::from("query"),
+        String::from("metadata"),
+    ]);
+    let row = |username: &str, id: &str, queries: Vec<&str>, token: &str| {
+        TransactionInfo::from_record(&Record::new(
+            columns.clone(),
+            vec![
+                Value::String(username.to_string()),
+                Value::String(id.to_string()),
+                Value::List(
+                    queries
+                        .into_iter()
+                        .map(|x| Value::String(x.to_string()))
+                        .collect(),
+                ),
+                Value::Map(hashmap! {
+                    String::from("job") => Value::String(String::from("import")),
+                    String::from(CANCELLATION_TOKEN_KEY) => Value::String(token.to_string()),
+                }),
+            ],
+        ))
+    };
+    let query = "UNWIND range(1, 100) AS x RETURN x";
+    let transactions = vec![
+        row("alice", "1", vec![query], "a"),
+        row("alice", "2", vec!["CREATE (:Node)", query], "b"),
+        row("alice", "3", vec![query], "b"),
+        row("bob", "4", vec!["SHOW TRANSACTIONS"], "c"),
+    ];
+    assert_eq!(transactions[1].username, "alice");
+    assert_eq!(transactions[1].transaction_id, "2");
+    assert_eq!(transactions[1].queries, vec!["CREATE (:Node)", query]);
+    assert_eq!(
+        transactions[1].metadata.get("job"),
+        Some(&Value::String(String::from("import")))
+    );
+
+    assert_eq!(transactions_to_terminate(&transactions, "a"), vec!["1"]);
+    assert_eq!(
+        transactions_to_terminate(&transactions, "b"),
+        vec!["2", "3"]
+    );
+    assert!(transactions_to_terminate(&transactions, "d").is_empty());
+}
+
+#[test]
+fn terminate_transactions_query() {
+    use super::transactions::terminate_transactions_query;
+
+    assert_eq!(
+        terminate_transactions_query(&["1", "it's"]).unwrap(),
+        r"TERMINATE TRANSACTIONS '1', 'it\'s'"
+    );
+}
+
+#[test]
+#[serial]
+fn show_and_terminate_transactions() {
+    let mut connection = initialize();
+    let transactions = connection.show_transactions().unwrap();
+    assert!(transactions
+        .iter()
+        .any(|x| x.queries.iter().any(|x| x == "SHOW TRANSACTIONS")));
+    assert!(connection.terminate_transactions(&[]).unwrap().is_empty());
+    assert!(connection
+        .terminate_transactions(&["999999999"])
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+#[serial]
+fn cancel_query() {
+    let mut connection = initialize();
+    let handle = connection.cancellation_handle();
+    assert!(!handle.cancel().unwrap());
+
+    let canceller = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(500));
+        handle.cancel().unwrap()
+    });
+    let result = connection
+        .execute(
+            "UNWIND range(1, 10000000000) AS x WITH sum(x) AS s RETURN s",
+            None,
+        )
+        .and_then(|_| connection.fetchall());
+    assert!(canceller.join().unwrap());
+    match result {
+        Ok(_) => panic!("Query was not cancelled"),
+        Err(err) => assert!(err.is_cancelled()),
+    }
+}
+
+#[test]
+#[serial]
+fn execute_script() {
+    let mut connection = initialize();
+    let stats = connection
+        .execute_script(
+            "CREATE (:Script {name: 'a;b'});\n\
+             // Second node;\n\
+             CREATE (:Script {name: 'c'});",
+        )
+        .unwrap();
+    assert_eq!(stats.nodes_created, 2);
+    assert_eq!(connection.status(), ConnectionStatus::Ready);
+    let records = execute_query_and_fetchall("MATCH (n:Script) RETURN n.name ORDER BY n.name");
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].values[0], Value::String(String::from("a;b")));
+
+    let err = connection
+        .execute_script("CREATE (:Script);\n\nMATCH (n:Script RETURN n;")
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("Statement 2 at line 3 failed: "));
+    assert_eq!(connection.status(), ConnectionStatus::Bad);
+    let records = execute_query_and_fetchall("MATCH (n:Script) RETURN count(n)");
+    assert_eq!(records[0].values[0], Value::Int(2));
+}
+
+#[test]
+fn prepared_query_check_params() {
+    let prepared =
+        PreparedQuery::new("q", "MATCH (n {name: $name}) WHERE n.age > $age RETURN n").unwrap();
+    assert!(prepared
+        .check_params(
+            "q",
+            Some(&create_params(String::from("name"), String::from("x")))
+        )
+        .is_err());
+    assert_eq!(
+        prepared.check_params("q", None).unwrap_err().to_string(),
+        "Parameters $name, $age of prepared query q are not provided"
+    );
+    let params = crate::params! { "name" => "Alice", "age" => 30 };
+    assert!(prepared.check_params("q", Some(&params)).is_ok());
+
+    assert!(PreparedQuery::new("q", "RETURN 1; RETURN 2").is_err());
+    assert!(PreparedQuery::new("q", "  // nothing").is_err());
+}
+
+#[test]
+#[serial]
+fn prepare_and_execute() {
+    let mut connection = initialize();
+    connection
+        .prepare("person", "RETURN $name AS name")
+        .unwrap();
+    assert_eq!(
+        connection.prepared_query("person"),
+        Some("RETURN $name AS name")
+    );
+
+    // Policy is applied to every execution, with its parameters.
+    connection.set_query_policy(Some(Box::new(|_, params| {
+        match params.and_then(|x| x.get("name")) {
+            Some(QueryParam::String(x)) if x == "Alice" => PolicyDecision::Allow,
+            _ => PolicyDecision::Deny(String::from("Denied")),
+        }
+    })));
+    let params = create_params(String::from("name"), String::from("Alice"));
+    assert_eq!(
+        connection
+            .execute_prepared("person", Some(&params))
+            .unwrap(),
+        vec![String::from("name")]
+    );
+    let records = connection.fetchall().unwrap();
+    assert_eq!(records[0].values[0], Value::String(String::from("Alice")));
+    let other_params = create_params(String::from("name"), String::from("Bob"));
+    assert_eq!(
+        connection
+            .execute_prepared("person", Some(&other_params))
+            .unwrap_err()
+            .to_string(),
+        "Denied"
+    );
+    connection.set_query_policy(Some(Box::new(|_, _| {
+        PolicyDecision::Rewrite(String::from("RETURN 1"), None)
+    })));
+    assert_eq!(
+        connection
+            .execute_prepared("person", Some(&params))
+            .unwrap_err()
+            .to_string(),
+        "Prepared query person can't be rewritten by the query policy"
+    );
+    connection.set_query_policy(None);
+
+    assert!(connection.execute_prepared("person", None).is_err());
+    assert!(connection
+        .try_clone()
+        .unwrap()
+        .prepared_query("person")
+        .is_some());
+    assert!(connection.unprepare("person"));
+    assert!(!connection.unprepare("person"));
+    assert!(connection
+        .execute_prepared("person", Some(&params))
+        .is_err());
+}
+
+#[test]
+#[serial]
+fn query_observer() {
+    struct Recorder(Arc<Mutex<Vec<String>>>);
+
+    impl QueryObserver for Recorder {
+        fn on_execute(&mut self, query: &str, params: Option<&HashMap<String, QueryParam>>) {
+            let params = params.map_or(0, |x| x.len());
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("execute {} {}", query, params));
+        }
+
+        fn on_complete(&mut self, _duration: Duration, summary: Option<&QuerySummary>) {
+            let has_summary = summary.is_some();
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("complete {}", has_summary));
+        }
+
+        fn on_error(&mut self, _error: &MgError) {
+            self.0.lock().unwrap().push(String::from("error"));
+        }
+    }
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let mut connection = initialize();
+    connection.set_observer(Some(Box::new(Recorder(events.clone()))));
+    let params = create_params(String::from("x"), String::from("y"));
+    connection.execute("RETURN $x AS x", Some(&params)).unwrap();
+    connection.fetchall().unwrap();
+    connection
+        .execute_without_results("CREATE (:Observed)")
+        .unwrap();
+    assert!(connection.execute("RETURN x", None).is_err());
+    assert_eq!(
+        *events.lock().unwrap(),
+        vec![
+            String::from("execute RETURN $x AS x 1"),
+            String::from("complete true"),
+            String::from("execute CREATE (:Observed) 0"),
+            String::from("complete true"),
+            String::from("execute RETURN x 0"),
+            String::from("error"),
+        ]
+    );
+}
+
+#[cfg(feature = "metrics")]
+#[test]
+fn metrics_error_kind() {
+    use super::instrumentation::error_kind;
+    use crate::ServerErrorCode;
+
+    let error = |code| MgError::with_code(String::from("Error"), code);
+    assert_eq!(
+        error_kind(&error(Some(ServerErrorCode::ClientError))),
+        "client"
+    );
+    assert_eq!(
+        error_kind(&error(Some(ServerErrorCode::TransientError))),
+        "transient"
+    );
+    assert_eq!(
+        error_kind(&error(Some(ServerErrorCode::DatabaseError))),
+        "database"
+    );
+    assert_eq!(error_kind(&error(None)), "other");
+    assert_eq!(
+        error_kind(&MgError::new(String::from(
+            "Cannot resolve conflicting transactions."
+        ))),
+        "conflict"
+    );
+    assert_eq!(
+        error_kind(&MgError::cancelled(String::from("Cancelled"), None)),
+        "cancelled"
+    );
+}
+
+#[test]
+fn wire_log_messages() {
+    let params = create_params(String::from("name"), String::from("Alice"));
+    assert_eq!(
+        run_message("RETURN $name", Some(&params), 0),
+        "RUN \"RETURN $name\" with 1 parameters -> SUCCESS"
+    );
+    assert_eq!(
+        run_message("RETURN x", None, bindings::MG_ERROR_CLIENT_ERROR),
+        "RUN \"RETURN x\" with 0 parameters -> FAILURE"
+    );
+    assert_eq!(
+        run_message("CREATE USER alice IDENTIFIED BY 'secret';", None, 0),
+        "RUN \"CREATE USER alice IDENTIFIED BY ?\" with 0 parameters -> SUCCESS"
+    );
+    assert_eq!(
+        run_message("set password for alice to \"secret\"", None, 0),
+        "RUN \"set password for alice to ?\" with 0 parameters -> SUCCESS"
+    );
+    assert_eq!(
+        run_message("MATCH (n {password: 'x'}) RETURN n", None, 0),
+        "RUN \"MATCH (n {password: ?}) RETURN n\" with 0 parameters -> SUCCESS"
+    );
+    assert_eq!(fetch_message(1), "RECORD");
+    assert_eq!(fetch_message(0), "SUCCESS");
+    assert_eq!(fetch_message(bindings::MG_ERROR_SEND_FAILED), "FAILURE");
+
+    assert!(wire_log::wire_log_enabled(&ConnectParams {
+        wire_log: true,
+        ..Default::default()
+    }));
+}
+
+/// Stream replaying `input` and recording everything written to it.
+struct MockStream {
+    input: std::io::Cursor<Vec<u8>>,
+    output: Vec<u8>,
+}
+
+impl MockStream {
+    fn new(input: &[u8]) -> MockStream {
+        MockStream {
+            input: std::io::Cursor::new(input.to_vec()),
+            output: Vec::new(),
+        }
+    }
+}
+
+impl std::io::Read for MockStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.input.read(buf)
+    }
+}
+
+impl std::io::Write for MockStream {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.output.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn socks5_handshake() {
+    let proxy = ProxyConfig::socks5("proxy", 1080);
+    let mut stream = MockStream::new(&[5, 0, 5, 0, 0, 1, 10, 0, 0, 1, 0x1f, 0x90]);
+    proxy::socks5_connect(&mut stream, &proxy, "memgraph", 7687).unwrap();
+    let mut expected = vec![5, 1, 0, 5, 1, 0, 3, 8];
+    expected.extend_from_slice(b"memgraph");
+    expected.extend_from_slice(&[0x1e, 0x07]);
+    assert_eq!(stream.output, expected);
+
+    let proxy = proxy.with_credentials("user", "pass");
+    let mut stream = MockStream::new(&[5, 2, 1, 0, 5, 0, 0, 1, 0, 0, 0, 0, 0, 0]);
+    proxy::socks5_connect(&mut stream, &proxy, "10.0.0.7", 7687).unwrap();
+    let mut expected = vec![5, 2, 0, 2, 1, 4];
+    expected.extend_from_slice(b"user");
+    expected.push(4);
+    expected.extend_from_slice(b"pass");
+    expected.extend_from_slice(&[5, 1, 0, 1, 10, 0, 0, 7, 0x1e, 0x07]);
+    assert_eq!(stream.output, expected);
+
+    let mut stream = MockStream::new(&[5, 2, 1, 1]);
+    assert!(proxy::socks5_connect(&mut stream, &proxy, "memgraph", 7687).is_err());
+
+    let mut stream = MockStream::new(&[5, 0, 5, 5, 0, 1, 0, 0, 0, 0, 0, 0]);
+    let err = proxy::socks5_connect(&mut stream, &proxy, "memgraph", 7687).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "proxy refused the connection with SOCKS5 reply 5"
+    );
+}
+
+#[test]
+fn http_connect_handshake() {
+    let proxy = ProxyConfig::http("proxy", 3128).with_credentials("user", "pass");
+    let mut stream = MockStream::new(b"HTTP/1.1 200 Connection established\r\n\r\nBOLT");
+    proxy::http_connect(&mut stream, &proxy, "::1", 7687).unwrap();
+    assert_eq!(
+        String::from_utf8(stream.output).unwrap(),
+        "CONNECT [::1]:7687 HTTP/1.1\r\nHost: [::1]:7687\r\n\
+         Proxy-Authorization: Basic dXNlcjpwYXNz\r\n\r\n"
+    );
+    let mut rest = String::new();
+    std::io::Read::read_to_string(&mut stream.input, &mut rest).unwrap();
+    assert_eq!(rest, "BOLT");
+
+    let mut stream = MockStream::new(b"HTTP/1.1 407 Proxy Authentication Required\r\n\r\n");
+    let err = proxy::http_connect(&mut stream, &proxy, "memgraph", 7687).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "proxy refused the connection: HTTP/1.1 407 Proxy Authentication Required"
+    );
+}
+
+#[test]
+fn proxy_base64_and_debug() {
+    assert_eq!(proxy::base64_encode(b""), "");
+    assert_eq!(proxy::base64_encode(b"f"), "Zg==");
+    assert_eq!(proxy::base64_encode(b"fo"), "Zm8=");
+    assert_eq!(proxy::base64_encode(b"foo"), "Zm9v");
+    assert_eq!(proxy::base64_encode(b"foobar"), "Zm9vYmFy");
+
+    let proxy = ProxyConfig::socks5("proxy", 1080).with_credentials("user", "secret");
+    assert!(!format!("{:?}", proxy).contains("secret"));
+}
+
+#[test]
+#[cfg(unix)]
+fn tunnel_through_proxy() {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::{TcpListener, TcpStream};
+
+    assert!(!Tunnel::is_needed(&ConnectParams {
+        tcp_nodelay: true,
+        ..Default::default()
+    }));
+    let proxy = TcpListener::bind("127.0.0.1:0").unwrap();
+    let params = ConnectParams {
+        proxy: Some(ProxyConfig::http(
+            "127.0.0.1",
+            proxy.local_addr().unwrap().port(),
+        )),
+        tcp_nodelay: true,
+        ..Default::default()
+    };
+    assert!(Tunnel::is_needed(&params));
+    let server = std::thread::spawn(move || {
+        let (stream, _) = proxy.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut request = String::new();
+        while !request.ends_with("\r\n\r\n") {
+            assert_ne!(reader.read_line(&mut request).unwrap(), 0);
+        }
+        assert!(request.starts_with("CONNECT memgraph:7687 "));
+        let mut stream = stream;
+        stream
+            .write_all(b"HTTP/1.1 200 Connection established\r\n\r\n")
+            .unwrap();
+        let mut buf = [0u8; 4];
+        reader.read_exact(&mut buf).unwrap();
+        stream.write_all(&buf).unwrap();
+    });
+
+    let tunnel = Tunnel::open(&params, "memgraph").unwrap();
+    let mut stream = TcpStream::connect(("127.0.0.1", tunnel.local_port())).unwrap();
+    stream.write_all(b"BOLT").unwrap();
+    let mut buf = [0u8; 4];
+    stream.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"BOLT");
+    server.join().unwrap();
+
+    let info = tunnel.trust_info(&TrustInfo {
+        hostname: String::new(),
+        ip: String::from("127.0.0.1"),
+        port: 0,
+        key_type: String::from("rsaEncryption"),
+        fingerprint: String::from("ab"),
+    });
+    assert_eq!(info.hostname, "memgraph");
+    assert_eq!(info.ip, "");
+}
+
+#[test]
+#[cfg(unix)]
+fn socket_finder() {
+    use super::socket::SocketFinder;
+    use std::net::{TcpListener, TcpStream};
+
+    let server = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = server.local_addr().unwrap().port();
+    let params = ConnectParams {
+        tcp_nodelay: true,
+        ..Default::default()
+    };
+    let finder = SocketFinder::new(port);
+    let stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+    assert!(!stream.nodelay().unwrap());
+    finder.set_options(&params).unwrap();
+    assert!(stream.nodelay().unwrap());
+
+    let finder = SocketFinder::new(port);
+    assert_eq!(
+        finder.set_options(&params).unwrap_err().to_string(),
+        "Unable to find the socket of the connection to set socket options"
+    );
+}
+
+#[test]
+#[cfg(not(feature = "socket2"))]
+fn tcp_keepalive_without_socket2() {
+    let params = ConnectParams {
+        tcp_keepalive: Some(Duration::from_secs(30)),
+        ..Default::default()
+    };
+    assert_eq!(
+        Connection::connect(&params).err().unwrap().to_string(),
+        "tcp_keepalive requires the socket2 feature"
+    );
+}
+
+#[test]
+fn host_strategy_rotation() {
+    assert_eq!(hosts::rotate(vec![1, 2, 3], 4), vec![2, 3, 1]);
+    assert_eq!(hosts::rotate(Vec::<i32>::new(), 1), Vec::<i32>::new());
+
+    assert_eq!(HostStrategy::FirstReachable.start(3), 0);
+    assert_eq!(HostStrategy::FirstReachable.start(3), 0);
+    let strategy = HostStrategy::round_robin();
+    let cloned = strategy.clone();
+    assert_eq!(strategy.start(3), 0);
+    assert_eq!(cloned.start(3), 1);
+    assert_eq!(strategy.start(3), 2);
+    assert_eq!(cloned.start(3), 0);
+    assert_eq!(strategy.start(0), 0);
+}
+
+#[test]
+#[serial]
+fn connect_hosts_fails_over() {
+    let connect_prms = ConnectParams {
+        hosts: vec![
+            (String::from("127.0.0.1"), 1),
+            (String::from("127.0.0.1"), 7687),
+        ],
+        ..Default::default()
+    };
+    let connection = get_connection(&connect_prms);
+    assert_eq!(connection.status, ConnectionStatus::Ready);
+    assert_eq!(connection.params.address, Some(String::from("127.0.0.1")));
+    assert_eq!(connection.params.port, 7687);
+    assert!(connection.params.hosts.is_empty());
+
+    let connect_prms = ConnectParams {
+        host: Some(String::from("localhost")),
+        ..connect_prms
+    };
+    match Connection::connect(&connect_prms) {
+        Ok(_) => panic!("Connecting with both hosts and host succeeded"),
+        Err(err) => assert_eq!(
+            format!("{}", err),
+            "hosts can't be specified together with host or address"
+        ),
+    }
+}
+
+#[test]
+fn replica_socket_address() {
+    use super::routing::split_socket_address;
+
+    assert_eq!(
+        split_socket_address("10.0.0.7:10000"),
+        Some((String::from("10.0.0.7"), 10000))
+    );
+    assert_eq!(
+        split_socket_address("[::1]:10000"),
+        Some((String::from("::1"), 10000))
+    );
+    assert_eq!(split_socket_address("replica"), None);
+    assert_eq!(split_socket_address(":10000"), None);
+}
+
+#[test]
+#[serial]
+fn routed_connection_without_replicas() {
+    initialize();
+    let mut connection = RoutedConnection::connect(&ConnectParams {
+        address: Some(String::from("127.0.0.1")),
+        ..Default::default()
+    })
+    .unwrap();
+    assert!(connection.replicas().is_empty());
+
+    connection
+        .execute_write("CREATE (:Routed {id: 1})", None)
+        .unwrap();
+    let records = connection
+        .execute_read("MATCH (n:Routed) RETURN n.id", None)
+        .unwrap();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].values[0], Value::Int(1));
+
+    connection.main().execute("RETURN 1", None).unwrap();
+    connection.main().fetchall().unwrap();
+    assert_eq!(connection.main().status(), ConnectionStatus::InTransaction);
+    match connection.execute_write("CREATE (:Routed)", None) {
+        Ok(_) => panic!("Routed write inside of a transaction succeeded"),
+        Err(err) => assert_eq!(
+            format!("{}", err),
+            "Can't call execute_write while in transaction"
+        ),
+    }
+}
+
+#[test]
+fn connect_params_from_vars() {
+    use std::env::VarError;
+
+    let from_vars = |vars: &[(&str, &str)]| {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        env::params_from_vars(|name| vars.get(name).cloned().ok_or(VarError::NotPresent))
+    };
+
+    let params = from_vars(&[]).unwrap();
+    assert_eq!(params.host, None);
+    assert_eq!(params.port, 7687);
+    assert_eq!(params.sslmode, SSLMode::Disable);
+    assert!(params.lazy);
+
+    let params = from_vars(&[
+        ("MEMGRAPH_HOST", "memgraph.internal"),
+        ("MEMGRAPH_PORT", "7688"),
+        ("MEMGRAPH_USERNAME", "alice"),
+        ("MEMGRAPH_PASSWORD", "secret"),
+        ("MEMGRAPH_SSLMODE", "Require"),
+        ("MEMGRAPH_DATABASE", ""),
+        ("MEMGRAPH_LAZY", "false"),
+        ("MEMGRAPH_AUTOCOMMIT", "1"),
+        ("MEMGRAPH_TCP_KEEPALIVE", "60"),
+    ])
+    .unwrap();
+    assert_eq!(params.host, Some(String::from("memgraph.internal")));
+    assert_eq!(params.port, 7688);
+    assert_eq!(params.username, Some(String::from("alice")));
+    assert_eq!(params.password, Some(String::from("secret")));
+    assert_eq!(params.sslmode, SSLMode::Require);
+    assert_eq!(params.database, None);
+    assert!(!params.lazy);
+    assert!(params.autocommit);
+    assert_eq!(params.tcp_keepalive, Some(Duration::from_secs(60)));
+
+    let params = from_vars(&[
+        ("MEMGRAPH_HOSTS", "main:7687, [::1]:7688"),
+        ("MEMGRAPH_HOST_STRATEGY", "round-robin"),
+    ])
+    .unwrap();
+    assert_eq!(
+        params.hosts,
+        vec![(String::from("main"), 7687), (String::from("::1"), 7688)]
+    );
+    assert!(matches!(params.host_strategy, HostStrategy::RoundRobin(_)));
+
+    let error = |vars: &[(&str, &str)]| match from_vars(vars) {
+        Ok(_) => panic!("Invalid environment variables were accepted"),
+        Err(err) => format!("{}", err),
+    };
+    assert_eq!(
+        error(&[("MEMGRAPH_PORT", "70000")]),
+        "Invalid value `70000` of environment variable MEMGRAPH_PORT, expected a port number"
+    );
+    assert_eq!(
+        error(&[("MEMGRAPH_SSLMODE", "prefer")]),
+        "Invalid value `prefer` of environment variable MEMGRAPH_SSLMODE, expected disable or require"
+    );
+    assert_eq!(
+        error(&[("MEMGRAPH_HOSTS", "main:7687,replica")]),
+        "Invalid value `main:7687,replica` of environment variable MEMGRAPH_HOSTS, expected \
+         comma-separated host:port pairs"
+    );
+    assert_eq!(
+        error(&[("MEMGRAPH_AUTOCOMMIT", "yes")]),
+        "Invalid value `yes` of environment variable MEMGRAPH_AUTOCOMMIT, expected true, false, \
+         1 or 0"
+    );
+}
+
+#[test]
+#[cfg(feature = "config")]
+fn connect_params_from_config() {
+    let params = config::params_from_toml_str(
+        r#"
+        hosts = ["main:7687", "replica:7687"]
+        host_strategy = "round-robin"
+        username = "analytics"
+        sslmode = "require"
+        trusted_fingerprints = ["ab:cd"]
+        lazy = false
+        tcp_keepalive = 60
+
+        [proxy]
+        kind = "socks5"
+        host = "bastion"
+        port = 1080
+        "#,
+    )
+    .unwrap();
+    assert_eq!(
+        params.hosts,
+        vec![
+            (String::from("main"), 7687),
+            (String::from("replica"), 7687)
+        ]
+    );
+    assert!(matches!(params.host_strategy, HostStrategy::RoundRobin(_)));
+    assert_eq!(params.username, Some(String::from("analytics")));
+    assert_eq!(params.sslmode, SSLMode::Require);
+    assert_eq!(
+        params.trusted_fingerprints,
+        Some(vec![String::from("ab:cd")])
+    );
+    assert!(!params.lazy);
+    assert_eq!(params.tcp_keepalive, Some(Duration::from_secs(60)));
+    assert_eq!(params.proxy, Some(ProxyConfig::socks5("bastion", 1080)));
+
+    let params = config::params_from_json_str(r#"{"address": "10.0.0.7", "port": 7688}"#).unwrap();
+    assert_eq!(params.address, Some(String::from("10.0.0.7")));
+    assert_eq!(params.port, 7688);
+    assert_eq!(params.client_name, ConnectParams::default().client_name);
+
+    let error = |content: &str| match config::params_from_toml_str(content) {
+        Ok(_) => panic!("Invalid config was accepted"),
+        Err(err) => format!("{}", err),
+    };
+    assert_eq!(error("prot = 7687"), "unknown key `prot`");
+    assert_eq!(
+        error("port = 70000"),
+        "invalid value of `port`, expected a port number"
+    );
+    assert_eq!(
+        error("[proxy]\nkind = \"socks4\""),
+        "invalid value of `proxy.kind`, expected socks5 or http"
+    );
+    assert_eq!(
+        error("[proxy]\nkind = \"http\"\nhost = \"proxy\""),
+        "missing key `proxy.port`"
+    );
+
+    let path = std::env::temp_dir().join("rsmgclient-missing-config.toml");
+    match ConnectParams::from_toml(&path) {
+        Ok(_) => panic!("Loading a missing config file succeeded"),
+        Err(err) => assert!(format!("{}", err).starts_with("Unable to read config file")),
+    }
+}
+
+#[test]
+#[serial]
+fn client_sessions() {
+    let client = Client::new();
+    let params = ConnectParams {
+        address: Some(String::from("127.0.0.1")),
+        ..Default::default()
+    };
+    let first = client.connect(&params).unwrap();
+    let second = first.try_clone().unwrap();
+    assert_eq!(client.session_count(), 2);
+    drop(first);
+    assert_eq!(client.session_count(), 1);
+
+    let third = Client::new();
+    assert!(third.shutdown().is_ok());
+    match client.shutdown() {
+        Ok(()) => panic!("Shutting down client with an open session succeeded"),
+        Err(err) => assert_eq!(
+            format!("{}", err),
+            "Can't shut down client while 1 sessions are open"
+        ),
+    }
+    drop(second);
+}
@@ -0,0 +1,132 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{TrustCallback, TrustDecision, TrustInfo};
+use std::ffi::OsString;
+use std::fs;
+use std::io::ErrorKind;
+use std::net::Ipv6Addr;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Serializes updates of known hosts files by connections of this process.
+static KNOWN_HOSTS_LOCK: Mutex<()> = Mutex::new(());
+
+/// Ready-made policies for trusting server certificates, usable as
+/// `ConnectParams::trust_callback`.
+///
+/// # Examples
+///
+/// ```
+/// use rsmgclient::{ConnectParams, SSLMode, SSLTrust};
+///
+/// let connect_params = ConnectParams {
+///     host: Some(String::from("localhost")),
+///     sslmode: SSLMode::Require,
+///     trust_callback: Some(
+///         SSLTrust::Tofu {
+///             store_path: "memgraph_known_hosts".into(),
+///         }
+///         .into(),
+///     ),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SSLTrust {
+    /// Trust on first use. The fingerprint of the first certificate seen for a server is stored
+    /// in the file at `store_path`, later connections are accepted only if the server presents
+    /// a certificate with the same fingerprint, like SSH `known_hosts`.
+    ///
+    /// Servers are identified by their host name or, if connected using an address, by their IP
+    /// address, together with the port, e.g. `localhost:7687` or `[::1]:7687`. Every line of the
+    /// file contains a server and its fingerprint separated by a space. Lines without a port,
+    /// written by older versions, apply to all ports of the server. To trust a changed
+    /// certificate, remove the server from the file. Connecting fails if the file can't be read
+    /// or written.
+    ///
+    /// The file is replaced atomically when a server is added, so it is never seen partially
+    /// written. Servers added concurrently by other processes sharing the file may be lost, in
+    /// which case they are trusted again on their next use.
+    Tofu { store_path: PathBuf },
+}
+
+impl From<SSLTrust> for TrustCallback {
+    fn from(trust: SSLTrust) -> TrustCallback {
+        match trust {
+            SSLTrust::Tofu { store_path } => {
+                TrustCallback::new(move |info| match verify_known_host(&store_path, info) {
+                    Ok(x) => x,
+                    Err(_) => TrustDecision::Reject,
+                })
+            }
+        }
+    }
+}
+
+fn verify_known_host(path: &Path, info: &TrustInfo) -> std::io::Result<TrustDecision> {
+    let host = match info.hostname.is_empty() {
+        true => &info.ip,
+        false => &info.hostname,
+    };
+    let server = match host.parse::<Ipv6Addr>() {
+        Ok(_) => format!("[{}]:{}", host, info.port),
+        Err(_) => format!("{}:{}", host, info.port),
+    };
+
+    let _guard = match KNOWN_HOSTS_LOCK.lock() {
+        Ok(x) => x,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    let mut content = match fs::read_to_string(path) {
+        Ok(x) => x,
+        Err(err) if err.kind() == ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err),
+    };
+    if let Some(fingerprint) =
+        find_known_host(&content, &server).or_else(|| find_known_host(&content, host))
+    {
+        return Ok(match fingerprint.eq_ignore_ascii_case(&info.fingerprint) {
+            true => TrustDecision::Accept,
+            false => TrustDecision::Reject,
+        });
+    }
+
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    content.push_str(&format!("{} {}\n", server, info.fingerprint));
+    let mut temp_path = OsString::from(path.as_os_str());
+    temp_path.push(format!(".{}.tmp", std::process::id()));
+    let temp_path = PathBuf::from(temp_path);
+    if let Err(err) = fs::write(&temp_path, content).and_then(|_| fs::rename(&temp_path, path)) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(err);
+    }
+    Ok(TrustDecision::Accept)
+}
+
+/// Returns fingerprint stored for `server` in the content of a known hosts file.
+pub(crate) fn find_known_host<'a>(content: &'a str, server: &str) -> Option<&'a str> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            Some((parts.next()?, parts.next()?))
+        })
+        .find(|(host, _)| *host == server)
+        .map(|(_, fingerprint)| fingerprint)
+}
@@ -0,0 +1,110 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::Connection;
+use crate::cypher::escape_string_literal;
+use crate::error::MgError;
+use crate::value::{Record, Value};
+use std::collections::HashMap;
+
+/// Transaction running on the server as reported by `SHOW TRANSACTIONS`, see
+/// `Connection::show_transactions`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionInfo {
+    /// User running the transaction, empty if authentication is disabled.
+    pub username: String,
+    /// Id identifying the transaction in `Connection::terminate_transactions`.
+    pub transaction_id: String,
+    /// Queries executed in the transaction so far.
+    pub queries: Vec<String>,
+    /// Metadata attached to the transaction, see `TxOptions::metadata`.
+    pub metadata: HashMap<String, Value>,
+}
+
+impl TransactionInfo {
+    pub(crate) fn from_record(record: &Record) -> TransactionInfo {
+        let username = match record.get("username") {
+            Some(Value::String(x)) => x.clone(),
+            _ => String::new(),
+        };
+        let transaction_id = match record.get("transaction_id") {
+            Some(Value::String(x)) => x.clone(),
+            Some(Value::Int(x)) => x.to_string(),
+            _ => String::new(),
+        };
+        let queries = match record.get("query") {
+            Some(Value::List(x)) => x
+                .iter()
+                .filter_map(|x| match x {
+                    Value::String(x) => Some(x.clone()),
+                    _ => None,
+                })
+                .collect(),
+            Some(Value::String(x)) => vec![x.clone()],
+            _ => Vec::new(),
+        };
+        let metadata = match record.get("metadata") {
+            Some(Value::Map(x)) => x.clone(),
+            _ => HashMap::new(),
+        };
+        TransactionInfo {
+            username,
+            transaction_id,
+            queries,
+            metadata,
+        }
+    }
+}
+
+pub(crate) fn terminate_transactions_query(ids: &[&str]) -> Result<String, MgError> {
+    let ids = ids
+        .iter()
+        .map(|x| escape_string_literal(x))
+        .collect::<Result<Vec<String>, MgError>>()?;
+    Ok(format!("TERMINATE TRANSACTIONS {}", ids.join(", ")))
+}
+
+impl Connection {
+    /// Returns transactions running on the server by running `SHOW TRANSACTIONS`.
+    ///
+    /// Users without the privilege to manage transactions only see their own transactions.
+    /// Connection needs to be in status `Ready`.
+    pub fn show_transactions(&mut self) -> Result<Vec<TransactionInfo>, MgError> {
+        let records = self.read_all_autocommit("show_transactions", "SHOW TRANSACTIONS")?;
+        Ok(records.iter().map(TransactionInfo::from_record).collect())
+    }
+
+    /// Terminates transactions with the given ids by running `TERMINATE TRANSACTIONS`.
+    ///
+    /// Returns ids of the terminated transactions, transactions which were not found or which
+    /// the user is not allowed to terminate are left out. Connection needs to be in status
+    /// `Ready`.
+    pub fn terminate_transactions(&mut self, ids: &[&str]) -> Result<Vec<String>, MgError> {
+        if ids.is_empty() {
+            self.check_ready("terminate_transactions")?;
+            return Ok(Vec::new());
+        }
+        let query = terminate_transactions_query(ids)?;
+        let records = self.read_all_autocommit("terminate_transactions", &query)?;
+        Ok(records
+            .iter()
+            .filter(|x| x.get("killed") == Some(&Value::Bool(true)))
+            .filter_map(|x| match x.get("transaction_id") {
+                Some(Value::String(x)) => Some(x.clone()),
+                Some(Value::Int(x)) => Some(x.to_string()),
+                _ => None,
+            })
+            .collect())
+    }
+}
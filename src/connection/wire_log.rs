@@ -0,0 +1,71 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{ConnectParams, Connection};
+use crate::statement_stats::normalize_query;
+use std::borrow::Cow;
+use std::env;
+use std::os::raw::c_int;
+use std::time::Instant;
+
+/// Environment variable enabling the wire log of all connections when set to a value other than
+/// `0`, see `ConnectParams::wire_log`.
+pub const WIRE_LOG_ENV: &str = "RSMGCLIENT_WIRE_LOG";
+
+pub(super) fn wire_log_enabled(params: &ConnectParams) -> bool {
+    params.wire_log || matches!(env::var(WIRE_LOG_ENV), Ok(x) if !x.is_empty() && x != "0")
+}
+
+/// Writes a line of the wire log using the `log` crate with target `rsmgclient::wire` and level
+/// `Debug` if the `log` feature is enabled, to the standard error otherwise.
+pub(super) fn write_wire_log(message: &str, start: Instant) {
+    #[cfg(feature = "log")]
+    log::debug!(target: "rsmgclient::wire", "{} in {:?}", message, start.elapsed());
+    #[cfg(not(feature = "log"))]
+    eprintln!("rsmgclient wire: {} in {:?}", message, start.elapsed());
+}
+
+/// Returns query text safe to log. Queries setting passwords, e.g. `CREATE USER ... IDENTIFIED
+/// BY` or `SET PASSWORD`, are logged normalized, with their literals replaced by `?`.
+pub(super) fn redact_query(query: &str) -> Cow<'_, str> {
+    let normalized = normalize_query(query);
+    let sets_password = normalized
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .any(|x| x.eq_ignore_ascii_case("IDENTIFIED") || x.eq_ignore_ascii_case("PASSWORD"));
+    match sets_password {
+        true => Cow::Owned(normalized),
+        false => Cow::Borrowed(query),
+    }
+}
+
+/// Returns name of the Bolt response received by a call into mgclient returning `status`.
+pub(super) fn response_name(status: c_int) -> &'static str {
+    match status {
+        0 => "SUCCESS",
+        _ => "FAILURE",
+    }
+}
+
+impl Connection {
+    /// Logs a message exchanged with the server if the wire log is enabled. `message` is only
+    /// called if the message is logged.
+    pub(super) fn log_wire<F>(&self, message: F, start: Instant)
+    where
+        F: FnOnce() -> String,
+    {
+        if self.wire_log {
+            write_wire_log(&message(), start);
+        }
+    }
+}
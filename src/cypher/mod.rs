@@ -0,0 +1,241 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Escaping of identifiers and string literals for Cypher queries.
+//!
+//! Values should be passed as query parameters whenever possible. These helpers are meant for
+//! parts of queries which can't be parameterized, e.g. labels, relationship types and property
+//! keys, and for generating scripts.
+
+use crate::error::MgError;
+
+fn check_null(text: &str, kind: &str) -> Result<(), MgError> {
+    match text.contains('\0') {
+        true => Err(MgError::new(format!(
+            "{} {:?} can't contain null characters",
+            kind, text
+        ))),
+        false => Ok(()),
+    }
+}
+
+/// Escapes an identifier, e.g. a label or a property key, by enclosing it in backticks and
+/// doubling backticks it contains.
+///
+/// Returns error if the identifier is empty or contains null characters.
+///
+/// # Examples
+///
+/// ```
+/// use rsmgclient::cypher::escape_identifier;
+///
+/// assert_eq!(escape_identifier("Person").unwrap(), "`Person`");
+/// assert_eq!(escape_identifier("a`b").unwrap(), "`a``b`");
+/// ```
+pub fn escape_identifier(identifier: &str) -> Result<String, MgError> {
+    if identifier.is_empty() {
+        return Err(MgError::new(String::from("Identifier can't be empty")));
+    }
+    check_null(identifier, "Identifier")?;
+    Ok(format!("`{}`", identifier.replace('`', "``")))
+}
+
+/// Escapes a string literal by enclosing it in single quotes and escaping quotes, backslashes
+/// and control characters it contains.
+///
+/// Returns error if the string contains null characters.
+///
+/// # Examples
+///
+/// ```
+/// use rsmgclient::cypher::escape_string_literal;
+///
+/// assert_eq!(escape_string_literal("it's").unwrap(), r"'it\'s'");
+/// ```
+pub fn escape_string_literal(text: &str) -> Result<String, MgError> {
+    check_null(text, "String literal")?;
+    let mut escaped = String::with_capacity(text.len() + 2);
+    escaped.push('\'');
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\'' => escaped.push_str("\\'"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            '\u{8}' => escaped.push_str("\\b"),
+            '\u{c}' => escaped.push_str("\\f"),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('\'');
+    Ok(escaped)
+}
+
+/// Statement of a script, see `split_statements`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Statement {
+    /// Text of the statement without the terminating semicolon and surrounding whitespace.
+    pub text: String,
+    /// Line of the script on which the statement starts, counted from 1.
+    pub line: usize,
+}
+
+/// Splits a script into statements separated by semicolons.
+///
+/// Semicolons in string literals, escaped identifiers and comments don't separate statements.
+/// Statements containing only whitespace and comments are left out.
+///
+/// # Examples
+///
+/// ```
+/// use rsmgclient::cypher::split_statements;
+///
+/// let statements = split_statements("CREATE (:A {x: ';'});\n// Done;\nMATCH (n) RETURN n;");
+/// assert_eq!(statements.len(), 2);
+/// assert_eq!(statements[0].text, "CREATE (:A {x: ';'})");
+/// assert_eq!(statements[1].line, 3);
+/// ```
+pub fn split_statements(script: &str) -> Vec<Statement> {
+    let chars: Vec<(usize, char)> = script.char_indices().collect();
+    let at = |i: usize| chars.get(i).map(|x| x.1);
+    let mut statements = Vec::new();
+    let mut start: Option<(usize, usize)> = None;
+    let mut line = 1;
+    let mut i = 0;
+
+    while let Some(c) = at(i) {
+        let offset = chars[i].0;
+        if c == '/' && at(i + 1) == Some('/') {
+            while !matches!(at(i), Some('\n') | None) {
+                i += 1;
+            }
+        } else if c == '/' && at(i + 1) == Some('*') {
+            i += 2;
+            while at(i).is_some() && !(at(i) == Some('*') && at(i + 1) == Some('/')) {
+                if at(i) == Some('\n') {
+                    line += 1;
+                }
+                i += 1;
+            }
+            i += 2;
+        } else if c == ';' {
+            if let Some((start, line)) = start.take() {
+                statements.push(Statement {
+                    text: script[start..offset].trim_end().to_string(),
+                    line,
+                });
+            }
+            i += 1;
+        } else if c.is_whitespace() {
+            if c == '\n' {
+                line += 1;
+            }
+            i += 1;
+        } else {
+            if start.is_none() {
+                start = Some((offset, line));
+            }
+            i += 1;
+            if c == '\'' || c == '"' || c == '`' {
+                while let Some(x) = at(i) {
+                    if x == c {
+                        break;
+                    }
+                    if x == '\\' && c != '`' {
+                        i += 1;
+                    }
+                    if at(i) == Some('\n') {
+                        line += 1;
+                    }
+                    i += 1;
+                }
+                i += 1;
+            }
+        }
+    }
+
+    if let Some((start, line)) = start {
+        statements.push(Statement {
+            text: script[start..].trim_end().to_string(),
+            line,
+        });
+    }
+    statements
+}
+
+/// Returns names of the parameters used by a query, e.g. `name` for `$name`, in the order of
+/// their first use.
+///
+/// Parameters in string literals, escaped identifiers and comments are not taken into account.
+///
+/// # Examples
+///
+/// ```
+/// use rsmgclient::cypher::query_parameters;
+///
+/// assert_eq!(
+///     query_parameters("MATCH (n {name: $name}) WHERE n.age > $age RETURN n, '$x', $name"),
+///     vec!["name", "age"]
+/// );
+/// ```
+pub fn query_parameters(query: &str) -> Vec<String> {
+    let chars: Vec<char> = query.chars().collect();
+    let at = |i: usize| chars.get(i).copied();
+    let mut parameters: Vec<String> = Vec::new();
+    let mut i = 0;
+
+    while let Some(c) = at(i) {
+        if c == '/' && at(i + 1) == Some('/') {
+            while !matches!(at(i), Some('\n') | None) {
+                i += 1;
+            }
+        } else if c == '/' && at(i + 1) == Some('*') {
+            i += 2;
+            while at(i).is_some() && !(at(i) == Some('*') && at(i + 1) == Some('/')) {
+                i += 1;
+            }
+            i += 2;
+        } else if c == '\'' || c == '"' || c == '`' {
+            i += 1;
+            while let Some(x) = at(i) {
+                if x == c {
+                    break;
+                }
+                if x == '\\' && c != '`' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            i += 1;
+        } else if c == '$' {
+            i += 1;
+            let start = i;
+            while matches!(at(i), Some(x) if x.is_alphanumeric() || x == '_') {
+                i += 1;
+            }
+            let name: String = chars[start..i].iter().collect();
+            if !name.is_empty() && !parameters.contains(&name) {
+                parameters.push(name);
+            }
+        } else {
+            i += 1;
+        }
+    }
+    parameters
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,90 @@
+use super::*;
+
+#[test]
+fn escape_identifier_backticks() {
+    assert_eq!(escape_identifier("name").unwrap(), "`name`");
+    assert_eq!(escape_identifier("first name").unwrap(), "`first name`");
+    assert_eq!(
+        escape_identifier("x`) DETACH DELETE (n").unwrap(),
+        "`x``) DETACH DELETE (n`"
+    );
+}
+
+#[test]
+fn escape_identifier_invalid() {
+    assert_eq!(
+        escape_identifier("").unwrap_err().to_string(),
+        "Identifier can't be empty"
+    );
+    assert_eq!(
+        escape_identifier("a\0b").unwrap_err().to_string(),
+        "Identifier \"a\\0b\" can't contain null characters"
+    );
+}
+
+#[test]
+fn escape_string_literal_quotes() {
+    assert_eq!(escape_string_literal("").unwrap(), "''");
+    assert_eq!(
+        escape_string_literal("x' OR 1=1 //").unwrap(),
+        r"'x\' OR 1=1 //'"
+    );
+    assert_eq!(
+        escape_string_literal("a\\b\"c\nd\te").unwrap(),
+        r#"'a\\b\"c\nd\te'"#
+    );
+    assert_eq!(
+        escape_string_literal("a\0").unwrap_err().to_string(),
+        "String literal \"a\\0\" can't contain null characters"
+    );
+}
+
+#[test]
+fn split_statements_on_semicolons() {
+    let script = "CREATE (:Person {name: 'a;b'});\n\
+                  \n\
+                  // Comment; with a semicolon\n\
+                  MATCH (n:`Odd;Label`) /* multi\n\
+                  line; comment */ RETURN n;\n\
+                  CREATE (:Quote {text: \"it\\\"s; \\\\\"});;\n\
+                  RETURN 1";
+    let statements = split_statements(script);
+    assert_eq!(
+        statements,
+        vec![
+            Statement {
+                text: String::from("CREATE (:Person {name: 'a;b'})"),
+                line: 1,
+            },
+            Statement {
+                text: String::from("MATCH (n:`Odd;Label`) /* multi\nline; comment */ RETURN n"),
+                line: 4,
+            },
+            Statement {
+                text: String::from("CREATE (:Quote {text: \"it\\\"s; \\\\\"})"),
+                line: 6,
+            },
+            Statement {
+                text: String::from("RETURN 1"),
+                line: 7,
+            },
+        ]
+    );
+
+    assert!(split_statements("").is_empty());
+    assert!(split_statements(" ;\n// only a comment;\n/* and; another */").is_empty());
+    assert_eq!(split_statements("RETURN 'unterminated;")[0].line, 1);
+}
+
+#[test]
+fn query_parameters_skip_literals_and_comments() {
+    assert_eq!(
+        query_parameters(
+            "MATCH (n:`$label` {id: $id}) // $comment\n\
+             WHERE n.name = 'it\\'s $x' /* $y */ AND n.id IN $ids\n\
+             SET n.x = $_value1 RETURN $id, $"
+        ),
+        vec!["id", "ids", "_value1"]
+    );
+    assert!(query_parameters("RETURN 1").is_empty());
+}
@@ -12,12 +12,57 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use super::bindings;
 use std::fmt;
+use std::os::raw::c_int;
+
+/// Classification of an error reported by the server.
+///
+/// Memgraph and Neo4j error codes have the form `<Vendor>.<Classification>.<Category>.<Title>`,
+/// e.g. `Memgraph.ClientError.MemgraphError.MemgraphError`. mgclient only reports the
+/// classification of the errors it receives, see `MgError::code`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ServerErrorCode {
+    /// The request was invalid, e.g. a syntax error or a constraint violation. Retrying the same
+    /// request fails again.
+    ClientError,
+    /// Temporary failure, e.g. a conflict with a concurrent transaction. The request may succeed
+    /// when retried.
+    TransientError,
+    /// Failure of the database itself.
+    DatabaseError,
+}
+
+impl ServerErrorCode {
+    /// Parses the classification out of a full error code, e.g.
+    /// `Memgraph.TransientError.MemgraphError.MemgraphError`.
+    ///
+    /// Returns `None` if the code is malformed or the classification is unknown.
+    pub fn parse(code: &str) -> Option<ServerErrorCode> {
+        match code.split('.').nth(1)? {
+            "ClientError" => Some(ServerErrorCode::ClientError),
+            "TransientError" => Some(ServerErrorCode::TransientError),
+            "DatabaseError" => Some(ServerErrorCode::DatabaseError),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn from_status(status: c_int) -> Option<ServerErrorCode> {
+        match status {
+            bindings::MG_ERROR_CLIENT_ERROR => Some(ServerErrorCode::ClientError),
+            bindings::MG_ERROR_TRANSIENT_ERROR => Some(ServerErrorCode::TransientError),
+            bindings::MG_ERROR_DATABASE_ERROR => Some(ServerErrorCode::DatabaseError),
+            _ => None,
+        }
+    }
+}
 
 /// Error returned by using connection.
 #[derive(Debug)]
 pub struct MgError {
     message: String,
+    code: Option<ServerErrorCode>,
+    cancelled: bool,
 }
 
 impl fmt::Display for MgError {
@@ -28,6 +73,54 @@ impl fmt::Display for MgError {
 
 impl MgError {
     pub fn new(message: String) -> MgError {
-        MgError { message }
+        MgError {
+            message,
+            code: None,
+            cancelled: false,
+        }
+    }
+
+    pub(crate) fn with_code(message: String, code: Option<ServerErrorCode>) -> MgError {
+        MgError {
+            message,
+            code,
+            cancelled: false,
+        }
+    }
+
+    pub(crate) fn cancelled(message: String, code: Option<ServerErrorCode>) -> MgError {
+        MgError {
+            message,
+            code,
+            cancelled: true,
+        }
+    }
+
+    /// Returns classification of the error if it was reported by the server, `None` for errors
+    /// detected by the client, e.g. network failures or misuse of the connection.
+    pub fn code(&self) -> Option<ServerErrorCode> {
+        self.code
+    }
+
+    /// Returns whether the error was caused by a conflict with a concurrent transaction.
+    ///
+    /// Transactions failing due to a conflict can be retried.
+    pub fn is_conflict(&self) -> bool {
+        let message = self.message.to_lowercase();
+        message.contains("conflicting transaction") || message.contains("serialization error")
+    }
+
+    /// Returns whether the query failed because it was cancelled using a `CancellationHandle`.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    /// Returns whether the failed request may succeed if retried, i.e. the server reported a
+    /// transient error or the error was caused by a conflict.
+    pub fn is_retryable(&self) -> bool {
+        self.code == Some(ServerErrorCode::TransientError) || self.is_conflict()
     }
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,75 @@
+// Copyright (c) 2016-2020 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+#[test]
+fn parse_server_error_code() {
+    assert_eq!(
+        ServerErrorCode::parse("Memgraph.ClientError.MemgraphError.MemgraphError"),
+        Some(ServerErrorCode::ClientError)
+    );
+    assert_eq!(
+        ServerErrorCode::parse("Neo.TransientError.Transaction.DeadlockDetected"),
+        Some(ServerErrorCode::TransientError)
+    );
+    assert_eq!(
+        ServerErrorCode::parse("Memgraph.DatabaseError.MemgraphError.MemgraphError"),
+        Some(ServerErrorCode::DatabaseError)
+    );
+    assert_eq!(ServerErrorCode::parse("Memgraph.Unknown.A.B"), None);
+    assert_eq!(ServerErrorCode::parse("ClientError"), None);
+}
+
+#[test]
+fn server_error_code_from_status() {
+    assert_eq!(
+        ServerErrorCode::from_status(bindings::MG_ERROR_TRANSIENT_ERROR),
+        Some(ServerErrorCode::TransientError)
+    );
+    assert_eq!(
+        ServerErrorCode::from_status(bindings::MG_ERROR_SEND_FAILED),
+        None
+    );
+}
+
+#[test]
+fn is_retryable() {
+    let transient = MgError::with_code(
+        String::from("Timeout"),
+        Some(ServerErrorCode::TransientError),
+    );
+    assert!(transient.is_retryable());
+    assert_eq!(transient.code(), Some(ServerErrorCode::TransientError));
+
+    let conflict = MgError::new(String::from(
+        "Cannot resolve conflicting transactions. You can retry this transaction.",
+    ));
+    assert!(conflict.is_retryable());
+
+    let client = MgError::with_code(String::from("Syntax"), Some(ServerErrorCode::ClientError));
+    assert!(!client.is_retryable());
+    assert_eq!(MgError::new(String::from("Bad")).code(), None);
+}
+
+#[test]
+fn is_cancelled() {
+    let cancelled = MgError::cancelled(
+        String::from("Query was cancelled: Transaction was asked to abort"),
+        Some(ServerErrorCode::ClientError),
+    );
+    assert!(cancelled.is_cancelled());
+    assert!(!cancelled.is_retryable());
+    assert!(!MgError::new(String::from("Timeout")).is_cancelled());
+}
@@ -0,0 +1,125 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::graph::GraphElements;
+use crate::value::{Properties, Record, Value};
+use std::fmt::Write;
+
+/// Options of `to_dot`.
+#[derive(Debug, Clone)]
+pub struct DotOptions {
+    /// Name of the generated digraph.
+    pub graph_name: String,
+    /// Property used as the caption of nodes, e.g. `name`. Nodes without the property, or all
+    /// nodes if not set, are captioned by their labels.
+    pub caption_property: Option<String>,
+    /// Whether properties of nodes and relationships are rendered below their caption.
+    pub show_properties: bool,
+}
+
+impl Default for DotOptions {
+    fn default() -> Self {
+        DotOptions {
+            graph_name: String::from("G"),
+            caption_property: None,
+            show_properties: false,
+        }
+    }
+}
+
+/// Renders nodes and relationships contained in the records as a Graphviz digraph.
+///
+/// Nodes, relationships and paths are collected the same way as by `graph::to_petgraph`.
+/// Relationships are rendered even if their start or end node isn't contained in the records,
+/// Graphviz then draws the missing node captioned by its id.
+///
+/// # Examples
+///
+/// ```
+/// use rsmgclient::export::{to_dot, DotOptions};
+///
+/// assert_eq!(to_dot(&[], &DotOptions::default()), "digraph \"G\" {\n}\n");
+/// ```
+pub fn to_dot(records: &[Record], options: &DotOptions) -> String {
+    let elements = GraphElements::from_records(records);
+    let mut dot = String::new();
+    writeln!(dot, "digraph {} {{", quote(&options.graph_name)).unwrap();
+    for node in &elements.nodes {
+        let caption = options
+            .caption_property
+            .as_ref()
+            .and_then(|x| node.properties.get(x.as_str()))
+            .map(caption_value)
+            .unwrap_or_else(|| format!(":{}", node.labels.join(":")));
+        writeln!(
+            dot,
+            "  {} [label={}];",
+            quote(&node.id.to_string()),
+            quote(&label(caption, &node.properties, options))
+        )
+        .unwrap();
+    }
+    for relationship in &elements.relationships {
+        writeln!(
+            dot,
+            "  {} -> {} [label={}];",
+            quote(&relationship.start_id.to_string()),
+            quote(&relationship.end_id.to_string()),
+            quote(&label(
+                relationship.type_.clone(),
+                &relationship.properties,
+                options
+            ))
+        )
+        .unwrap();
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+fn caption_value(value: &Value) -> String {
+    match value {
+        Value::String(x) => x.clone(),
+        x => x.to_string(),
+    }
+}
+
+fn label(caption: String, properties: &Properties, options: &DotOptions) -> String {
+    if !options.show_properties {
+        return caption;
+    }
+    let mut properties: Vec<_> = properties.iter().collect();
+    properties.sort_by(|x, y| x.0.cmp(y.0));
+    let mut label = caption;
+    for (key, value) in properties {
+        label.push_str(&format!("\n{}: {}", key, value));
+    }
+    label
+}
+
+/// Quotes text as a DOT string, escaping quotes, backslashes and newlines.
+fn quote(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
@@ -0,0 +1,49 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::value::{Record, Value};
+use serde_json::{Map, Value as JsonValue};
+
+/// Converts a single value into JSON, see the `export` module documentation for the format.
+pub fn value_to_json(value: &Value) -> JsonValue {
+    // Serialization into `serde_json::Value` only fails for maps with non-string keys.
+    serde_json::to_value(value).expect("Value is always serializable into JSON")
+}
+
+/// Converts records into a JSON object with the column names and one object per record, see
+/// the `export` module documentation for the format.
+///
+/// Values without a matching column name are skipped.
+pub fn records_to_json(columns: &[String], records: &[Record]) -> JsonValue {
+    let records = records
+        .iter()
+        .map(|record| {
+            JsonValue::Object(
+                columns
+                    .iter()
+                    .zip(record.values.iter())
+                    .map(|(column, value)| (column.clone(), value_to_json(value)))
+                    .collect::<Map<String, JsonValue>>(),
+            )
+        })
+        .collect();
+
+    let mut result = Map::new();
+    result.insert(
+        String::from("columns"),
+        JsonValue::Array(columns.iter().cloned().map(JsonValue::String).collect()),
+    );
+    result.insert(String::from("records"), JsonValue::Array(records));
+    JsonValue::Object(result)
+}
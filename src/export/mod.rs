@@ -0,0 +1,85 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Export of query results into formats understood by other tools.
+//!
+//! # JSON
+//!
+//! Enabled by the `json` feature. Results are exported as an object with the column names, in
+//! the order returned by the query, and one object per record keyed by the column names:
+//!
+//! ```json
+//! {
+//!   "columns": ["n", "since"],
+//!   "records": [
+//!     {
+//!       "n": {"id": 1, "labels": ["Person"], "properties": {"name": "Alice"}},
+//!       "since": "2020-01-01"
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! Values are converted the same way as by their `Serialize` implementation:
+//!
+//! - `Null`, booleans, numbers, strings, lists and maps map to their JSON counterparts. Floats
+//!   which can't be represented in JSON, i.e. NaN and infinities, become `null`.
+//! - Nodes become `{"id", "labels", "properties"}`.
+//! - Relationships become `{"id", "start_id", "end_id", "type", "properties"}` and unbound
+//!   relationships `{"id", "type", "properties"}`.
+//! - Paths become `{"nodes", "relationships", "sequence"}`.
+//! - Temporal values become ISO 8601 strings, e.g. `"2020-01-01"`, `"12:30:00+01:00"`,
+//!   `"2020-01-01T12:30:00"`, `"2020-01-01T12:30:00+01:00"` and `"P1DT2H"`.
+//!
+//! # Arrow
+//!
+//! Enabled by the `arrow` feature. `to_record_batch` converts records into an Arrow
+//! `RecordBatch` with one column per query column. Types of the columns are inferred from all
+//! records:
+//!
+//! - integers become `Int64`, or `Float64` if the column also contains floats, in which case
+//!   integers beyond 2^53 lose precision,
+//! - floats become `Float64`,
+//! - strings become `Utf8`,
+//! - booleans become `Boolean`,
+//! - local date times become `Timestamp(Microsecond, None)` and date times become
+//!   `Timestamp(Microsecond, "UTC")`,
+//! - lists become `List` of the type inferred from their elements,
+//! - columns which contain only nulls become `Null`.
+//!
+//! Other values, e.g. nodes or maps, can't be converted. Conversion fails if a column contains
+//! values of incompatible types, e.g. strings and integers.
+//!
+//! # DOT
+//!
+//! `to_dot` renders nodes and relationships contained in query results as a Graphviz digraph.
+//! Nodes are identified by their ids and captioned by their labels or by a chosen property,
+//! relationships are captioned by their type. Properties can optionally be rendered below the
+//! captions.
+
+mod dot;
+pub use dot::{to_dot, DotOptions};
+
+#[cfg(feature = "json")]
+mod json;
+#[cfg(feature = "json")]
+pub use json::{records_to_json, value_to_json};
+
+#[cfg(feature = "arrow")]
+mod record_batch;
+#[cfg(feature = "arrow")]
+pub use record_batch::to_record_batch;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,208 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::MgError;
+use crate::value::{Record, Value};
+use arrow::array::{
+    ArrayRef, BooleanArray, Float64Array, Int64Array, ListArray, NullArray, StringArray,
+    TimestampMicrosecondArray,
+};
+use arrow::buffer::{NullBuffer, OffsetBuffer};
+use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+use arrow::record_batch::{RecordBatch, RecordBatchOptions};
+use std::sync::Arc;
+
+const UTC: &str = "UTC";
+
+/// Converts records into an Arrow `RecordBatch` with one column per query column, see the
+/// `export` module documentation for the inferred types.
+///
+/// Returns error if a column contains values which can't be converted or values of incompatible
+/// types.
+pub fn to_record_batch(columns: &[String], records: &[Record]) -> Result<RecordBatch, MgError> {
+    let mut fields = Vec::with_capacity(columns.len());
+    let mut arrays = Vec::with_capacity(columns.len());
+    for (i, column) in columns.iter().enumerate() {
+        let values: Vec<&Value> = records
+            .iter()
+            .map(|record| record.values.get(i).unwrap_or(&Value::Null))
+            .collect();
+        let data_type = infer_type(values.iter().copied())
+            .map_err(|err| MgError::new(format!("Column {}: {}", column, err)))?;
+        let array = build_array(&data_type, &values)
+            .map_err(|err| MgError::new(format!("Column {}: {}", column, err)))?;
+        fields.push(Field::new(column.as_str(), data_type, true));
+        arrays.push(array);
+    }
+
+    let options = RecordBatchOptions::new().with_row_count(Some(records.len()));
+    RecordBatch::try_new_with_options(Arc::new(Schema::new(fields)), arrays, &options)
+        .map_err(|err| MgError::new(format!("Can't create record batch: {}", err)))
+}
+
+fn list_type(element_type: DataType) -> DataType {
+    DataType::List(Arc::new(Field::new("item", element_type, true)))
+}
+
+fn infer_type<'a>(values: impl Iterator<Item = &'a Value>) -> Result<DataType, MgError> {
+    let mut data_type = DataType::Null;
+    for value in values {
+        let value_type = match value {
+            Value::Null => DataType::Null,
+            Value::Bool(_) => DataType::Boolean,
+            Value::Int(_) => DataType::Int64,
+            Value::Float(_) => DataType::Float64,
+            Value::String(_) => DataType::Utf8,
+            Value::LocalDateTime(_) => DataType::Timestamp(TimeUnit::Microsecond, None),
+            Value::DateTime(_) => DataType::Timestamp(TimeUnit::Microsecond, Some(UTC.into())),
+            Value::List(x) => list_type(infer_type(x.iter())?),
+            x => {
+                return Err(MgError::new(format!(
+                    "Can't convert {} value to Arrow",
+                    x.type_name()
+                )))
+            }
+        };
+        data_type = merge_types(data_type, value_type)?;
+    }
+    Ok(data_type)
+}
+
+fn merge_types(x: DataType, y: DataType) -> Result<DataType, MgError> {
+    match (x, y) {
+        (DataType::Null, y) => Ok(y),
+        (x, DataType::Null) => Ok(x),
+        (DataType::Int64, DataType::Float64) | (DataType::Float64, DataType::Int64) => {
+            Ok(DataType::Float64)
+        }
+        (DataType::List(x), DataType::List(y)) => Ok(list_type(merge_types(
+            x.data_type().clone(),
+            y.data_type().clone(),
+        )?)),
+        (x, y) if x == y => Ok(x),
+        (x, y) => Err(MgError::new(format!(
+            "Can't infer Arrow type of both {} and {} values",
+            x, y
+        ))),
+    }
+}
+
+fn type_error(value: &Value, data_type: &DataType) -> MgError {
+    MgError::new(format!(
+        "Can't convert {} value to {}",
+        value.type_name(),
+        data_type
+    ))
+}
+
+fn build_array(data_type: &DataType, values: &[&Value]) -> Result<ArrayRef, MgError> {
+    Ok(match data_type {
+        DataType::Null => match values.iter().find(|x| !matches!(x, Value::Null)) {
+            Some(x) => return Err(type_error(x, data_type)),
+            None => Arc::new(NullArray::new(values.len())),
+        },
+        DataType::Boolean => Arc::new(
+            values
+                .iter()
+                .map(|x| match x {
+                    Value::Null => Ok(None),
+                    Value::Bool(x) => Ok(Some(*x)),
+                    x => Err(type_error(x, data_type)),
+                })
+                .collect::<Result<BooleanArray, MgError>>()?,
+        ),
+        DataType::Int64 => Arc::new(
+            values
+                .iter()
+                .map(|x| match x {
+                    Value::Null => Ok(None),
+                    Value::Int(x) => Ok(Some(*x)),
+                    x => Err(type_error(x, data_type)),
+                })
+                .collect::<Result<Int64Array, MgError>>()?,
+        ),
+        DataType::Float64 => Arc::new(
+            values
+                .iter()
+                .map(|x| match x {
+                    Value::Null => Ok(None),
+                    Value::Int(x) => Ok(Some(*x as f64)),
+                    Value::Float(x) => Ok(Some(*x)),
+                    x => Err(type_error(x, data_type)),
+                })
+                .collect::<Result<Float64Array, MgError>>()?,
+        ),
+        DataType::Utf8 => Arc::new(
+            values
+                .iter()
+                .map(|x| match x {
+                    Value::Null => Ok(None),
+                    Value::String(x) => Ok(Some(x.as_str())),
+                    x => Err(type_error(x, data_type)),
+                })
+                .collect::<Result<StringArray, MgError>>()?,
+        ),
+        DataType::Timestamp(TimeUnit::Microsecond, None) => Arc::new(
+            values
+                .iter()
+                .map(|x| match x {
+                    Value::Null => Ok(None),
+                    Value::LocalDateTime(x) => Ok(Some(x.and_utc().timestamp_micros())),
+                    x => Err(type_error(x, data_type)),
+                })
+                .collect::<Result<TimestampMicrosecondArray, MgError>>()?,
+        ),
+        DataType::Timestamp(TimeUnit::Microsecond, Some(_)) => Arc::new(
+            values
+                .iter()
+                .map(|x| match x {
+                    Value::Null => Ok(None),
+                    Value::DateTime(x) => Ok(Some(x.timestamp_micros())),
+                    x => Err(type_error(x, data_type)),
+                })
+                .collect::<Result<TimestampMicrosecondArray, MgError>>()?
+                .with_timezone(UTC),
+        ),
+        DataType::List(field) => {
+            let mut lengths = Vec::with_capacity(values.len());
+            let mut validity = Vec::with_capacity(values.len());
+            let mut elements = Vec::new();
+            for value in values {
+                match value {
+                    Value::Null => {
+                        lengths.push(0);
+                        validity.push(false);
+                    }
+                    Value::List(x) => {
+                        lengths.push(x.len());
+                        validity.push(true);
+                        elements.extend(x.iter());
+                    }
+                    x => return Err(type_error(x, data_type)),
+                }
+            }
+            let elements = build_array(field.data_type(), &elements)?;
+            Arc::new(
+                ListArray::try_new(
+                    field.clone(),
+                    OffsetBuffer::from_lengths(lengths),
+                    elements,
+                    Some(NullBuffer::from(validity)),
+                )
+                .map_err(|err| MgError::new(err.to_string()))?,
+            )
+        }
+        x => return Err(MgError::new(format!("Unsupported Arrow type {}", x))),
+    })
+}
@@ -0,0 +1,237 @@
+use super::*;
+use crate::value::{Node, Record, Relationship, Value};
+use chrono::NaiveDate;
+use std::sync::Arc;
+
+#[cfg(feature = "json")]
+#[test]
+fn records_to_json_format() {
+    let columns = vec![String::from("n"), String::from("since")];
+    let records = vec![Record::new(
+        Arc::new(columns.clone()),
+        vec![
+            Value::Node(Node {
+                id: 1,
+                label_count: 1,
+                labels: vec![String::from("Person")],
+                properties: hashmap! {
+                    Arc::from("name") => Value::String(String::from("Alice")),
+                },
+            }),
+            Value::Date(NaiveDate::from_ymd(2020, 1, 1)),
+        ],
+    )];
+
+    assert_eq!(
+        records_to_json(&columns, &records),
+        serde_json::json!({
+            "columns": ["n", "since"],
+            "records": [{
+                "n": {"id": 1, "labels": ["Person"], "properties": {"name": "Alice"}},
+                "since": "2020-01-01",
+            }],
+        })
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn records_to_json_empty() {
+    assert_eq!(
+        records_to_json(&[String::from("x")], &[]),
+        serde_json::json!({"columns": ["x"], "records": []})
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn value_to_json_null_and_nan() {
+    assert_eq!(value_to_json(&Value::Null), serde_json::Value::Null);
+    assert_eq!(
+        value_to_json(&Value::Float(f64::NAN)),
+        serde_json::Value::Null
+    );
+    assert_eq!(
+        value_to_json(&Value::List(vec![Value::Int(1), Value::Bool(true)])),
+        serde_json::json!([1, true])
+    );
+}
+
+fn record(values: Vec<Value>) -> Record {
+    Record::new(Arc::default(), values)
+}
+
+#[cfg(feature = "arrow")]
+#[test]
+fn to_record_batch_infers_types() {
+    use arrow::array::{Array, Float64Array, ListArray, StringArray, TimestampMicrosecondArray};
+    use arrow::datatypes::{DataType, TimeUnit};
+
+    let columns = vec![
+        String::from("name"),
+        String::from("score"),
+        String::from("tags"),
+        String::from("at"),
+        String::from("nothing"),
+    ];
+    let at = NaiveDate::from_ymd(2020, 1, 1).and_hms(0, 0, 1);
+    let records = vec![
+        record(vec![
+            Value::String(String::from("Alice")),
+            Value::Int(1),
+            Value::List(vec![Value::String(String::from("a"))]),
+            Value::LocalDateTime(at),
+            Value::Null,
+        ]),
+        record(vec![
+            Value::Null,
+            Value::Float(1.5),
+            Value::Null,
+            Value::Null,
+            Value::Null,
+        ]),
+    ];
+
+    let batch = to_record_batch(&columns, &records).unwrap();
+    assert_eq!(batch.num_rows(), 2);
+    let schema = batch.schema();
+    assert_eq!(schema.field(0).data_type(), &DataType::Utf8);
+    assert_eq!(schema.field(1).data_type(), &DataType::Float64);
+    assert!(
+        matches!(schema.field(2).data_type(), DataType::List(x) if x.data_type() == &DataType::Utf8)
+    );
+    assert_eq!(
+        schema.field(3).data_type(),
+        &DataType::Timestamp(TimeUnit::Microsecond, None)
+    );
+    assert_eq!(schema.field(4).data_type(), &DataType::Null);
+
+    let names = batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert_eq!(names.value(0), "Alice");
+    assert!(names.is_null(1));
+    let scores = batch
+        .column(1)
+        .as_any()
+        .downcast_ref::<Float64Array>()
+        .unwrap();
+    assert_eq!(scores.values(), &[1.0, 1.5]);
+    let tags = batch
+        .column(2)
+        .as_any()
+        .downcast_ref::<ListArray>()
+        .unwrap();
+    assert_eq!(tags.value_length(0), 1);
+    assert!(tags.is_null(1));
+    let at = batch
+        .column(3)
+        .as_any()
+        .downcast_ref::<TimestampMicrosecondArray>()
+        .unwrap();
+    assert_eq!(at.value(0), 1_577_836_801_000_000);
+}
+
+#[cfg(feature = "arrow")]
+#[test]
+fn to_record_batch_errors() {
+    let columns = vec![String::from("n")];
+    let records = vec![record(vec![Value::Node(Node {
+        id: 1,
+        label_count: 0,
+        labels: vec![],
+        properties: hashmap! {},
+    })])];
+    assert_eq!(
+        to_record_batch(&columns, &records).unwrap_err().to_string(),
+        "Column n: Can't convert Node value to Arrow"
+    );
+
+    let mut records: Vec<Record> = (0..100).map(|x| record(vec![Value::Int(x)])).collect();
+    records.push(record(vec![Value::String(String::from("x"))]));
+    assert_eq!(
+        to_record_batch(&columns, &records).unwrap_err().to_string(),
+        "Column n: Can't infer Arrow type of both Int64 and Utf8 values"
+    );
+}
+
+#[cfg(feature = "arrow")]
+#[test]
+fn to_record_batch_infers_types_from_all_records() {
+    use arrow::array::{Array, Float64Array};
+    use arrow::datatypes::DataType;
+
+    let columns = vec![String::from("x"), String::from("late")];
+    let mut records: Vec<Record> = (0..200)
+        .map(|x| record(vec![Value::Int(x), Value::Null]))
+        .collect();
+    records.push(record(vec![Value::Float(0.5), Value::Bool(true)]));
+
+    let batch = to_record_batch(&columns, &records).unwrap();
+    let schema = batch.schema();
+    assert_eq!(schema.field(0).data_type(), &DataType::Float64);
+    assert_eq!(schema.field(1).data_type(), &DataType::Boolean);
+    let x = batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<Float64Array>()
+        .unwrap();
+    assert_eq!(x.value(199), 199.0);
+    assert_eq!(x.value(200), 0.5);
+}
+
+#[test]
+fn to_dot_nodes_and_relationships() {
+    let alice = Node {
+        id: 1,
+        label_count: 1,
+        labels: vec![String::from("Person")],
+        properties: hashmap! {
+            Arc::from("name") => Value::String(String::from("Alice \"A\"")),
+            Arc::from("born") => Value::Date(NaiveDate::from_ymd(1990, 1, 1)),
+        },
+    };
+    let bob = Node {
+        id: 2,
+        label_count: 2,
+        labels: vec![String::from("Person"), String::from("Admin")],
+        properties: hashmap! {},
+    };
+    let knows = Relationship {
+        id: 10,
+        start_id: 1,
+        end_id: 2,
+        type_: String::from("KNOWS"),
+        properties: hashmap! { Arc::from("since") => Value::Int(2020) },
+    };
+    let records = vec![record(vec![
+        Value::Node(alice),
+        Value::Relationship(knows),
+        Value::Node(bob),
+    ])];
+
+    assert_eq!(
+        to_dot(&records, &DotOptions::default()),
+        "digraph \"G\" {\n\
+         \x20 \"1\" [label=\":Person\"];\n\
+         \x20 \"2\" [label=\":Person:Admin\"];\n\
+         \x20 \"1\" -> \"2\" [label=\"KNOWS\"];\n\
+         }\n"
+    );
+
+    let options = DotOptions {
+        graph_name: String::from("people"),
+        caption_property: Some(String::from("name")),
+        show_properties: true,
+    };
+    assert_eq!(
+        to_dot(&records, &options),
+        "digraph \"people\" {\n\
+         \x20 \"1\" [label=\"Alice \\\"A\\\"\\nborn: '1990-01-01'\\nname: 'Alice \\\"A\\\"'\"];\n\
+         \x20 \"2\" [label=\":Person:Admin\"];\n\
+         \x20 \"1\" -> \"2\" [label=\"KNOWS\\nsince: 2020\"];\n\
+         }\n"
+    );
+}
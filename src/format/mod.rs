@@ -0,0 +1,214 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Human readable rendering of query results.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use rsmgclient::format::{render_table, TableOptions};
+//! use rsmgclient::{ConnectParams, Connection, MgError};
+//!
+//! # fn main() -> Result<(), MgError> {
+//! let mut connection = Connection::connect(&ConnectParams::default())?;
+//! let columns = connection.execute("MATCH (p:Person) RETURN p.name, p.age", None)?;
+//! let records = connection.fetchall()?;
+//! print!("{}", render_table(&columns, &records, TableOptions::default()));
+//! // +---------+-------+
+//! // | p.name  | p.age |
+//! // +---------+-------+
+//! // | 'Alice' |    30 |
+//! // +---------+-------+
+//! # Ok(()) }
+//! ```
+
+use crate::value::{DisplayOptions, Record, Value};
+
+/// Characters used to draw tables, see `TableOptions::style`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableStyle {
+    /// Borders drawn using `+`, `-` and `|`.
+    Ascii,
+    /// Borders drawn using Unicode box-drawing characters.
+    Unicode,
+}
+
+/// Options of `render_table`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableOptions {
+    /// Defaults to `TableStyle::Ascii`.
+    pub style: TableStyle,
+    /// Maximum number of characters in a cell, longer values are truncated and end with `...`,
+    /// or `…` in the Unicode style. Not limited by default.
+    pub max_column_width: Option<usize>,
+    /// Maximum number of records rendered, the number of omitted records is written below the
+    /// table. Not limited by default.
+    pub max_rows: Option<usize>,
+    /// Formatting of values, e.g. of dates and times.
+    pub display: DisplayOptions,
+}
+
+impl Default for TableOptions {
+    fn default() -> Self {
+        TableOptions {
+            style: TableStyle::Ascii,
+            max_column_width: None,
+            max_rows: None,
+            display: DisplayOptions::default(),
+        }
+    }
+}
+
+struct Border {
+    horizontal: char,
+    vertical: char,
+    /// Left, middle and right junctions of the top, header separator and bottom lines.
+    top: [char; 3],
+    middle: [char; 3],
+    bottom: [char; 3],
+    ellipsis: &'static str,
+}
+
+const ASCII: Border = Border {
+    horizontal: '-',
+    vertical: '|',
+    top: ['+', '+', '+'],
+    middle: ['+', '+', '+'],
+    bottom: ['+', '+', '+'],
+    ellipsis: "...",
+};
+
+const UNICODE: Border = Border {
+    horizontal: '─',
+    vertical: '│',
+    top: ['┌', '┬', '┐'],
+    middle: ['├', '┼', '┤'],
+    bottom: ['└', '┴', '┘'],
+    ellipsis: "…",
+};
+
+/// Returns text of a cell, on a single line and truncated to `max_width` characters.
+fn cell_text(text: &str, max_width: Option<usize>, ellipsis: &str) -> String {
+    let text = text.replace('\r', "\\r").replace('\n', "\\n");
+    let max_width = match max_width {
+        Some(x) if text.chars().count() > x => x,
+        _ => return text,
+    };
+    let ellipsis_width = ellipsis.chars().count();
+    if max_width <= ellipsis_width {
+        return text.chars().take(max_width).collect();
+    }
+    let mut truncated: String = text.chars().take(max_width - ellipsis_width).collect();
+    truncated.push_str(ellipsis);
+    truncated
+}
+
+fn line(border: &Border, junctions: [char; 3], widths: &[usize]) -> String {
+    let mut line = String::new();
+    line.push(junctions[0]);
+    for (i, width) in widths.iter().enumerate() {
+        if i > 0 {
+            line.push(junctions[1]);
+        }
+        line.push_str(&border.horizontal.to_string().repeat(width + 2));
+    }
+    line.push(junctions[2]);
+    line.push('\n');
+    line
+}
+
+fn row(border: &Border, cells: &[(String, bool)], widths: &[usize]) -> String {
+    let mut row = String::new();
+    row.push(border.vertical);
+    for ((text, right_aligned), width) in cells.iter().zip(widths) {
+        let padding = " ".repeat(width - text.chars().count());
+        match right_aligned {
+            true => row.push_str(&format!(" {}{} ", padding, text)),
+            false => row.push_str(&format!(" {}{} ", text, padding)),
+        }
+        row.push(border.vertical);
+    }
+    row.push('\n');
+    row
+}
+
+/// Renders `records` as a table with a header of `columns`.
+///
+/// Values are formatted using `Value::display_with` and `TableOptions::display`, numbers are
+/// aligned to the right.
+/// Line breaks in values are escaped to keep every record on a single line. Widths are counted
+/// in characters, so columns containing wide characters, e.g. CJK, may not be aligned.
+pub fn render_table(columns: &[String], records: &[Record], options: TableOptions) -> String {
+    let border = match options.style {
+        TableStyle::Ascii => &ASCII,
+        TableStyle::Unicode => &UNICODE,
+    };
+    let rendered = match options.max_rows {
+        Some(x) => &records[..records.len().min(x)],
+        None => records,
+    };
+
+    let header: Vec<(String, bool)> = columns
+        .iter()
+        .map(|x| {
+            (
+                cell_text(x, options.max_column_width, border.ellipsis),
+                false,
+            )
+        })
+        .collect();
+    let rows: Vec<Vec<(String, bool)>> = rendered
+        .iter()
+        .map(|record| {
+            (0..columns.len())
+                .map(|i| match record.values.get(i) {
+                    Some(value) => (
+                        cell_text(
+                            &value.display_with(&options.display).to_string(),
+                            options.max_column_width,
+                            border.ellipsis,
+                        ),
+                        matches!(value, Value::Int(_) | Value::Float(_)),
+                    ),
+                    None => (String::new(), false),
+                })
+                .collect()
+        })
+        .collect();
+    let widths: Vec<usize> = (0..columns.len())
+        .map(|i| {
+            rows.iter()
+                .chain(std::iter::once(&header))
+                .map(|x| x[i].0.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let mut table = line(border, border.top, &widths);
+    table.push_str(&row(border, &header, &widths));
+    table.push_str(&line(border, border.middle, &widths));
+    for cells in &rows {
+        table.push_str(&row(border, cells, &widths));
+    }
+    table.push_str(&line(border, border.bottom, &widths));
+    let omitted = records.len() - rendered.len();
+    if omitted > 0 {
+        table.push_str(&format!("({} more rows)\n", omitted));
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,92 @@
+use super::*;
+use std::sync::Arc;
+
+fn records(columns: &[String], rows: Vec<Vec<Value>>) -> Vec<Record> {
+    let columns = Arc::new(columns.to_vec());
+    rows.into_iter()
+        .map(|values| Record::new(columns.clone(), values))
+        .collect()
+}
+
+#[test]
+fn render_ascii_table() {
+    let columns = vec![String::from("name"), String::from("age")];
+    let records = records(
+        &columns,
+        vec![
+            vec![Value::String(String::from("Alice")), Value::Int(30)],
+            vec![Value::String(String::from("Bob")), Value::Null],
+        ],
+    );
+    assert_eq!(
+        render_table(&columns, &records, TableOptions::default()),
+        "+---------+------+\n\
+         | name    | age  |\n\
+         +---------+------+\n\
+         | 'Alice' |   30 |\n\
+         | 'Bob'   | NULL |\n\
+         +---------+------+\n"
+    );
+}
+
+#[test]
+fn render_unicode_table() {
+    let columns = vec![String::from("x")];
+    let records = records(&columns, vec![vec![Value::Float(1.5)]]);
+    let options = TableOptions {
+        style: TableStyle::Unicode,
+        ..Default::default()
+    };
+    assert_eq!(
+        render_table(&columns, &records, options),
+        "┌─────┐\n\
+         │ x   │\n\
+         ├─────┤\n\
+         │ 1.5 │\n\
+         └─────┘\n"
+    );
+}
+
+#[test]
+fn render_table_truncation() {
+    let columns = vec![String::from("text")];
+    let records = records(
+        &columns,
+        vec![
+            vec![Value::String(String::from("first\nline"))],
+            vec![Value::String(String::from("second"))],
+            vec![Value::String(String::from("third"))],
+        ],
+    );
+    let options = TableOptions {
+        max_column_width: Some(8),
+        max_rows: Some(2),
+        ..Default::default()
+    };
+    assert_eq!(
+        render_table(&columns, &records, options),
+        "+----------+\n\
+         | text     |\n\
+         +----------+\n\
+         | 'firs... |\n\
+         | 'second' |\n\
+         +----------+\n\
+         (1 more rows)\n"
+    );
+
+    assert_eq!(cell_text("abcdef", Some(4), "…"), "abc…");
+    assert_eq!(cell_text("abcdef", Some(2), "..."), "ab");
+    assert_eq!(cell_text("abc", Some(3), "..."), "abc");
+}
+
+#[test]
+fn render_empty_table() {
+    let columns = vec![String::from("n")];
+    assert_eq!(
+        render_table(&columns, &[], TableOptions::default()),
+        "+---+\n\
+         | n |\n\
+         +---+\n\
+         +---+\n"
+    );
+}
@@ -0,0 +1,127 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Graphs of the nodes and relationships returned by queries.
+//!
+//! `to_petgraph` is enabled by the `petgraph` feature.
+
+use crate::value::{Node, Path, Record, Relationship, Value};
+#[cfg(feature = "petgraph")]
+use petgraph::graph::{DiGraph, NodeIndex};
+#[cfg(feature = "petgraph")]
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+/// Builds a directed graph of all nodes and relationships contained in the records.
+///
+/// Nodes, relationships and paths are collected from all values of the records, including the
+/// ones nested in lists and maps. Nodes and relationships are deduplicated by their ids, the
+/// first occurrence is kept. Relationships of paths are converted into relationships between the
+/// adjacent nodes of the path.
+///
+/// Relationships whose start or end node isn't contained in the records are skipped, e.g.
+/// `MATCH (a)-[r]->(b) RETURN r` doesn't produce any edges while `RETURN a, r, b` does.
+#[cfg(feature = "petgraph")]
+pub fn to_petgraph(records: &[Record]) -> DiGraph<Node, Relationship> {
+    let elements = GraphElements::from_records(records);
+    let mut graph = DiGraph::with_capacity(elements.nodes.len(), elements.relationships.len());
+    let mut indices: HashMap<i64, NodeIndex> = HashMap::new();
+    for node in elements.nodes {
+        let id = node.id;
+        indices.insert(id, graph.add_node(node));
+    }
+    for relationship in elements.relationships {
+        if let (Some(start), Some(end)) = (
+            indices.get(&relationship.start_id),
+            indices.get(&relationship.end_id),
+        ) {
+            graph.add_edge(*start, *end, relationship);
+        }
+    }
+    graph
+}
+
+/// Nodes and relationships contained in records, deduplicated by their ids and kept in the
+/// order of their first occurrence.
+#[derive(Default)]
+pub(crate) struct GraphElements {
+    pub(crate) nodes: Vec<Node>,
+    node_ids: HashSet<i64>,
+    pub(crate) relationships: Vec<Relationship>,
+    relationship_ids: HashSet<i64>,
+}
+
+impl GraphElements {
+    /// Collects nodes, relationships and paths from all values of the records, including the
+    /// ones nested in lists and maps. Relationships of paths are converted into relationships
+    /// between the adjacent nodes of the path.
+    pub(crate) fn from_records(records: &[Record]) -> GraphElements {
+        let mut elements = GraphElements::default();
+        for record in records {
+            for value in &record.values {
+                elements.collect(value);
+            }
+        }
+        elements
+    }
+
+    fn collect(&mut self, value: &Value) {
+        match value {
+            Value::Node(x) => self.add_node(x),
+            Value::Relationship(x) => self.add_relationship(x),
+            Value::Path(x) => self.add_path(x),
+            Value::List(x) => x.iter().for_each(|x| self.collect(x)),
+            Value::Map(x) => x.values().for_each(|x| self.collect(x)),
+            _ => {}
+        }
+    }
+
+    fn add_node(&mut self, node: &Node) {
+        if self.node_ids.insert(node.id) {
+            self.nodes.push(node.clone());
+        }
+    }
+
+    fn add_relationship(&mut self, relationship: &Relationship) {
+        if self.relationship_ids.insert(relationship.id) {
+            self.relationships.push(relationship.clone());
+        }
+    }
+
+    fn add_path(&mut self, path: &Path) {
+        for node in &path.nodes {
+            self.add_node(node);
+        }
+        for (i, relationship) in path.relationships.iter().enumerate() {
+            let (start, end) = match (path.nodes.get(i), path.nodes.get(i + 1)) {
+                (Some(x), Some(y)) => (x, y),
+                _ => break,
+            };
+            let (start, end) = match path.is_reversed(i) {
+                true => (end, start),
+                false => (start, end),
+            };
+            self.add_relationship(&Relationship {
+                id: relationship.id,
+                start_id: start.id,
+                end_id: end.id,
+                type_: relationship.type_.clone(),
+                properties: relationship.properties.clone(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,101 @@
+use super::*;
+use crate::value::UnboundRelationship;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+fn node(id: i64) -> Node {
+    Node {
+        id,
+        label_count: 1,
+        labels: vec![String::from("Person")],
+        properties: HashMap::new(),
+    }
+}
+
+fn relationship(id: i64, start_id: i64, end_id: i64) -> Relationship {
+    Relationship {
+        id,
+        start_id,
+        end_id,
+        type_: String::from("KNOWS"),
+        properties: HashMap::new(),
+    }
+}
+
+fn record(values: Vec<Value>) -> Record {
+    Record::new(Arc::default(), values)
+}
+
+#[test]
+fn graph_elements_dedupe() {
+    let records = vec![
+        record(vec![
+            Value::Node(node(1)),
+            Value::Relationship(relationship(10, 1, 2)),
+            Value::Node(node(2)),
+        ]),
+        record(vec![
+            Value::Node(node(2)),
+            Value::Relationship(relationship(11, 2, 3)),
+            Value::List(vec![Value::Node(node(3)), Value::Node(node(1))]),
+        ]),
+        record(vec![Value::Relationship(relationship(12, 3, 4))]),
+    ];
+
+    let elements = GraphElements::from_records(&records);
+    let nodes: Vec<i64> = elements.nodes.iter().map(|x| x.id).collect();
+    assert_eq!(nodes, vec![1, 2, 3]);
+    let relationships: Vec<i64> = elements.relationships.iter().map(|x| x.id).collect();
+    assert_eq!(relationships, vec![10, 11, 12]);
+}
+
+fn path() -> Path {
+    Path {
+        node_count: 3,
+        relationship_count: 2,
+        nodes: vec![node(1), node(2), node(3)],
+        relationships: vec![
+            UnboundRelationship {
+                id: 10,
+                type_: String::from("KNOWS"),
+                properties: HashMap::new(),
+            },
+            UnboundRelationship {
+                id: 11,
+                type_: String::from("KNOWS"),
+                properties: HashMap::new(),
+            },
+        ],
+        sequence: vec![1, 1, -2, 2],
+    }
+}
+
+#[test]
+fn graph_elements_path() {
+    let elements = GraphElements::from_records(&[record(vec![Value::Path(path())])]);
+    assert_eq!(elements.nodes.len(), 3);
+    let relationships: Vec<(i64, i64, i64)> = elements
+        .relationships
+        .iter()
+        .map(|x| (x.start_id, x.end_id, x.id))
+        .collect();
+    assert_eq!(relationships, vec![(1, 2, 10), (3, 2, 11)]);
+}
+
+#[cfg(feature = "petgraph")]
+#[test]
+fn to_petgraph_wires_edges() {
+    let records = vec![
+        record(vec![Value::Path(path())]),
+        record(vec![Value::Relationship(relationship(12, 3, 4))]),
+    ];
+
+    let graph = to_petgraph(&records);
+    assert_eq!(graph.node_count(), 3);
+    let edges: Vec<(i64, i64, i64)> = graph
+        .raw_edges()
+        .iter()
+        .map(|x| (graph[x.source()].id, graph[x.target()].id, x.weight.id))
+        .collect();
+    assert_eq!(edges, vec![(1, 2, 10), (3, 2, 11)]);
+}
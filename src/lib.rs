@@ -14,13 +14,37 @@
 
 #[cfg_attr(test, macro_use)]
 extern crate maplit;
+// Lets code generated by the derive macros refer to `::rsmgclient` inside this crate as well.
+extern crate self as rsmgclient;
 
+#[cfg(not(feature = "raw"))]
 #[allow(dead_code)]
 mod bindings;
+/// Raw mgclient bindings, enabled by the `raw` feature, see `Connection::as_raw_session`.
+#[cfg(feature = "raw")]
+pub mod bindings;
 mod connection;
+pub mod cypher;
 mod error;
+pub mod export;
+pub mod format;
+pub mod graph;
+mod mapping;
+pub mod migrations;
+pub mod query;
+mod statement_stats;
+mod summary;
+#[cfg(feature = "test-harness")]
+pub mod testing;
 mod value;
 
 pub use connection::*;
 pub use error::*;
+#[doc(hidden)]
+pub use mapping::take_property;
+pub use mapping::{GraphNode, GraphRelationship};
+#[cfg(feature = "derive")]
+pub use rsmgclient_derive::{GraphNode, GraphRelationship};
+pub use statement_stats::*;
+pub use summary::*;
 pub use value::*;
@@ -1,4 +1,5 @@
-use rsmgclient::{ConnectParams, Connection, MgError, Value};
+use rsmgclient::format::{render_table, TableOptions};
+use rsmgclient::{ConnectParams, Connection, MgError};
 
 fn execute_query() -> Result<(), MgError> {
     // Connect to Memgraph.
@@ -16,17 +17,11 @@ fn execute_query() -> Result<(), MgError> {
 
     // Fetch the graph.
     let columns = connection.execute("MATCH (n)-[r]->(m) RETURN n, r, m;", None)?;
-    println!("Columns: {}", columns.join(", "));
-    for record in connection.fetchall()? {
-        for value in record.values {
-            match value {
-                Value::Node(node) => print!("{}", node),
-                Value::Relationship(edge) => print!("-{}-", edge),
-                value => print!("{}", value),
-            }
-        }
-        println!();
-    }
+    let records = connection.fetchall()?;
+    print!(
+        "{}",
+        render_table(&columns, &records, TableOptions::default())
+    );
     connection.commit()?;
 
     Ok(())
@@ -0,0 +1,165 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Mapping of Rust structs to nodes and relationships.
+//!
+//! The traits are usually implemented using `#[derive(GraphNode)]` and
+//! `#[derive(GraphRelationship)]`, enabled by the `derive` feature.
+
+use crate::cypher::escape_identifier;
+use crate::error::MgError;
+use crate::value::{Node, Properties, QueryParam, Relationship, Value};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+/// Struct stored as a node with a single label.
+///
+/// Property values are converted using `Into<QueryParam>` and `TryFrom<Value>` of the field
+/// types. Properties missing on a node are converted from `Value::Null`, which succeeds only for
+/// `Option` fields.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # {
+/// use rsmgclient::GraphNode;
+///
+/// #[derive(GraphNode)]
+/// #[graph(label = "Person")]
+/// struct Person {
+///     #[graph(id)]
+///     email: String,
+///     name: String,
+///     age: Option<i64>,
+/// }
+///
+/// assert_eq!(
+///     Person::create_query(),
+///     "CREATE (n:`Person` {`email`: $email, `name`: $name, `age`: $age}) RETURN n"
+/// );
+/// assert_eq!(
+///     Person::match_query(),
+///     "MATCH (n:`Person` {`email`: $email}) RETURN n"
+/// );
+/// # }
+/// ```
+pub trait GraphNode: Sized {
+    /// Label of the nodes.
+    const LABEL: &'static str;
+    /// Property identifying the nodes.
+    const ID_PROPERTY: &'static str;
+    /// All mapped properties.
+    const PROPERTIES: &'static [&'static str];
+
+    /// Returns all properties as query parameters named after the properties.
+    fn to_query_params(&self) -> HashMap<String, QueryParam>;
+
+    /// Converts node into the struct.
+    ///
+    /// Returns error if the node doesn't have the label or if any of the properties can't be
+    /// converted.
+    fn from_node(node: Node) -> Result<Self, MgError>;
+
+    /// Returns query creating a node bound to `n` with all properties taken from parameters
+    /// returned by `to_query_params`, and returning it.
+    fn create_query() -> String {
+        format!(
+            "CREATE (n:{} {}) RETURN n",
+            escape(Self::LABEL),
+            properties_pattern(Self::PROPERTIES)
+        )
+    }
+
+    /// Returns query matching the node by its id property, taken from a parameter of the same
+    /// name, and returning it.
+    fn match_query() -> String {
+        format!(
+            "MATCH (n:{} {}) RETURN n",
+            escape(Self::LABEL),
+            properties_pattern(&[Self::ID_PROPERTY])
+        )
+    }
+}
+
+/// Struct stored as a relationship of a single type.
+///
+/// Property values are converted the same way as for `GraphNode`. Property names `start_id` and
+/// `end_id` are reserved for parameters identifying the start and end nodes.
+pub trait GraphRelationship: Sized {
+    /// Type of the relationships.
+    const TYPE: &'static str;
+    /// All mapped properties.
+    const PROPERTIES: &'static [&'static str];
+
+    /// Returns all properties as query parameters named after the properties.
+    fn to_query_params(&self) -> HashMap<String, QueryParam>;
+
+    /// Converts relationship into the struct.
+    ///
+    /// Returns error if the relationship doesn't have the type or if any of the properties can't
+    /// be converted.
+    fn from_relationship(relationship: Relationship) -> Result<Self, MgError>;
+
+    /// Returns query creating a relationship bound to `r` between nodes with ids `$start_id` and
+    /// `$end_id`, with all properties taken from parameters returned by `to_query_params`, and
+    /// returning it.
+    fn create_query() -> String {
+        format!(
+            "MATCH (a), (b) WHERE id(a) = $start_id AND id(b) = $end_id \
+             CREATE (a)-[r:{} {}]->(b) RETURN r",
+            escape(Self::TYPE),
+            properties_pattern(Self::PROPERTIES)
+        )
+    }
+
+    /// Returns query matching relationships between nodes with ids `$start_id` and `$end_id` and
+    /// returning them.
+    fn match_query() -> String {
+        format!(
+            "MATCH (a)-[r:{}]->(b) WHERE id(a) = $start_id AND id(b) = $end_id RETURN r",
+            escape(Self::TYPE)
+        )
+    }
+}
+
+/// Escapes identifier of a mapping, panicking if the mapping is invalid. Identifiers generated
+/// by the derive macros are validated at compile time.
+fn escape(identifier: &str) -> String {
+    match escape_identifier(identifier) {
+        Ok(x) => x,
+        Err(err) => panic!("Invalid graph mapping: {}", err),
+    }
+}
+
+fn properties_pattern(properties: &[&str]) -> String {
+    let properties: Vec<String> = properties
+        .iter()
+        .map(|x| format!("{}: ${}", escape(x), x))
+        .collect();
+    format!("{{{}}}", properties.join(", "))
+}
+
+/// Removes property from the map and converts it, used by the derive macros.
+#[doc(hidden)]
+pub fn take_property<T>(properties: &mut Properties, key: &str) -> Result<T, MgError>
+where
+    T: TryFrom<Value, Error = MgError>,
+{
+    let value = properties.remove(key).unwrap_or(Value::Null);
+    T::try_from(value).map_err(|err| MgError::new(format!("Property {}: {}", key, err)))
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,155 @@
+use super::*;
+use std::sync::Arc;
+
+struct City {
+    name: String,
+    population: Option<i64>,
+}
+
+impl GraphNode for City {
+    const LABEL: &'static str = "City";
+    const ID_PROPERTY: &'static str = "name";
+    const PROPERTIES: &'static [&'static str] = &["name", "population"];
+
+    fn to_query_params(&self) -> HashMap<String, QueryParam> {
+        hashmap! {
+            String::from("name") => QueryParam::from(self.name.clone()),
+            String::from("population") => QueryParam::from(self.population),
+        }
+    }
+
+    fn from_node(node: Node) -> Result<Self, MgError> {
+        let mut properties = node.properties;
+        Ok(City {
+            name: take_property(&mut properties, "name")?,
+            population: take_property(&mut properties, "population")?,
+        })
+    }
+}
+
+#[test]
+fn graph_node_queries() {
+    assert_eq!(
+        City::create_query(),
+        "CREATE (n:`City` {`name`: $name, `population`: $population}) RETURN n"
+    );
+    assert_eq!(
+        City::match_query(),
+        "MATCH (n:`City` {`name`: $name}) RETURN n"
+    );
+}
+
+#[test]
+fn take_property_conversion() {
+    let node = Node {
+        id: 1,
+        label_count: 1,
+        labels: vec![String::from("City")],
+        properties: hashmap! { Arc::from("name") => Value::String(String::from("Zagreb")) },
+    };
+    let city = City::from_node(node).unwrap();
+    assert_eq!(city.name, "Zagreb");
+    assert_eq!(city.population, None);
+
+    let mut properties = hashmap! { Arc::from("name") => Value::Int(1) };
+    let err = take_property::<String>(&mut properties, "name").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Property name: Can't convert Int value to String"
+    );
+    let err = take_property::<String>(&mut properties, "name").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Property name: Can't convert Null value to String"
+    );
+}
+
+#[cfg(feature = "derive")]
+mod derive {
+    use crate::{GraphNode, GraphRelationship, Node, QueryParam, Relationship, Value};
+    use std::sync::Arc;
+
+    #[derive(GraphNode, Debug, PartialEq)]
+    #[graph(label = "Person")]
+    struct Person {
+        #[graph(id)]
+        email: String,
+        #[graph(rename = "full_name")]
+        name: String,
+        age: Option<i64>,
+        #[graph(skip)]
+        cached: Vec<String>,
+    }
+
+    #[derive(GraphRelationship, Debug, PartialEq)]
+    struct WorksAt {
+        since: i64,
+    }
+
+    #[test]
+    fn derive_graph_node() {
+        assert_eq!(Person::LABEL, "Person");
+        assert_eq!(Person::ID_PROPERTY, "email");
+        assert_eq!(Person::PROPERTIES, &["email", "full_name", "age"]);
+
+        let person = Person {
+            email: String::from("alice@example.com"),
+            name: String::from("Alice"),
+            age: None,
+            cached: vec![String::from("x")],
+        };
+        let params = person.to_query_params();
+        assert_eq!(params.len(), 3);
+        assert!(matches!(&params["full_name"], QueryParam::String(x) if x == "Alice"));
+        assert!(matches!(params["age"], QueryParam::Null));
+
+        let node = Node {
+            id: 1,
+            label_count: 1,
+            labels: vec![String::from("Person")],
+            properties: hashmap! {
+                Arc::from("email") => Value::String(String::from("alice@example.com")),
+                Arc::from("full_name") => Value::String(String::from("Alice")),
+                Arc::from("age") => Value::Int(30),
+            },
+        };
+        assert_eq!(
+            Person::from_node(node.clone()).unwrap(),
+            Person {
+                email: String::from("alice@example.com"),
+                name: String::from("Alice"),
+                age: Some(30),
+                cached: Vec::new(),
+            }
+        );
+
+        let mut other = node;
+        other.labels = vec![String::from("City")];
+        assert_eq!(
+            Person::from_node(other).unwrap_err().to_string(),
+            "Node doesn't have label Person"
+        );
+    }
+
+    #[test]
+    fn derive_graph_relationship() {
+        assert_eq!(WorksAt::TYPE, "WORKS_AT");
+        assert_eq!(
+            WorksAt::create_query(),
+            "MATCH (a), (b) WHERE id(a) = $start_id AND id(b) = $end_id \
+             CREATE (a)-[r:`WORKS_AT` {`since`: $since}]->(b) RETURN r"
+        );
+
+        let relationship = Relationship {
+            id: 1,
+            start_id: 2,
+            end_id: 3,
+            type_: String::from("WORKS_AT"),
+            properties: hashmap! { Arc::from("since") => Value::Int(2020) },
+        };
+        assert_eq!(
+            WorksAt::from_relationship(relationship).unwrap(),
+            WorksAt { since: 2020 }
+        );
+    }
+}
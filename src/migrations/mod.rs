@@ -0,0 +1,434 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Versioned Cypher migrations.
+//!
+//! Every applied migration is tracked by a node labeled `__rsmgclient_migrations` holding its
+//! `version`, `name` and `applied_at` time. `Migrator` compares these nodes with the known
+//! migrations, applies the pending ones in the order of their versions and reverts the applied
+//! ones using their down scripts.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use rsmgclient::migrations::{Migration, Migrator};
+//! use rsmgclient::{ConnectParams, Connection};
+//!
+//! let mut connection = Connection::connect(&ConnectParams {
+//!     host: Some(String::from("localhost")),
+//!     ..Default::default()
+//! })
+//! .unwrap();
+//! let migrator = Migrator::new(vec![
+//!     Migration::new(1, "create_index", "CREATE INDEX ON :Person(name);")
+//!         .with_down("DROP INDEX ON :Person(name);")
+//!         .non_transactional(),
+//!     Migration::new(2, "seed", "CREATE (:Person {name: 'Alice'});")
+//!         .with_down("MATCH (n:Person {name: 'Alice'}) DETACH DELETE n;"),
+//! ])
+//! .unwrap();
+//!
+//! for step in migrator.plan(&mut connection, None).unwrap() {
+//!     println!("Pending: {} {}", step.version, step.name);
+//! }
+//! migrator.migrate(&mut connection).unwrap();
+//! ```
+
+use crate::connection::{Connection, ConnectionStatus, TxOptions};
+use crate::cypher::{escape_identifier, split_statements};
+use crate::error::MgError;
+use crate::value::{QueryParam, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Label of the nodes tracking applied migrations.
+pub const MIGRATIONS_LABEL: &str = "__rsmgclient_migrations";
+
+/// Versioned migration of the database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Migration {
+    /// Version determining the order of migrations, needs to be positive.
+    pub version: u64,
+    pub name: String,
+    /// Script applying the migration, statements are separated by semicolons.
+    pub up: String,
+    /// Script reverting the migration, migrations without it can't be reverted.
+    pub down: Option<String>,
+    /// Whether the script runs in a single transaction, true by default.
+    ///
+    /// Scripts containing queries which can't be executed in a transaction, e.g. index or
+    /// constraint creation, need to be non-transactional. Their statements are executed one by
+    /// one, a failed migration may therefore remain partially applied.
+    pub transactional: bool,
+}
+
+impl Migration {
+    /// Makes a transactional migration without a down script.
+    pub fn new(version: u64, name: &str, up: &str) -> Migration {
+        Migration {
+            version,
+            name: name.to_string(),
+            up: up.to_string(),
+            down: None,
+            transactional: true,
+        }
+    }
+
+    /// Sets the script reverting the migration.
+    pub fn with_down(mut self, down: &str) -> Migration {
+        self.down = Some(down.to_string());
+        self
+    }
+
+    /// Makes the migration non-transactional, see `Migration::transactional`.
+    pub fn non_transactional(mut self) -> Migration {
+        self.transactional = false;
+        self
+    }
+}
+
+/// Whether a migration step applies or reverts a migration.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Direction {
+    Up,
+    Down,
+}
+
+/// Application or reversion of a single migration, see `Migrator::plan`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationStep {
+    pub version: u64,
+    pub name: String,
+    pub direction: Direction,
+}
+
+/// Applies and reverts migrations, see the module documentation.
+#[derive(Debug, Clone)]
+pub struct Migrator {
+    migrations: Vec<Migration>,
+}
+
+impl Migrator {
+    /// Makes a migrator of the given migrations, which are ordered by their versions.
+    ///
+    /// Returns error if a version is zero, doesn't fit into an `i64` or is used more than once.
+    pub fn new(mut migrations: Vec<Migration>) -> Result<Migrator, MgError> {
+        migrations.sort_by_key(|x| x.version);
+        for (i, migration) in migrations.iter().enumerate() {
+            if migration.version == 0 || migration.version > i64::MAX as u64 {
+                return Err(MgError::new(format!(
+                    "Invalid version {} of migration {}",
+                    migration.version, migration.name
+                )));
+            }
+            if i > 0 && migrations[i - 1].version == migration.version {
+                return Err(MgError::new(format!(
+                    "Duplicate migration version {}",
+                    migration.version
+                )));
+            }
+        }
+        Ok(Migrator { migrations })
+    }
+
+    /// Makes a migrator of the scripts in a directory.
+    ///
+    /// Scripts are named `<version>_<name>.up.cypher` and `<version>_<name>.down.cypher`, e.g.
+    /// `1_create_people.up.cypher`, the `.up` may be left out. Files with other extensions are
+    /// ignored. Migrations loaded from files are transactional.
+    pub fn from_directory<P: AsRef<Path>>(path: P) -> Result<Migrator, MgError> {
+        let path = path.as_ref();
+        let read_error = |err: std::io::Error| {
+            MgError::new(format!(
+                "Can't read migrations from {}: {}",
+                path.display(),
+                err
+            ))
+        };
+
+        let mut files = Vec::new();
+        for entry in fs::read_dir(path).map_err(read_error)? {
+            let entry = entry.map_err(read_error)?;
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            if !file_name.ends_with(".cypher") {
+                continue;
+            }
+            let (version, name, direction) = parse_file_name(&file_name)?;
+            let script = fs::read_to_string(entry.path()).map_err(read_error)?;
+            files.push((version, name, direction, script, file_name));
+        }
+        // Up scripts first so that down scripts find their migrations.
+        files.sort_by_key(|x| (x.0, x.2 == Direction::Down));
+
+        let mut migrations: Vec<Migration> = Vec::new();
+        for (version, name, direction, script, file_name) in files {
+            let last = migrations.last_mut().filter(|x| x.version == version);
+            match (direction, last) {
+                (Direction::Up, None) => migrations.push(Migration::new(version, &name, &script)),
+                (Direction::Down, Some(x)) if x.down.is_none() => x.down = Some(script),
+                (Direction::Up, Some(_)) | (Direction::Down, Some(_)) => {
+                    return Err(MgError::new(format!(
+                        "Duplicate migration version {} in {}",
+                        version, file_name
+                    )))
+                }
+                (Direction::Down, None) => {
+                    return Err(MgError::new(format!(
+                        "Migration {} has no up script",
+                        file_name
+                    )))
+                }
+            }
+        }
+        Migrator::new(migrations)
+    }
+
+    /// Returns the migrations ordered by their versions.
+    pub fn migrations(&self) -> &[Migration] {
+        &self.migrations
+    }
+
+    /// Returns versions of the migrations applied to the database, in ascending order.
+    ///
+    /// Connection needs to be in status `Ready`.
+    pub fn applied(&self, connection: &mut Connection) -> Result<Vec<u64>, MgError> {
+        check_ready(connection)?;
+        connection.execute(
+            &format!(
+                "MATCH (m:{}) RETURN m.version ORDER BY m.version",
+                escape_identifier(MIGRATIONS_LABEL)?
+            ),
+            None,
+        )?;
+        let records = connection.fetchall()?;
+        connection.commit()?;
+        Ok(records
+            .iter()
+            .filter_map(|x| match x.values.first() {
+                Some(Value::Int(x)) if *x > 0 => Some(*x as u64),
+                _ => None,
+            })
+            .collect())
+    }
+
+    /// Returns steps which `migrate_to` would execute, without executing them, i.e. a dry run.
+    ///
+    /// With `target` set, migrations with higher versions are reverted and the rest are
+    /// applied, `Some(0)` reverts all migrations. Otherwise all migrations are applied.
+    /// Connection needs to be in status `Ready`.
+    pub fn plan(
+        &self,
+        connection: &mut Connection,
+        target: Option<u64>,
+    ) -> Result<Vec<MigrationStep>, MgError> {
+        let applied = self.applied(connection)?;
+        self.plan_steps(&applied, target)
+    }
+
+    /// Applies all pending migrations and returns the executed steps.
+    ///
+    /// Every migration is applied in a separate transaction, together with recording it, unless
+    /// it is non-transactional. Migrations applied before an error occurred remain applied.
+    /// Connection needs to be in status `Ready`.
+    pub fn migrate(&self, connection: &mut Connection) -> Result<Vec<MigrationStep>, MgError> {
+        self.migrate_to(connection, None)
+    }
+
+    /// Applies and reverts migrations to reach the `target` version, see `plan`, and returns the
+    /// executed steps.
+    ///
+    /// Reverting fails before anything is executed if a migration which needs to be reverted
+    /// has no down script or is not known to the migrator.
+    pub fn migrate_to(
+        &self,
+        connection: &mut Connection,
+        target: Option<u64>,
+    ) -> Result<Vec<MigrationStep>, MgError> {
+        let steps = self.plan(connection, target)?;
+        for step in &steps {
+            let migration = self
+                .migrations
+                .iter()
+                .find(|x| x.version == step.version)
+                .expect("Planned migration is known");
+            run_step(connection, migration, step.direction).map_err(|err| {
+                let action = match step.direction {
+                    Direction::Up => "Applying",
+                    Direction::Down => "Reverting",
+                };
+                MgError::with_code(
+                    format!(
+                        "{} migration {} ({}) failed: {}",
+                        action, step.version, step.name, err
+                    ),
+                    err.code(),
+                )
+            })?;
+        }
+        Ok(steps)
+    }
+
+    pub(crate) fn plan_steps(
+        &self,
+        applied: &[u64],
+        target: Option<u64>,
+    ) -> Result<Vec<MigrationStep>, MgError> {
+        let target = target.unwrap_or(u64::MAX);
+        let mut steps = Vec::new();
+        for version in applied.iter().rev().filter(|x| **x > target) {
+            let migration = match self.migrations.iter().find(|x| x.version == *version) {
+                Some(x) => x,
+                None => {
+                    return Err(MgError::new(format!(
+                        "Applied migration {} is unknown",
+                        version
+                    )))
+                }
+            };
+            if migration.down.is_none() {
+                return Err(MgError::new(format!(
+                    "Migration {} ({}) can't be reverted, it has no down script",
+                    migration.version, migration.name
+                )));
+            }
+            steps.push(MigrationStep {
+                version: migration.version,
+                name: migration.name.clone(),
+                direction: Direction::Down,
+            });
+        }
+        for migration in &self.migrations {
+            if migration.version <= target && !applied.contains(&migration.version) {
+                steps.push(MigrationStep {
+                    version: migration.version,
+                    name: migration.name.clone(),
+                    direction: Direction::Up,
+                });
+            }
+        }
+        Ok(steps)
+    }
+}
+
+/// Parses `<version>_<name>.up.cypher`, `<version>_<name>.cypher` or
+/// `<version>_<name>.down.cypher`.
+pub(crate) fn parse_file_name(file_name: &str) -> Result<(u64, String, Direction), MgError> {
+    let (stem, direction) = if let Some(x) = file_name.strip_suffix(".down.cypher") {
+        (x, Direction::Down)
+    } else if let Some(x) = file_name.strip_suffix(".up.cypher") {
+        (x, Direction::Up)
+    } else {
+        (file_name.trim_end_matches(".cypher"), Direction::Up)
+    };
+    let parsed = stem.split_once('_').and_then(|(version, name)| {
+        match (version.parse::<u64>(), name.is_empty()) {
+            (Ok(version), false) => Some((version, name.to_string(), direction)),
+            _ => None,
+        }
+    });
+    parsed.ok_or_else(|| {
+        MgError::new(format!(
+            "Invalid migration file name {}, expected <version>_<name>.up.cypher or \
+             <version>_<name>.down.cypher",
+            file_name
+        ))
+    })
+}
+
+fn check_ready(connection: &Connection) -> Result<(), MgError> {
+    let reason = match connection.status() {
+        ConnectionStatus::Ready => return Ok(()),
+        ConnectionStatus::InTransaction => "while in transaction",
+        ConnectionStatus::Executing => "while executing",
+        ConnectionStatus::Fetching => "while fetching",
+        ConnectionStatus::Closed => "while connection is closed",
+        ConnectionStatus::Bad => "while connection is bad",
+    };
+    Err(MgError::new(format!("Can't run migrations {}", reason)))
+}
+
+fn run_step(
+    connection: &mut Connection,
+    migration: &Migration,
+    direction: Direction,
+) -> Result<(), MgError> {
+    let label = escape_identifier(MIGRATIONS_LABEL)?;
+    let (script, record) = match direction {
+        Direction::Up => (
+            migration.up.as_str(),
+            format!(
+                "CREATE (:{} {{version: $version, name: $name, applied_at: localDateTime()}})",
+                label
+            ),
+        ),
+        Direction::Down => (
+            migration.down.as_deref().unwrap_or_default(),
+            format!("MATCH (m:{}) WHERE m.version = $version DELETE m", label),
+        ),
+    };
+    let mut params = HashMap::new();
+    params.insert(
+        String::from("version"),
+        QueryParam::Int(migration.version as i64),
+    );
+    params.insert(
+        String::from("name"),
+        QueryParam::String(migration.name.clone()),
+    );
+
+    if migration.transactional {
+        connection.begin(&TxOptions::default())?;
+        let result = connection
+            .execute_script(script)
+            .and_then(|_| connection.execute(&record, Some(&params)))
+            .and_then(|_| connection.fetchall());
+        return match result {
+            Ok(_) => connection.commit(),
+            Err(err) => {
+                if connection.status() == ConnectionStatus::InTransaction {
+                    let _ = connection.rollback();
+                }
+                Err(err)
+            }
+        };
+    }
+
+    let autocommit = connection.autocommit();
+    connection.try_set_autocommit(true)?;
+    let mut result = Ok(());
+    for statement in split_statements(script) {
+        result = connection
+            .execute(&statement.text, None)
+            .and_then(|_| connection.fetchall())
+            .map(|_| ())
+            .map_err(|err| {
+                MgError::with_code(
+                    format!("Statement at line {} failed: {}", statement.line, err),
+                    err.code(),
+                )
+            });
+        if result.is_err() {
+            break;
+        }
+    }
+    let result = result
+        .and_then(|_| connection.execute(&record, Some(&params)))
+        .and_then(|_| connection.fetchall());
+    let _ = connection.try_set_autocommit(autocommit);
+    result.map(|_| ())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,218 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::connection::ConnectParams;
+use serial_test::serial;
+
+fn migrator() -> Migrator {
+    Migrator::new(vec![
+        Migration::new(3, "seed", "CREATE (:MigrationTest {name: 'c'});"),
+        Migration::new(1, "first", "CREATE (:MigrationTest {name: 'a'});")
+            .with_down("MATCH (n:MigrationTest {name: 'a'}) DELETE n;"),
+        Migration::new(2, "second", "CREATE (:MigrationTest {name: 'b'});")
+            .with_down("MATCH (n:MigrationTest {name: 'b'}) DELETE n;"),
+    ])
+    .unwrap()
+}
+
+fn step(version: u64, name: &str, direction: Direction) -> MigrationStep {
+    MigrationStep {
+        version,
+        name: name.to_string(),
+        direction,
+    }
+}
+
+#[test]
+fn migrator_orders_migrations() {
+    let migrator = migrator();
+    let versions: Vec<u64> = migrator.migrations().iter().map(|x| x.version).collect();
+    assert_eq!(versions, vec![1, 2, 3]);
+    assert!(migrator.migrations()[0].transactional);
+
+    assert_eq!(
+        Migrator::new(vec![Migration::new(1, "a", ""), Migration::new(1, "b", "")])
+            .unwrap_err()
+            .to_string(),
+        "Duplicate migration version 1"
+    );
+    assert!(Migrator::new(vec![Migration::new(0, "a", "")]).is_err());
+    assert!(Migrator::new(vec![Migration::new(u64::MAX, "a", "")]).is_err());
+}
+
+#[test]
+fn plan_migration_steps() {
+    let migrator = migrator();
+    assert_eq!(
+        migrator.plan_steps(&[], None).unwrap(),
+        vec![
+            step(1, "first", Direction::Up),
+            step(2, "second", Direction::Up),
+            step(3, "seed", Direction::Up),
+        ]
+    );
+    assert_eq!(
+        migrator.plan_steps(&[1], Some(2)).unwrap(),
+        vec![step(2, "second", Direction::Up)]
+    );
+    assert_eq!(
+        migrator.plan_steps(&[1, 2], Some(0)).unwrap(),
+        vec![
+            step(2, "second", Direction::Down),
+            step(1, "first", Direction::Down),
+        ]
+    );
+    assert_eq!(
+        migrator
+            .plan_steps(&[1, 3], Some(2))
+            .unwrap_err()
+            .to_string(),
+        "Migration 3 (seed) can't be reverted, it has no down script"
+    );
+    assert_eq!(
+        migrator
+            .plan_steps(&[1, 4], Some(2))
+            .unwrap_err()
+            .to_string(),
+        "Applied migration 4 is unknown"
+    );
+    assert!(migrator.plan_steps(&[1, 2, 3], None).unwrap().is_empty());
+}
+
+#[test]
+fn parse_migration_file_names() {
+    assert_eq!(
+        parse_file_name("1_create_people.up.cypher").unwrap(),
+        (1, String::from("create_people"), Direction::Up)
+    );
+    assert_eq!(
+        parse_file_name("002_seed.cypher").unwrap(),
+        (2, String::from("seed"), Direction::Up)
+    );
+    assert_eq!(
+        parse_file_name("10_seed.down.cypher").unwrap(),
+        (10, String::from("seed"), Direction::Down)
+    );
+    assert!(parse_file_name("seed.cypher").is_err());
+    assert!(parse_file_name("1_.cypher").is_err());
+    assert!(parse_file_name("x_seed.cypher").is_err());
+}
+
+#[test]
+fn migrator_from_directory() {
+    let dir = std::env::temp_dir().join(format!("rsmgclient-migrations-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("2_seed.cypher"), "CREATE (:B);").unwrap();
+    fs::write(dir.join("1_first.up.cypher"), "CREATE (:A);").unwrap();
+    fs::write(dir.join("1_first.down.cypher"), "MATCH (n:A) DELETE n;").unwrap();
+    fs::write(dir.join("README.md"), "Migrations").unwrap();
+
+    let migrator = Migrator::from_directory(&dir).unwrap();
+    assert_eq!(
+        migrator.migrations(),
+        &[
+            Migration::new(1, "first", "CREATE (:A);").with_down("MATCH (n:A) DELETE n;"),
+            Migration::new(2, "seed", "CREATE (:B);"),
+        ]
+    );
+
+    fs::write(dir.join("3_orphan.down.cypher"), "").unwrap();
+    assert!(Migrator::from_directory(&dir).is_err());
+    fs::remove_dir_all(&dir).unwrap();
+    assert!(Migrator::from_directory(&dir).is_err());
+}
+
+#[test]
+#[serial]
+fn migrate_up_and_down() {
+    let mut connection = Connection::connect(&ConnectParams {
+        address: Some(String::from("127.0.0.1")),
+        ..Default::default()
+    })
+    .unwrap();
+    connection
+        .execute("MATCH (n) DETACH DELETE n", None)
+        .unwrap();
+    connection.fetchall().unwrap();
+    connection.commit().unwrap();
+
+    let count = |connection: &mut Connection| {
+        connection
+            .execute("MATCH (n:MigrationTest) RETURN count(n)", None)
+            .unwrap();
+        let records = connection.fetchall().unwrap();
+        connection.commit().unwrap();
+        records[0].values[0].clone()
+    };
+
+    let migrator = migrator();
+    assert_eq!(migrator.plan(&mut connection, None).unwrap().len(), 3);
+    assert_eq!(
+        migrator.migrate_to(&mut connection, Some(2)).unwrap().len(),
+        2
+    );
+    assert_eq!(migrator.applied(&mut connection).unwrap(), vec![1, 2]);
+    assert_eq!(count(&mut connection), Value::Int(2));
+
+    assert_eq!(migrator.migrate(&mut connection).unwrap().len(), 1);
+    assert!(migrator.migrate(&mut connection).unwrap().is_empty());
+    assert_eq!(count(&mut connection), Value::Int(3));
+
+    assert!(migrator.migrate_to(&mut connection, Some(1)).is_err());
+    assert_eq!(migrator.applied(&mut connection).unwrap(), vec![1, 2, 3]);
+
+    let migrator = Migrator::new(vec![
+        Migration::new(1, "first", "CREATE (:MigrationTest {name: 'a'});")
+            .with_down("MATCH (n:MigrationTest {name: 'a'}) DELETE n;"),
+        Migration::new(2, "second", "CREATE (:MigrationTest {name: 'b'});")
+            .with_down("MATCH (n:MigrationTest {name: 'b'}) DELETE n;"),
+        Migration::new(3, "seed", "CREATE (:MigrationTest {name: 'c'});")
+            .with_down("MATCH (n:MigrationTest {name: 'c'}) DELETE n;"),
+        Migration::new(4, "index", "CREATE INDEX ON :MigrationTest(name);")
+            .with_down("DROP INDEX ON :MigrationTest(name);")
+            .non_transactional(),
+        Migration::new(
+            5,
+            "broken",
+            "CREATE (:MigrationTest {name: 'd'}); RETURN x;",
+        ),
+    ])
+    .unwrap();
+    let err = migrator.migrate(&mut connection).unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("Applying migration 5 (broken) failed: "));
+    drop(connection);
+    let mut connection = Connection::connect(&ConnectParams {
+        address: Some(String::from("127.0.0.1")),
+        ..Default::default()
+    })
+    .unwrap();
+    assert_eq!(migrator.applied(&mut connection).unwrap(), vec![1, 2, 3, 4]);
+    assert_eq!(count(&mut connection), Value::Int(3));
+
+    assert_eq!(
+        migrator.migrate_to(&mut connection, Some(0)).unwrap(),
+        vec![
+            step(4, "index", Direction::Down),
+            step(3, "seed", Direction::Down),
+            step(2, "second", Direction::Down),
+            step(1, "first", Direction::Down),
+        ]
+    );
+    assert!(migrator.applied(&mut connection).unwrap().is_empty());
+    assert_eq!(count(&mut connection), Value::Int(0));
+}
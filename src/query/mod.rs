@@ -0,0 +1,205 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Builder of simple Cypher queries.
+//!
+//! The builder escapes all identifiers, i.e. variables, labels and property keys, using
+//! `cypher::escape_identifier` and passes all values as query parameters, so queries built from
+//! user input aren't prone to Cypher injection.
+
+use crate::cypher::escape_identifier;
+use crate::error::MgError;
+use crate::value::QueryParam;
+use std::collections::HashMap;
+
+/// Query text with its parameters, ready to be passed to `Connection::execute`.
+#[derive(Debug, Clone)]
+pub struct Query {
+    pub text: String,
+    pub params: HashMap<String, QueryParam>,
+}
+
+/// Builder of `MATCH` queries.
+///
+/// # Examples
+///
+/// ```
+/// use rsmgclient::query::Match;
+///
+/// let query = Match::node("n", "Person")
+///     .where_eq("n.name", "Alice")
+///     .return_(["n.age"])
+///     .unwrap();
+/// assert_eq!(
+///     query.text,
+///     "MATCH (`n`:`Person`) WHERE `n`.`name` = $p0 RETURN `n`.`age`"
+/// );
+/// ```
+#[derive(Debug)]
+pub struct Match {
+    patterns: Vec<String>,
+    conditions: Vec<String>,
+    params: HashMap<String, QueryParam>,
+    error: Option<MgError>,
+}
+
+impl Match {
+    /// Starts a query matching nodes with the given label bound to `variable`.
+    pub fn node(variable: &str, label: &str) -> Match {
+        let mut builder = Match {
+            patterns: Vec::new(),
+            conditions: Vec::new(),
+            params: HashMap::new(),
+            error: None,
+        };
+        builder.add_node(variable, label);
+        builder
+    }
+
+    /// Additionally matches nodes with the given label bound to `variable`.
+    pub fn and_node(mut self, variable: &str, label: &str) -> Match {
+        self.add_node(variable, label);
+        self
+    }
+
+    /// Adds condition that the property, e.g. `n.name`, is equal to `value`. Multiple conditions
+    /// are combined using `AND`.
+    pub fn where_eq<T: Into<QueryParam>>(mut self, property: &str, value: T) -> Match {
+        let property = self.escape_path(property);
+        let param = format!("p{}", self.params.len());
+        self.conditions.push(format!("{} = ${}", property, param));
+        self.params.insert(param, value.into());
+        self
+    }
+
+    /// Finishes the query by returning the given variables or properties, e.g. `n` or `n.name`.
+    ///
+    /// Returns error if any of the identifiers used in the query can't be escaped.
+    pub fn return_<I, S>(mut self, items: I) -> Result<Query, MgError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let items: Vec<String> = items
+            .into_iter()
+            .map(|x| self.escape_path(x.as_ref()))
+            .collect();
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+        if items.is_empty() {
+            return Err(MgError::new(String::from(
+                "Can't build query without return items",
+            )));
+        }
+
+        let mut text = format!("MATCH {}", self.patterns.join(", "));
+        if !self.conditions.is_empty() {
+            text.push_str(&format!(" WHERE {}", self.conditions.join(" AND ")));
+        }
+        text.push_str(&format!(" RETURN {}", items.join(", ")));
+        Ok(Query {
+            text,
+            params: self.params,
+        })
+    }
+
+    fn add_node(&mut self, variable: &str, label: &str) {
+        let pattern = format!("({}:{})", self.escape(variable), self.escape(label));
+        self.patterns.push(pattern);
+    }
+
+    /// Escapes dot separated identifiers, e.g. `n.name`.
+    fn escape_path(&mut self, path: &str) -> String {
+        path.split('.')
+            .map(|x| self.escape(x))
+            .collect::<Vec<String>>()
+            .join(".")
+    }
+
+    fn escape(&mut self, identifier: &str) -> String {
+        match escape_identifier(identifier) {
+            Ok(x) => x,
+            Err(err) => {
+                self.error.get_or_insert(err);
+                String::new()
+            }
+        }
+    }
+}
+
+/// Helpers for nodes identified by UUIDs, enabled by the `uuid` feature.
+#[cfg(feature = "uuid")]
+impl Query {
+    /// Returns `MERGE` query of a node with the given label and key properties, which sets its
+    /// property `id_key` to a new random UUID if the node is created. The node is returned bound
+    /// to `variable`, the generated UUID is passed as parameter `id`.
+    ///
+    /// Returns error if there are no key properties, since the query would then merge any node
+    /// with the label, or if any of the identifiers can't be escaped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rsmgclient::query::Query;
+    ///
+    /// let query =
+    ///     Query::merge_node_with_uuid("n", "Person", "id", [("email", "alice@example.com")])
+    ///         .unwrap();
+    /// assert_eq!(
+    ///     query.text,
+    ///     "MERGE (`n`:`Person` {`email`: $p0}) ON CREATE SET `n`.`id` = $id RETURN `n`"
+    /// );
+    /// ```
+    pub fn merge_node_with_uuid<I, K, V>(
+        variable: &str,
+        label: &str,
+        id_key: &str,
+        properties: I,
+    ) -> Result<Query, MgError>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: Into<QueryParam>,
+    {
+        let mut params = HashMap::new();
+        let mut keys = Vec::new();
+        for (key, value) in properties {
+            let param = format!("p{}", params.len());
+            keys.push(format!("{}: ${}", escape_identifier(key.as_ref())?, param));
+            params.insert(param, value.into());
+        }
+        if keys.is_empty() {
+            return Err(MgError::new(String::from(
+                "Can't build MERGE query without key properties",
+            )));
+        }
+        params.insert(String::from("id"), QueryParam::new_uuid());
+
+        let variable = escape_identifier(variable)?;
+        let text = format!(
+            "MERGE ({}:{} {{{}}}) ON CREATE SET {}.{} = $id RETURN {}",
+            variable,
+            escape_identifier(label)?,
+            keys.join(", "),
+            variable,
+            escape_identifier(id_key)?,
+            variable
+        );
+        Ok(Query { text, params })
+    }
+}
+
+#[cfg(test)]
+mod tests;
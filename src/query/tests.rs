@@ -0,0 +1,78 @@
+use super::*;
+
+#[test]
+fn match_where_return() {
+    let query = Match::node("n", "Person")
+        .and_node("m", "City")
+        .where_eq("n.name", "Alice")
+        .where_eq("m.population", 1000)
+        .return_(vec!["n", "m.name"])
+        .unwrap();
+    assert_eq!(
+        query.text,
+        "MATCH (`n`:`Person`), (`m`:`City`) WHERE `n`.`name` = $p0 AND `m`.`population` = $p1 \
+         RETURN `n`, `m`.`name`"
+    );
+    assert_eq!(query.params.len(), 2);
+    assert!(matches!(&query.params["p0"], QueryParam::String(x) if x == "Alice"));
+    assert!(matches!(query.params["p1"], QueryParam::Int(1000)));
+}
+
+#[test]
+fn match_escapes_identifiers() {
+    let query = Match::node("n", "Person`) DETACH DELETE (x")
+        .where_eq("n.`name", "x' OR 1=1")
+        .return_(["n"])
+        .unwrap();
+    assert_eq!(
+        query.text,
+        "MATCH (`n`:`Person``) DETACH DELETE (x`) WHERE `n`.```name` = $p0 RETURN `n`"
+    );
+    assert!(matches!(&query.params["p0"], QueryParam::String(x) if x == "x' OR 1=1"));
+}
+
+#[test]
+fn match_invalid_identifiers() {
+    let err = Match::node("n", "Per\0son").return_(["n"]).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Identifier \"Per\\0son\" can't contain null characters"
+    );
+
+    let err = Match::node("n", "Person").return_(["n."]).unwrap_err();
+    assert_eq!(err.to_string(), "Identifier can't be empty");
+
+    let err = Match::node("n", "Person")
+        .return_(Vec::<String>::new())
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Can't build query without return items");
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn merge_node_with_uuid() {
+    let query = Query::merge_node_with_uuid(
+        "n",
+        "Person",
+        "id",
+        vec![("email", "alice@example.com"), ("tenant", "acme")],
+    )
+    .unwrap();
+    assert_eq!(
+        query.text,
+        "MERGE (`n`:`Person` {`email`: $p0, `tenant`: $p1}) ON CREATE SET `n`.`id` = $id \
+         RETURN `n`"
+    );
+    assert_eq!(query.params.len(), 3);
+    match &query.params["id"] {
+        QueryParam::String(x) => assert!(uuid::Uuid::parse_str(x).is_ok()),
+        x => panic!("Unexpected id parameter {:?}", x),
+    }
+
+    let err =
+        Query::merge_node_with_uuid("n", "Person", "id", Vec::<(&str, i64)>::new()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Can't build MERGE query without key properties"
+    );
+}
@@ -0,0 +1,234 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+/// Maximum number of latency samples kept per query shape for computing percentiles.
+const MAX_SAMPLES: usize = 1024;
+
+/// Aggregated execution statistics of all queries sharing the same normalized text.
+///
+/// Collected by `Connection` when enabled with `Connection::set_collect_statement_stats`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatementStats {
+    /// Normalized query text, see `normalize_query`.
+    pub query: String,
+    /// Number of finished executions, including the failed ones.
+    pub calls: u64,
+    /// Number of executions which returned an error.
+    pub errors: u64,
+    /// Number of executions which failed due to a conflict with a concurrent transaction.
+    pub conflicts: u64,
+    /// Sum of all execution times.
+    pub total_time: Duration,
+    /// Shortest execution time.
+    pub min_time: Duration,
+    /// Longest execution time.
+    pub max_time: Duration,
+    samples: Vec<Duration>,
+    next_sample: usize,
+}
+
+impl StatementStats {
+    pub(crate) fn new(query: String) -> StatementStats {
+        StatementStats {
+            query,
+            calls: 0,
+            errors: 0,
+            conflicts: 0,
+            total_time: Duration::from_secs(0),
+            min_time: Duration::from_secs(0),
+            max_time: Duration::from_secs(0),
+            samples: Vec::new(),
+            next_sample: 0,
+        }
+    }
+
+    pub(crate) fn record(&mut self, elapsed: Duration, failed: bool) {
+        if self.calls == 0 || elapsed < self.min_time {
+            self.min_time = elapsed;
+        }
+        if elapsed > self.max_time {
+            self.max_time = elapsed;
+        }
+        self.calls += 1;
+        if failed {
+            self.errors += 1;
+        }
+        self.total_time += elapsed;
+
+        if self.samples.len() < MAX_SAMPLES {
+            self.samples.push(elapsed);
+        } else {
+            self.samples[self.next_sample] = elapsed;
+        }
+        self.next_sample = (self.next_sample + 1) % MAX_SAMPLES;
+    }
+
+    /// Returns average execution time or `None` if nothing was recorded.
+    pub fn mean_time(&self) -> Option<Duration> {
+        if self.calls == 0 {
+            return None;
+        }
+        Some(Duration::from_secs_f64(
+            self.total_time.as_secs_f64() / self.calls as f64,
+        ))
+    }
+
+    /// Returns the execution time below which `percentile` percent of the recent executions
+    /// fall, e.g. `percentile(99.0)` for p99.
+    ///
+    /// Only the latest 1024 executions are taken into account. Returns `None` if nothing was
+    /// recorded.
+    pub fn percentile(&self, percentile: f64) -> Option<Duration> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let mut sorted = self.samples.clone();
+        sorted.sort();
+        let percentile = percentile.clamp(0.0, 100.0);
+        let rank = (percentile / 100.0 * (sorted.len() - 1) as f64).round() as usize;
+        Some(sorted[rank])
+    }
+}
+
+/// Statistics of transaction conflicts on a connection, see `Connection::conflict_stats`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConflictStats {
+    /// Number of statements which failed due to a conflict with a concurrent transaction.
+    pub conflicts: u64,
+    /// Number of conflicts since the last successfully committed transaction, i.e. how many
+    /// times in a row a retried transaction failed.
+    pub consecutive_conflicts: u64,
+    /// Statement which failed due to the latest conflict. Literals of statements setting
+    /// passwords are replaced by `?`.
+    pub last_conflicting_query: Option<String>,
+}
+
+/// Breakdown of time spent on a single query, see `Connection::query_timings`.
+///
+/// Time spent waiting for the server, which includes the query execution on the server and the
+/// network, is measured separately from the time spent converting received records into
+/// `Value`s.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueryTimings {
+    /// Time spent sending the query and receiving the reply to it (`mg_session_run`).
+    pub run: Duration,
+    /// Time spent requesting records (`mg_session_pull`).
+    pub pull: Duration,
+    /// Number of record requests.
+    pub pulls: u64,
+    /// Time spent receiving records and the summary (`mg_session_fetch`).
+    pub fetch: Duration,
+    /// Number of received records and summaries.
+    pub fetches: u64,
+    /// Time spent converting received records into `Value`s.
+    pub conversion: Duration,
+}
+
+/// Normalizes query text so that queries differing only in literal values, comments or
+/// whitespace map to the same shape.
+///
+/// String and numeric literals are replaced by `?`, comments are removed and whitespace is
+/// collapsed. Identifiers, keywords and parameters are kept as they are.
+///
+/// # Examples
+///
+/// ```
+/// use rsmgclient::normalize_query;
+///
+/// assert_eq!(
+///     normalize_query("MATCH (n {name: 'Alice'})\n  WHERE n.age > 30 RETURN n;"),
+///     "MATCH (n {name: ?}) WHERE n.age > ? RETURN n"
+/// );
+/// ```
+pub fn normalize_query(query: &str) -> String {
+    let chars: Vec<char> = query.chars().collect();
+    let mut normalized = String::with_capacity(query.len());
+    let mut pending_space = false;
+    let mut i = 0;
+
+    fn push(normalized: &mut String, pending_space: &mut bool, text: &str) {
+        if *pending_space && !normalized.is_empty() {
+            normalized.push(' ');
+        }
+        *pending_space = false;
+        normalized.push_str(text);
+    }
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            pending_space = true;
+            i += 1;
+        } else if c == '/' && chars.get(i + 1) == Some(&'/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            pending_space = true;
+        } else if c == '/' && chars.get(i + 1) == Some(&'*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                i += 1;
+            }
+            i += 2;
+            pending_space = true;
+        } else if c == '\'' || c == '"' {
+            i += 1;
+            while i < chars.len() && chars[i] != c {
+                if chars[i] == '\\' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            i += 1;
+            push(&mut normalized, &mut pending_space, "?");
+        } else if c == '`' {
+            let start = i;
+            i += 1;
+            while i < chars.len() && chars[i] != '`' {
+                i += 1;
+            }
+            i += 1;
+            let end = i.min(chars.len());
+            let identifier: String = chars[start..end].iter().collect();
+            push(&mut normalized, &mut pending_space, &identifier);
+        } else if c.is_ascii_digit() {
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '.') {
+                i += 1;
+            }
+            push(&mut normalized, &mut pending_space, "?");
+        } else if c.is_alphanumeric() || c == '_' || c == '$' {
+            let start = i;
+            while i < chars.len()
+                && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '$')
+            {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            push(&mut normalized, &mut pending_space, &word);
+        } else {
+            i += 1;
+            push(&mut normalized, &mut pending_space, &c.to_string());
+        }
+    }
+
+    while normalized.ends_with(';') || normalized.ends_with(' ') {
+        normalized.pop();
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests;
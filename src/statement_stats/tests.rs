@@ -0,0 +1,70 @@
+use super::*;
+
+#[test]
+fn normalize_strips_literals() {
+    assert_eq!(
+        normalize_query("MATCH (n:Person {name: 'Alice', age: 32}) RETURN n;"),
+        "MATCH (n:Person {name: ?, age: ?}) RETURN n"
+    );
+    assert_eq!(
+        normalize_query("RETURN \"it\\\"s\", 3.14, -7"),
+        "RETURN ?, ?, -?"
+    );
+}
+
+#[test]
+fn normalize_collapses_whitespace_and_comments() {
+    assert_eq!(
+        normalize_query("  MATCH (n)   // all nodes\n\tRETURN /* count */ count(n)  ;  "),
+        "MATCH (n) RETURN count(n)"
+    );
+}
+
+#[test]
+fn normalize_keeps_identifiers_and_parameters() {
+    assert_eq!(
+        normalize_query("MATCH (n1:`Label 2`) WHERE n1.id = $id2 RETURN n1"),
+        "MATCH (n1:`Label 2`) WHERE n1.id = $id2 RETURN n1"
+    );
+}
+
+#[test]
+fn same_shape_for_different_literals() {
+    assert_eq!(
+        normalize_query("CREATE (:Person {name: 'Alice'})"),
+        normalize_query("CREATE (:Person {name: 'Bob'});")
+    );
+}
+
+#[test]
+fn record_aggregates() {
+    let mut stats = StatementStats::new(String::from("RETURN ?"));
+    assert_eq!(stats.mean_time(), None);
+    assert_eq!(stats.percentile(50.0), None);
+
+    for millis in 1..=100 {
+        stats.record(Duration::from_millis(millis), millis % 10 == 0);
+    }
+
+    assert_eq!(stats.calls, 100);
+    assert_eq!(stats.errors, 10);
+    assert_eq!(stats.min_time, Duration::from_millis(1));
+    assert_eq!(stats.max_time, Duration::from_millis(100));
+    assert_eq!(stats.total_time, Duration::from_millis(5050));
+    assert_eq!(stats.percentile(0.0), Some(Duration::from_millis(1)));
+    assert_eq!(stats.percentile(100.0), Some(Duration::from_millis(100)));
+    assert_eq!(stats.percentile(50.0), Some(Duration::from_millis(51)));
+}
+
+#[test]
+fn record_keeps_latest_samples() {
+    let mut stats = StatementStats::new(String::from("RETURN ?"));
+    for _ in 0..MAX_SAMPLES {
+        stats.record(Duration::from_secs(10), false);
+    }
+    for _ in 0..MAX_SAMPLES {
+        stats.record(Duration::from_secs(1), false);
+    }
+    assert_eq!(stats.percentile(100.0), Some(Duration::from_secs(1)));
+    assert_eq!(stats.max_time, Duration::from_secs(10));
+}
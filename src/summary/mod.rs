@@ -0,0 +1,267 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::value::Value;
+use std::collections::HashMap;
+use std::ops::AddAssign;
+use std::time::Duration;
+
+/// Type of the executed query as reported by the server.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum QueryType {
+    /// Query only reads data (`r`).
+    Read,
+    /// Query only writes data (`w`).
+    Write,
+    /// Query reads and writes data (`rw`).
+    ReadWrite,
+    /// Query changes schema (`s`).
+    Schema,
+    /// Type not known to the client.
+    Unknown(String),
+}
+
+impl QueryType {
+    fn from_code(code: &str) -> QueryType {
+        match code {
+            "r" => QueryType::Read,
+            "w" => QueryType::Write,
+            "rw" => QueryType::ReadWrite,
+            "s" => QueryType::Schema,
+            x => QueryType::Unknown(x.to_string()),
+        }
+    }
+}
+
+/// Counters of changes made by a query, parsed from the `stats` entry of the summary.
+///
+/// Counters not reported by the server are zero.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct QueryStats {
+    /// Number of created nodes.
+    pub nodes_created: i64,
+    /// Number of deleted nodes.
+    pub nodes_deleted: i64,
+    /// Number of created relationships.
+    pub relationships_created: i64,
+    /// Number of deleted relationships.
+    pub relationships_deleted: i64,
+    /// Number of set properties.
+    pub properties_set: i64,
+    /// Number of added labels.
+    pub labels_added: i64,
+    /// Number of removed labels.
+    pub labels_removed: i64,
+    /// Number of created indexes.
+    pub indexes_added: i64,
+    /// Number of dropped indexes.
+    pub indexes_removed: i64,
+    /// Number of created constraints.
+    pub constraints_added: i64,
+    /// Number of dropped constraints.
+    pub constraints_removed: i64,
+}
+
+impl QueryStats {
+    /// Creates stats from the `stats` map sent by the server.
+    pub fn from_map(stats: &HashMap<String, Value>) -> QueryStats {
+        let counter = |key: &str| match stats.get(key) {
+            Some(Value::Int(x)) => *x,
+            _ => 0,
+        };
+        QueryStats {
+            nodes_created: counter("nodes-created"),
+            nodes_deleted: counter("nodes-deleted"),
+            relationships_created: counter("relationships-created"),
+            relationships_deleted: counter("relationships-deleted"),
+            properties_set: counter("properties-set"),
+            labels_added: counter("labels-added"),
+            labels_removed: counter("labels-removed"),
+            indexes_added: counter("indexes-added"),
+            indexes_removed: counter("indexes-removed"),
+            constraints_added: counter("constraints-added"),
+            constraints_removed: counter("constraints-removed"),
+        }
+    }
+
+    /// Returns whether the query made any changes.
+    pub fn contains_updates(&self) -> bool {
+        *self != QueryStats::default()
+    }
+
+    /// Returns descriptions of counters which differ from `expected`, e.g.
+    /// `nodes_created: 3 (expected 2)`, or an empty list if the stats are equal.
+    pub fn diff(&self, expected: &QueryStats) -> Vec<String> {
+        self.counters()
+            .iter()
+            .zip(expected.counters().iter())
+            .filter(|(actual, expected)| actual.1 != expected.1)
+            .map(|(actual, expected)| {
+                format!("{}: {} (expected {})", actual.0, actual.1, expected.1)
+            })
+            .collect()
+    }
+
+    fn counters(&self) -> [(&'static str, i64); 11] {
+        [
+            ("nodes_created", self.nodes_created),
+            ("nodes_deleted", self.nodes_deleted),
+            ("relationships_created", self.relationships_created),
+            ("relationships_deleted", self.relationships_deleted),
+            ("properties_set", self.properties_set),
+            ("labels_added", self.labels_added),
+            ("labels_removed", self.labels_removed),
+            ("indexes_added", self.indexes_added),
+            ("indexes_removed", self.indexes_removed),
+            ("constraints_added", self.constraints_added),
+            ("constraints_removed", self.constraints_removed),
+        ]
+    }
+}
+
+/// Asserts that `QueryStats` have the given counters, all other counters have to be zero.
+///
+/// On failure panics with the list of counters which differ, see `QueryStats::diff`.
+///
+/// # Examples
+///
+/// ```
+/// use rsmgclient::{assert_stats, QueryStats};
+///
+/// let stats = QueryStats {
+///     nodes_created: 2,
+///     relationships_created: 1,
+///     ..Default::default()
+/// };
+/// assert_stats!(stats, nodes_created: 2, relationships_created: 1);
+/// ```
+#[macro_export]
+macro_rules! assert_stats {
+    ($stats:expr $(, $field:ident: $value:expr)* $(,)?) => {{
+        let expected = $crate::QueryStats {
+            $($field: $value,)*
+            ..::std::default::Default::default()
+        };
+        let differences = $crate::QueryStats::diff(&$stats, &expected);
+        if !differences.is_empty() {
+            panic!(
+                "assertion failed: query stats differ: {}",
+                differences.join(", ")
+            );
+        }
+    }};
+}
+
+impl AddAssign<&QueryStats> for QueryStats {
+    fn add_assign(&mut self, other: &QueryStats) {
+        self.nodes_created += other.nodes_created;
+        self.nodes_deleted += other.nodes_deleted;
+        self.relationships_created += other.relationships_created;
+        self.relationships_deleted += other.relationships_deleted;
+        self.properties_set += other.properties_set;
+        self.labels_added += other.labels_added;
+        self.labels_removed += other.labels_removed;
+        self.indexes_added += other.indexes_added;
+        self.indexes_removed += other.indexes_removed;
+        self.constraints_added += other.constraints_added;
+        self.constraints_removed += other.constraints_removed;
+    }
+}
+
+/// Summary of a query, available after all of its records have been fetched.
+///
+/// Well known entries of the summary sent by the server are parsed into typed fields. The
+/// complete summary, including entries not known to the client, is available in `raw`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct QuerySummary {
+    /// Whether there are more records to pull.
+    pub has_more: bool,
+    /// Type of the executed query.
+    pub query_type: Option<QueryType>,
+    /// Time spent parsing the query.
+    pub parsing_time: Option<Duration>,
+    /// Time spent planning the query.
+    pub planning_time: Option<Duration>,
+    /// Time spent executing the query plan.
+    pub plan_execution_time: Option<Duration>,
+    /// Cost estimate of the query plan.
+    pub cost_estimate: Option<f64>,
+    /// Identifier of the query execution. It is also logged by `LogObserver`, so that client
+    /// logs can be matched with the query logs of the server.
+    pub run_id: Option<String>,
+    /// Database the query was executed on.
+    pub db: Option<String>,
+    /// Number of hops made while executing the query.
+    pub number_of_hops: Option<i64>,
+    /// Statistics about changes made by the query, present only for queries making changes.
+    pub stats: Option<QueryStats>,
+    /// All entries of the summary as sent by the server.
+    pub raw: HashMap<String, Value>,
+}
+
+impl QuerySummary {
+    /// Creates summary from the map sent by the server.
+    pub fn from_map(raw: HashMap<String, Value>) -> QuerySummary {
+        QuerySummary {
+            has_more: matches!(raw.get("has_more"), Some(Value::Bool(true))),
+            query_type: match raw.get("type") {
+                Some(Value::String(x)) => Some(QueryType::from_code(x)),
+                _ => None,
+            },
+            parsing_time: duration_entry(&raw, "parsing_time"),
+            planning_time: duration_entry(&raw, "planning_time"),
+            plan_execution_time: duration_entry(&raw, "plan_execution_time"),
+            cost_estimate: match raw.get("cost_estimate") {
+                Some(Value::Float(x)) => Some(*x),
+                Some(Value::Int(x)) => Some(*x as f64),
+                _ => None,
+            },
+            run_id: string_entry(&raw, "run_id"),
+            db: string_entry(&raw, "db"),
+            number_of_hops: match raw.get("number_of_hops") {
+                Some(Value::Int(x)) => Some(*x),
+                _ => None,
+            },
+            stats: match raw.get("stats") {
+                Some(Value::Map(x)) => Some(QueryStats::from_map(x)),
+                _ => None,
+            },
+            raw,
+        }
+    }
+
+    /// Returns raw summary entry with the given key.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.raw.get(key)
+    }
+}
+
+fn duration_entry(raw: &HashMap<String, Value>, key: &str) -> Option<Duration> {
+    let seconds = match raw.get(key) {
+        Some(Value::Float(x)) => *x,
+        Some(Value::Int(x)) => *x as f64,
+        _ => return None,
+    };
+    Duration::try_from_secs_f64(seconds).ok()
+}
+
+fn string_entry(raw: &HashMap<String, Value>, key: &str) -> Option<String> {
+    match raw.get(key) {
+        Some(Value::String(x)) => Some(x.clone()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,152 @@
+use super::*;
+
+#[test]
+fn from_map_parses_known_entries() {
+    let summary = QuerySummary::from_map(hashmap! {
+        String::from("has_more") => Value::Bool(false),
+        String::from("type") => Value::String(String::from("rw")),
+        String::from("parsing_time") => Value::Float(0.5),
+        String::from("planning_time") => Value::Float(0.25),
+        String::from("plan_execution_time") => Value::Float(2.0),
+        String::from("cost_estimate") => Value::Float(1.5),
+        String::from("run_id") => Value::String(String::from("1234")),
+        String::from("db") => Value::String(String::from("memgraph")),
+        String::from("number_of_hops") => Value::Int(3),
+        String::from("stats") => Value::Map(hashmap! {
+            String::from("nodes-created") => Value::Int(2),
+        }),
+        String::from("custom") => Value::Int(7),
+    });
+
+    assert!(!summary.has_more);
+    assert_eq!(summary.query_type, Some(QueryType::ReadWrite));
+    assert_eq!(summary.parsing_time, Some(Duration::from_millis(500)));
+    assert_eq!(summary.planning_time, Some(Duration::from_millis(250)));
+    assert_eq!(summary.plan_execution_time, Some(Duration::from_secs(2)));
+    assert_eq!(summary.cost_estimate, Some(1.5));
+    assert_eq!(summary.run_id, Some(String::from("1234")));
+    assert_eq!(summary.db, Some(String::from("memgraph")));
+    assert_eq!(summary.number_of_hops, Some(3));
+    assert_eq!(
+        summary.stats,
+        Some(QueryStats {
+            nodes_created: 2,
+            ..Default::default()
+        })
+    );
+    assert_eq!(summary.get("custom"), Some(&Value::Int(7)));
+    assert_eq!(summary.raw.len(), 11);
+}
+
+#[test]
+fn from_map_missing_entries() {
+    let summary = QuerySummary::from_map(hashmap! {
+        String::from("has_more") => Value::Bool(true),
+        String::from("type") => Value::String(String::from("x")),
+        String::from("parsing_time") => Value::Float(-1.0),
+        String::from("planning_time") => Value::Float(1e300),
+        String::from("plan_execution_time") => Value::Float(f64::NAN),
+    });
+
+    assert!(summary.has_more);
+    assert_eq!(
+        summary.query_type,
+        Some(QueryType::Unknown(String::from("x")))
+    );
+    assert_eq!(summary.parsing_time, None);
+    assert_eq!(summary.planning_time, None);
+    assert_eq!(summary.plan_execution_time, None);
+    assert_eq!(summary.cost_estimate, None);
+    assert_eq!(summary.run_id, None);
+    assert_eq!(summary.stats, None);
+}
+
+#[test]
+fn query_stats_from_map() {
+    let stats = QueryStats::from_map(&hashmap! {
+        String::from("nodes-created") => Value::Int(1),
+        String::from("nodes-deleted") => Value::Int(2),
+        String::from("relationships-created") => Value::Int(3),
+        String::from("relationships-deleted") => Value::Int(4),
+        String::from("properties-set") => Value::Int(5),
+        String::from("labels-added") => Value::Int(6),
+        String::from("labels-removed") => Value::Int(7),
+        String::from("indexes-added") => Value::Int(8),
+        String::from("indexes-removed") => Value::Int(9),
+        String::from("constraints-added") => Value::Int(10),
+        String::from("constraints-removed") => Value::Int(11),
+    });
+
+    assert_eq!(stats.nodes_created, 1);
+    assert_eq!(stats.nodes_deleted, 2);
+    assert_eq!(stats.relationships_created, 3);
+    assert_eq!(stats.relationships_deleted, 4);
+    assert_eq!(stats.properties_set, 5);
+    assert_eq!(stats.labels_added, 6);
+    assert_eq!(stats.labels_removed, 7);
+    assert_eq!(stats.indexes_added, 8);
+    assert_eq!(stats.indexes_removed, 9);
+    assert_eq!(stats.constraints_added, 10);
+    assert_eq!(stats.constraints_removed, 11);
+    assert!(stats.contains_updates());
+    assert!(!QueryStats::from_map(&HashMap::new()).contains_updates());
+}
+
+#[test]
+fn query_stats_diff() {
+    let stats = QueryStats {
+        nodes_created: 3,
+        properties_set: 1,
+        ..Default::default()
+    };
+    assert!(stats.diff(&stats.clone()).is_empty());
+    assert_eq!(
+        stats.diff(&QueryStats {
+            nodes_created: 2,
+            properties_set: 1,
+            labels_added: 1,
+            ..Default::default()
+        }),
+        vec![
+            String::from("nodes_created: 3 (expected 2)"),
+            String::from("labels_added: 0 (expected 1)"),
+        ]
+    );
+
+    assert_stats!(stats, nodes_created: 3, properties_set: 1);
+    assert_stats!(QueryStats::default());
+}
+
+#[test]
+#[should_panic(expected = "query stats differ: properties_set: 1 (expected 0)")]
+fn assert_stats_fails() {
+    let stats = QueryStats {
+        nodes_created: 3,
+        properties_set: 1,
+        ..Default::default()
+    };
+    assert_stats!(stats, nodes_created: 3);
+}
+
+#[test]
+fn query_stats_add_assign() {
+    let mut stats = QueryStats {
+        nodes_created: 1,
+        properties_set: 2,
+        ..Default::default()
+    };
+    stats += &QueryStats {
+        nodes_created: 3,
+        labels_added: 4,
+        ..Default::default()
+    };
+    assert_eq!(
+        stats,
+        QueryStats {
+            nodes_created: 4,
+            properties_set: 2,
+            labels_added: 4,
+            ..Default::default()
+        }
+    );
+}
@@ -0,0 +1,165 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers for integration tests against a real Memgraph server, enabled by the `test-harness`
+//! feature.
+
+use crate::connection::{ConnectParams, Connection};
+use crate::error::MgError;
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Docker image started by `MemgraphContainer::start`.
+pub const DEFAULT_IMAGE: &str = "memgraph/memgraph";
+
+/// Memgraph server running in a Docker container.
+///
+/// The Bolt port of the container is published on a random free port of the local host, so
+/// several containers can run at the same time. The container is removed when the
+/// `MemgraphContainer` is dropped. Requires the `docker` command to be available.
+///
+/// # Examples
+///
+/// ```no_run
+/// use rsmgclient::testing::MemgraphContainer;
+///
+/// let memgraph = MemgraphContainer::start().unwrap();
+/// let mut connection = memgraph.connect().unwrap();
+/// connection.execute("RETURN 1", None).unwrap();
+/// assert_eq!(connection.fetchall().unwrap().len(), 1);
+/// ```
+pub struct MemgraphContainer {
+    id: String,
+    port: u16,
+}
+
+impl MemgraphContainer {
+    /// Starts a container of the `DEFAULT_IMAGE` and waits until the server accepts connections.
+    pub fn start() -> Result<MemgraphContainer, MgError> {
+        MemgraphContainer::start_image(DEFAULT_IMAGE, &[], Duration::from_secs(30))
+    }
+
+    /// Starts a container of the given `image`, passing `args` to Memgraph, and waits at most
+    /// `timeout` until the server accepts connections.
+    ///
+    /// Telemetry is always disabled. The container is removed if the server doesn't become
+    /// ready in time.
+    pub fn start_image(
+        image: &str,
+        args: &[&str],
+        timeout: Duration,
+    ) -> Result<MemgraphContainer, MgError> {
+        let output = docker(
+            &[
+                "run",
+                "-d",
+                "--rm",
+                "-p",
+                "127.0.0.1::7687",
+                image,
+                "--telemetry-enabled=False",
+            ],
+            args,
+        )?;
+        let id = output.trim().to_string();
+        let port = match docker(&["port", &id, "7687"], &[]).and_then(|x| parse_port(&x)) {
+            Ok(x) => x,
+            Err(err) => {
+                let _ = docker(&["rm", "-f", &id], &[]);
+                return Err(err);
+            }
+        };
+        let container = MemgraphContainer { id, port };
+        container.wait_until_ready(timeout)?;
+        Ok(container)
+    }
+
+    /// Returns id of the container.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns local port the Bolt port of the container is published on.
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Returns parameters for connecting to the server in the container.
+    pub fn connect_params(&self) -> ConnectParams {
+        ConnectParams {
+            address: Some(String::from("127.0.0.1")),
+            port: self.port,
+            ..Default::default()
+        }
+    }
+
+    /// Connects to the server in the container using `connect_params`.
+    pub fn connect(&self) -> Result<Connection, MgError> {
+        Connection::connect(&self.connect_params())
+    }
+
+    /// Stops and removes the container.
+    pub fn stop(self) {}
+
+    fn wait_until_ready(&self, timeout: Duration) -> Result<(), MgError> {
+        let start = Instant::now();
+        loop {
+            match self.connect() {
+                Ok(_) => return Ok(()),
+                Err(err) => {
+                    if start.elapsed() >= timeout {
+                        return Err(MgError::new(format!(
+                            "Memgraph in container {} is not ready after {:?}: {}",
+                            self.id, timeout, err
+                        )));
+                    }
+                    thread::sleep(Duration::from_millis(100));
+                }
+            }
+        }
+    }
+}
+
+impl Drop for MemgraphContainer {
+    fn drop(&mut self) {
+        let _ = docker(&["rm", "-f", &self.id], &[]);
+    }
+}
+
+fn docker(args: &[&str], extra_args: &[&str]) -> Result<String, MgError> {
+    let output = match Command::new("docker").args(args).args(extra_args).output() {
+        Ok(x) => x,
+        Err(err) => return Err(MgError::new(format!("Unable to run docker: {}", err))),
+    };
+    if !output.status.success() {
+        return Err(MgError::new(format!(
+            "Command docker {} failed: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Parses output of `docker port`, e.g. `127.0.0.1:49153`.
+fn parse_port(output: &str) -> Result<u16, MgError> {
+    output
+        .lines()
+        .find_map(|line| line.trim().rsplit(':').next()?.parse().ok())
+        .ok_or_else(|| MgError::new(format!("Unable to parse published port: {}", output)))
+}
+
+#[cfg(test)]
+mod tests;
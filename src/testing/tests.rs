@@ -0,0 +1,23 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+#[test]
+fn parse_docker_port() {
+    assert_eq!(parse_port("127.0.0.1:49153\n").unwrap(), 49153);
+    assert_eq!(parse_port("[::1]:32768\n").unwrap(), 32768);
+    assert!(parse_port("").is_err());
+    assert!(parse_port("127.0.0.1:port").is_err());
+}
@@ -0,0 +1,89 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversions of `num_bigint::BigInt`, enabled by the `num-bigint` feature.
+//!
+//! Memgraph integers have 64 bits. Larger integers have to be passed as strings, e.g.
+//! `QueryParam::from(big_int.to_string())`.
+
+use super::{QueryParam, Value};
+use crate::error::MgError;
+use num_bigint::BigInt;
+use std::convert::TryFrom;
+
+/// Returns the integer equal to the float, `None` if it isn't a whole number.
+fn float_to_big_int(x: f64) -> Option<BigInt> {
+    if !x.is_finite() || x.fract() != 0.0 {
+        return None;
+    }
+    let bits = x.to_bits();
+    let biased_exponent = ((bits >> 52) & 0x7ff) as i64;
+    if biased_exponent == 0 {
+        // Subnormal floats are below one, so they are whole only if zero.
+        return Some(BigInt::from(0));
+    }
+    let mantissa = BigInt::from((bits & 0xf_ffff_ffff_ffff) | 0x10_0000_0000_0000);
+    let exponent = biased_exponent - 1075;
+    // Whole numbers have no set bits below the binary point, shifting right is exact.
+    let magnitude = if exponent >= 0 {
+        mantissa << exponent as usize
+    } else {
+        mantissa >> (-exponent) as usize
+    };
+    Some(if x < 0.0 { -magnitude } else { magnitude })
+}
+
+/// Converts to an integer, returns error if it doesn't fit into 64 bits.
+impl TryFrom<BigInt> for QueryParam {
+    type Error = MgError;
+
+    fn try_from(x: BigInt) -> Result<Self, Self::Error> {
+        match i64::try_from(&x) {
+            Ok(x) => Ok(QueryParam::Int(x)),
+            Err(_) => Err(MgError::new(format!(
+                "BigInt {} is out of range of Int, pass it as a string instead",
+                x
+            ))),
+        }
+    }
+}
+
+/// Converts `Value::Int`, `Value::String` and whole `Value::Float`s to integer.
+///
+/// Floats are converted to the integer they are exactly equal to, e.g. `1e20` to
+/// `100000000000000000000`. Returns error for floats with a fractional part.
+impl TryFrom<Value> for BigInt {
+    type Error = MgError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let big_int = match &value {
+            Value::Int(x) => Some(BigInt::from(*x)),
+            Value::Float(x) => float_to_big_int(*x),
+            Value::String(x) => x.parse().ok(),
+            x => {
+                return Err(MgError::new(format!(
+                    "Can't convert {} value to BigInt",
+                    x.type_name()
+                )))
+            }
+        };
+        big_int.ok_or_else(|| {
+            MgError::new(format!(
+                "Can't convert {} value {} to BigInt, it isn't a whole number",
+                value.type_name(),
+                value
+            ))
+        })
+    }
+}
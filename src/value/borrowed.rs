@@ -0,0 +1,302 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Values borrowed from the last received record, see `Connection::fetchone_ref`.
+
+use super::{
+    convert_mg_list, convert_mg_map, keep_key, mg_value_bool, mg_value_float, mg_value_int,
+    KeyInterner, Value,
+};
+use crate::bindings;
+use std::collections::HashMap;
+use std::fmt;
+use std::marker::PhantomData;
+use std::slice;
+
+unsafe fn mg_string_as_str<'a>(mg_string: *const bindings::mg_string) -> &'a str {
+    let data = bindings::mg_string_data(mg_string) as *const u8;
+    let size = bindings::mg_string_size(mg_string) as usize;
+    std::str::from_utf8(slice::from_raw_parts(data, size)).unwrap()
+}
+
+/// Record whose values are borrowed from the connection until the next fetch.
+///
+/// Strings, lists and maps are read directly from the received message instead of being copied
+/// into `Value`s, use `to_values` to get an owned copy.
+pub struct RecordRef<'a> {
+    columns: &'a [String],
+    row: *const bindings::mg_list,
+    _marker: PhantomData<&'a ()>,
+}
+
+impl<'a> RecordRef<'a> {
+    /// The row must stay valid for `'a`, i.e. until the next fetch on the session.
+    pub(crate) unsafe fn new(columns: &'a [String], row: *const bindings::mg_list) -> Self {
+        RecordRef {
+            columns,
+            row,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns names of the columns.
+    pub fn columns(&self) -> &'a [String] {
+        self.columns
+    }
+
+    /// Returns the number of values.
+    pub fn len(&self) -> usize {
+        ListRef::new(self.row).len()
+    }
+
+    /// Returns `true` if the record has no values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns value at `index` or `None` if it's out of bounds.
+    pub fn get(&self, index: usize) -> Option<ValueRef<'a>> {
+        ListRef::new(self.row).get(index)
+    }
+
+    /// Returns value of the column named `column` or `None` if there is no such column.
+    pub fn get_by_name(&self, column: &str) -> Option<ValueRef<'a>> {
+        let index = self.columns.iter().position(|x| x == column)?;
+        self.get(index)
+    }
+
+    /// Returns iterator over the values.
+    pub fn iter(&self) -> ListIter<'a> {
+        ListRef::new(self.row).iter()
+    }
+
+    /// Returns owned copy of the values.
+    pub fn to_values(&self) -> Vec<Value> {
+        ListRef::new(self.row).to_vec()
+    }
+}
+
+impl fmt::Debug for RecordRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RecordRef")
+            .field("columns", &self.columns)
+            .field("values", &ListRef::new(self.row))
+            .finish()
+    }
+}
+
+/// Value borrowed from a received record.
+///
+/// Strings, lists and maps are borrowed. Values of other types, which are small or rarely
+/// dominate the results, are converted into owned `Value`s when they are accessed. As with
+/// `Connection::fetchone`, values which can't be converted are replaced by `Value::Null`.
+#[derive(Debug, Clone)]
+pub enum ValueRef<'a> {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(&'a str),
+    List(ListRef<'a>),
+    Map(MapRef<'a>),
+    /// Temporal values and graph elements.
+    Other(Value),
+}
+
+impl<'a> ValueRef<'a> {
+    unsafe fn new(mg_value: *const bindings::mg_value) -> ValueRef<'a> {
+        match bindings::mg_value_get_type(mg_value) {
+            bindings::mg_value_type_MG_VALUE_TYPE_NULL => ValueRef::Null,
+            bindings::mg_value_type_MG_VALUE_TYPE_BOOL => ValueRef::Bool(mg_value_bool(mg_value)),
+            bindings::mg_value_type_MG_VALUE_TYPE_INTEGER => ValueRef::Int(mg_value_int(mg_value)),
+            bindings::mg_value_type_MG_VALUE_TYPE_FLOAT => {
+                ValueRef::Float(mg_value_float(mg_value))
+            }
+            bindings::mg_value_type_MG_VALUE_TYPE_STRING => {
+                ValueRef::String(mg_string_as_str(bindings::mg_value_string(mg_value)))
+            }
+            bindings::mg_value_type_MG_VALUE_TYPE_LIST => {
+                ValueRef::List(ListRef::new(bindings::mg_value_list(mg_value)))
+            }
+            bindings::mg_value_type_MG_VALUE_TYPE_MAP => {
+                ValueRef::Map(MapRef::new(bindings::mg_value_map(mg_value)))
+            }
+            _ => ValueRef::Other(Value::from_mg_value(mg_value)),
+        }
+    }
+
+    /// Returns owned copy of the value.
+    pub fn to_value(&self) -> Value {
+        match self {
+            ValueRef::Null => Value::Null,
+            ValueRef::Bool(x) => Value::Bool(*x),
+            ValueRef::Int(x) => Value::Int(*x),
+            ValueRef::Float(x) => Value::Float(*x),
+            ValueRef::String(x) => Value::String(x.to_string()),
+            ValueRef::List(x) => Value::List(x.to_vec()),
+            ValueRef::Map(x) => Value::Map(x.to_hash_map()),
+            ValueRef::Other(x) => x.clone(),
+        }
+    }
+}
+
+/// List borrowed from a received record.
+#[derive(Clone, Copy)]
+pub struct ListRef<'a> {
+    mg_list: *const bindings::mg_list,
+    _marker: PhantomData<&'a ()>,
+}
+
+impl<'a> ListRef<'a> {
+    fn new(mg_list: *const bindings::mg_list) -> Self {
+        ListRef {
+            mg_list,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of elements.
+    pub fn len(&self) -> usize {
+        unsafe { bindings::mg_list_size(self.mg_list) as usize }
+    }
+
+    /// Returns `true` if the list has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns element at `index` or `None` if it's out of bounds.
+    pub fn get(&self, index: usize) -> Option<ValueRef<'a>> {
+        if index >= self.len() {
+            return None;
+        }
+        unsafe {
+            Some(ValueRef::new(bindings::mg_list_at(
+                self.mg_list,
+                index as u32,
+            )))
+        }
+    }
+
+    /// Returns iterator over the elements.
+    pub fn iter(&self) -> ListIter<'a> {
+        ListIter {
+            list: *self,
+            index: 0,
+        }
+    }
+
+    /// Returns owned copy of the elements.
+    pub fn to_vec(&self) -> Vec<Value> {
+        unsafe { convert_mg_list(self.mg_list, false, &mut KeyInterner::default()) }
+            .unwrap_or_default()
+    }
+}
+
+impl fmt::Debug for ListRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Iterator over elements of a `ListRef` or values of a `RecordRef`.
+pub struct ListIter<'a> {
+    list: ListRef<'a>,
+    index: usize,
+}
+
+impl<'a> Iterator for ListIter<'a> {
+    type Item = ValueRef<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.list.get(self.index)?;
+        self.index += 1;
+        Some(value)
+    }
+}
+
+/// Map borrowed from a received record.
+#[derive(Clone, Copy)]
+pub struct MapRef<'a> {
+    mg_map: *const bindings::mg_map,
+    _marker: PhantomData<&'a ()>,
+}
+
+impl<'a> MapRef<'a> {
+    fn new(mg_map: *const bindings::mg_map) -> Self {
+        MapRef {
+            mg_map,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        unsafe { bindings::mg_map_size(self.mg_map) as usize }
+    }
+
+    /// Returns `true` if the map has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns value of `key` or `None` if the map doesn't contain it.
+    pub fn get(&self, key: &str) -> Option<ValueRef<'a>> {
+        self.iter().find(|(x, _)| *x == key).map(|(_, value)| value)
+    }
+
+    /// Returns iterator over the entries in the order they were received.
+    pub fn iter(&self) -> MapIter<'a> {
+        MapIter {
+            map: *self,
+            index: 0,
+        }
+    }
+
+    /// Returns owned copy of the entries.
+    pub fn to_hash_map(&self) -> HashMap<String, Value> {
+        convert_mg_map(self.mg_map, false, &mut KeyInterner::default(), keep_key)
+            .unwrap_or_default()
+    }
+}
+
+impl fmt::Debug for MapRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// Iterator over entries of a `MapRef`.
+pub struct MapIter<'a> {
+    map: MapRef<'a>,
+    index: usize,
+}
+
+impl<'a> Iterator for MapIter<'a> {
+    type Item = (&'a str, ValueRef<'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.map.len() {
+            return None;
+        }
+        let index = self.index as u32;
+        self.index += 1;
+        unsafe {
+            Some((
+                mg_string_as_str(bindings::mg_map_key_at(self.map.mg_map, index)),
+                ValueRef::new(bindings::mg_map_value_at(self.map.mg_map, index)),
+            ))
+        }
+    }
+}
@@ -0,0 +1,80 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversions of `rust_decimal::Decimal`, enabled by the `rust_decimal` feature.
+//!
+//! Memgraph has no decimal type. Decimals are passed as floats when that is lossless, otherwise
+//! they have to be passed as strings, e.g. `QueryParam::from(decimal.to_string())`.
+
+use super::{QueryParam, Value};
+use crate::error::MgError;
+use rust_decimal::Decimal;
+use std::convert::TryFrom;
+
+/// Returns the shortest decimal representation of the float, `None` if it has too many digits.
+fn float_to_decimal(x: f64) -> Option<Decimal> {
+    if !x.is_finite() {
+        return None;
+    }
+    Decimal::from_str_exact(&x.to_string()).ok()
+}
+
+/// Converts to a float.
+///
+/// Returns error if the float doesn't represent the decimal exactly, i.e. if the shortest decimal
+/// representation of the float differs from the decimal, e.g. for `0.12345678901234567890`.
+impl TryFrom<Decimal> for QueryParam {
+    type Error = MgError;
+
+    fn try_from(x: Decimal) -> Result<Self, Self::Error> {
+        // Decimal representation is always a valid float.
+        let float: f64 = x.to_string().parse().unwrap_or(f64::NAN);
+        match float_to_decimal(float) {
+            Some(y) if y == x => Ok(QueryParam::Float(float)),
+            _ => Err(MgError::new(format!(
+                "Decimal {} can't be represented exactly as Float, pass it as a string instead",
+                x
+            ))),
+        }
+    }
+}
+
+/// Converts `Value::Int`, `Value::Float` and `Value::String` to decimal.
+///
+/// Floats are converted to their shortest decimal representation, e.g. `0.1`. Returns error if
+/// the value has more digits than fit into a decimal, instead of rounding it.
+impl TryFrom<Value> for Decimal {
+    type Error = MgError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let decimal = match &value {
+            Value::Int(x) => Some(Decimal::from(*x)),
+            Value::Float(x) => float_to_decimal(*x),
+            Value::String(x) => Decimal::from_str_exact(x).ok(),
+            x => {
+                return Err(MgError::new(format!(
+                    "Can't convert {} value to Decimal",
+                    x.type_name()
+                )))
+            }
+        };
+        decimal.ok_or_else(|| {
+            MgError::new(format!(
+                "Can't convert {} value {} to Decimal, it is out of range",
+                value.type_name(),
+                value
+            ))
+        })
+    }
+}
@@ -0,0 +1,231 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Configurable formatting of values, see `Value::display_with`.
+
+use super::{mg_map_to_string, write_node, write_path, write_relationship, Value, NSEC_IN_SEC};
+use chrono::{FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone, Timelike};
+use std::fmt::{self, Display, Formatter, Write};
+
+/// Format of dates, times and date times, see `DisplayOptions`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemporalFormat {
+    /// Format of `chrono`'s `Display` implementations, e.g. `2021-07-14 18:30:05 +02:00`.
+    Default,
+    /// ISO 8601 extended format, e.g. `2021-07-14T18:30:05+02:00`. Offsets of zero are written
+    /// as `+00:00`.
+    Iso8601,
+    /// RFC 3339 format, the same as `Iso8601` except that offsets of zero are written as `Z`.
+    /// Local times and date times are written without an offset, since they have none.
+    Rfc3339,
+    /// `chrono` strftime formats, e.g. `%d.%m.%Y`. The `date` format is used for dates, `time`
+    /// for local times and times with an offset and `date_time` for local date times and date
+    /// times with an offset. Values for which the format is invalid, e.g. local times formatted
+    /// using `%z`, are written in the default format.
+    Custom {
+        date: String,
+        time: String,
+        date_time: String,
+    },
+}
+
+/// Options of `Value::display_with`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisplayOptions {
+    /// Defaults to `TemporalFormat::Default`.
+    pub temporal_format: TemporalFormat,
+    /// Number of digits of fractions of seconds in the ISO 8601 and RFC 3339 formats, at most 9.
+    /// Fractions are truncated or padded with zeros. By default, 3, 6 or 9 digits are written,
+    /// as many as needed to represent the value without loss, and none for whole seconds.
+    pub subsecond_digits: Option<u8>,
+}
+
+impl Default for DisplayOptions {
+    fn default() -> Self {
+        DisplayOptions {
+            temporal_format: TemporalFormat::Default,
+            subsecond_digits: None,
+        }
+    }
+}
+
+/// Value formatted using `DisplayOptions`, returned by `Value::display_with`.
+pub struct ValueDisplay<'a> {
+    value: &'a Value,
+    options: &'a DisplayOptions,
+}
+
+impl Value {
+    /// Returns the value formatted according to `options`, e.g. for writing temporal values in
+    /// RFC 3339 format. Values are formatted the same way as by `Display` otherwise, and
+    /// `Display` of `Value` uses the default options.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use rsmgclient::{DisplayOptions, TemporalFormat, Value};
+    ///
+    /// let value = Value::LocalDateTime(NaiveDate::from_ymd(2021, 7, 14).and_hms_milli(18, 30, 5, 500));
+    /// let options = DisplayOptions {
+    ///     temporal_format: TemporalFormat::Iso8601,
+    ///     subsecond_digits: Some(1),
+    /// };
+    /// assert_eq!(value.display_with(&options).to_string(), "'2021-07-14T18:30:05.5'");
+    /// ```
+    pub fn display_with<'a>(&'a self, options: &'a DisplayOptions) -> ValueDisplay<'a> {
+        ValueDisplay {
+            value: self,
+            options,
+        }
+    }
+}
+
+/// Returns fraction of a second, starting with `.`, for `nanoseconds` below a second.
+fn fraction(nanoseconds: u32, digits: Option<u8>) -> String {
+    let digits = match digits {
+        Some(x) => usize::from(x.min(9)),
+        None if nanoseconds == 0 => 0,
+        None if nanoseconds.is_multiple_of(1_000_000) => 3,
+        None if nanoseconds.is_multiple_of(1_000) => 6,
+        None => 9,
+    };
+    match digits {
+        0 => String::new(),
+        _ => format!(".{}", &format!("{:09}", nanoseconds)[..digits]),
+    }
+}
+
+fn iso_time(time: &NaiveTime, digits: Option<u8>) -> String {
+    // Leap seconds are represented by chrono as nanoseconds exceeding a second.
+    let (second, nanoseconds) = match time.nanosecond() {
+        x if i64::from(x) >= NSEC_IN_SEC => (time.second() + 1, x - NSEC_IN_SEC as u32),
+        x => (time.second(), x),
+    };
+    format!(
+        "{:02}:{:02}:{:02}{}",
+        time.hour(),
+        time.minute(),
+        second,
+        fraction(nanoseconds, digits)
+    )
+}
+
+fn iso_date(date: &NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+fn iso_date_time(date_time: &NaiveDateTime, digits: Option<u8>) -> String {
+    format!(
+        "{}T{}",
+        iso_date(&date_time.date()),
+        iso_time(&date_time.time(), digits)
+    )
+}
+
+fn iso_offset(offset: &FixedOffset, format: &TemporalFormat) -> String {
+    let seconds = offset.local_minus_utc();
+    if seconds == 0 && *format == TemporalFormat::Rfc3339 {
+        return String::from("Z");
+    }
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let minutes = seconds.abs() / 60;
+    format!("{}{:02}:{:02}", sign, minutes / 60, minutes % 60)
+}
+
+/// Formats `value` using a strftime `format`, `None` if the format is invalid for it.
+fn strftime<T: Display>(value: impl Fn() -> T) -> Option<String> {
+    let mut formatted = String::new();
+    write!(formatted, "{}", value()).ok()?;
+    Some(formatted)
+}
+
+impl ValueDisplay<'_> {
+    /// Returns the temporal value formatted according to the options, `None` for other values
+    /// and the default format.
+    fn temporal(&self) -> Option<String> {
+        let format = &self.options.temporal_format;
+        let digits = self.options.subsecond_digits;
+        match (format, self.value) {
+            (TemporalFormat::Default, _) => None,
+            (TemporalFormat::Custom { date, .. }, Value::Date(x)) => strftime(|| x.format(date)),
+            (TemporalFormat::Custom { time, .. }, Value::LocalTime(x)) => {
+                strftime(|| x.format(time))
+            }
+            (TemporalFormat::Custom { time, .. }, Value::Time(x, offset)) => {
+                // A date is needed for formatting the offset, date specifiers are meaningless.
+                let epoch = NaiveDate::from_ymd_opt(1970, 1, 1)?.and_time(*x);
+                let value = offset.from_local_datetime(&epoch).single()?;
+                strftime(|| value.format(time))
+            }
+            (TemporalFormat::Custom { date_time, .. }, Value::LocalDateTime(x)) => {
+                strftime(|| x.format(date_time))
+            }
+            (TemporalFormat::Custom { date_time, .. }, Value::DateTime(x)) => {
+                strftime(|| x.format(date_time))
+            }
+            (TemporalFormat::Custom { .. }, _) => None,
+            (_, Value::Date(x)) => Some(iso_date(x)),
+            (_, Value::LocalTime(x)) => Some(iso_time(x, digits)),
+            (_, Value::Time(x, offset)) => Some(iso_time(x, digits) + &iso_offset(offset, format)),
+            (_, Value::LocalDateTime(x)) => Some(iso_date_time(x, digits)),
+            (_, Value::DateTime(x)) => Some(
+                iso_date_time(&x.naive_local(), digits) + &iso_offset(&x.offset().fix(), format),
+            ),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ValueDisplay<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(x) = self.temporal() {
+            return write!(f, "'{}'", x);
+        }
+        match self.value {
+            Value::Null => write!(f, "NULL"),
+            Value::Bool(x) => write!(f, "{}", x),
+            Value::Int(x) => write!(f, "{}", x),
+            Value::Float(x) => write!(f, "{}", x),
+            Value::String(x) => write!(f, "'{}'", x),
+            Value::Date(x) => write!(f, "'{}'", x),
+            Value::Time(time, offset) => write!(f, "'{}{}'", time, offset),
+            Value::LocalTime(x) => write!(f, "'{}'", x),
+            Value::LocalDateTime(x) => write!(f, "'{}'", x),
+            Value::DateTime(x) => write!(f, "'{}'", x),
+            Value::Duration(x) => write!(f, "'{}'", x),
+            Value::List(x) => write!(
+                f,
+                "{}",
+                x.iter()
+                    .map(|val| val.display_with(self.options).to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            Value::Map(x) => write!(f, "{}", mg_map_to_string(x, self.options)),
+            Value::Node(x) => write_node(f, x, self.options),
+            Value::Relationship(x) => write_relationship(f, &x.type_, &x.properties, self.options),
+            Value::UnboundRelationship(x) => {
+                write_relationship(f, &x.type_, &x.properties, self.options)
+            }
+            Value::Path(x) => write_path(f, x, self.options),
+        }
+    }
+}
+
+impl fmt::Debug for ValueDisplay<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
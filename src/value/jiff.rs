@@ -0,0 +1,180 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversions of `jiff` crate types, enabled by the `jiff` feature.
+//!
+//! `QueryParam`s are created using `From`, e.g. `QueryParam::from(jiff::Zoned)`, and values are
+//! converted using `TryFrom`, e.g. `jiff::civil::Date::try_from(value)`. Conversions of values
+//! fail if they are out of range of the `jiff` types, e.g. years beyond 9999 or durations with
+//! months.
+//!
+//! Memgraph date times have a fixed offset, so `Zoned` parameters are sent with the offset of
+//! their time zone at that instant and `Zoned` values have a fixed offset time zone. Times with
+//! an offset and `Zoned` are converted to `QueryParam` using `TryFrom`, which fails for offsets
+//! of 24 hours or more, out of range of `chrono`.
+
+use super::{MgDuration, QueryParam, Value};
+use crate::error::MgError;
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+use chrono::{Offset as _, Timelike};
+use jiff::civil::{self, Date, Time};
+use jiff::tz::{self, Offset};
+use jiff::{SignedDuration, Timestamp, Zoned};
+use std::convert::TryFrom;
+
+fn from_date(x: Date) -> NaiveDate {
+    NaiveDate::from_ymd_opt(i32::from(x.year()), x.month() as u32, x.day() as u32)
+        .expect("date out of range of chrono")
+}
+
+fn from_time(x: Time) -> NaiveTime {
+    NaiveTime::from_hms_nano_opt(
+        x.hour() as u32,
+        x.minute() as u32,
+        x.second() as u32,
+        x.subsec_nanosecond() as u32,
+    )
+    .expect("time out of range of chrono")
+}
+
+fn from_offset(x: Offset) -> Result<FixedOffset, MgError> {
+    FixedOffset::east_opt(x.seconds()).ok_or_else(|| {
+        MgError::new(format!(
+            "Can't convert offset {} to QueryParam, it is out of range",
+            x
+        ))
+    })
+}
+
+fn from_date_time(x: civil::DateTime) -> NaiveDateTime {
+    NaiveDateTime::new(from_date(x.date()), from_time(x.time()))
+}
+
+fn from_zoned(x: &Zoned) -> Result<DateTime<FixedOffset>, MgError> {
+    Ok(from_offset(x.offset())?
+        .from_local_datetime(&from_date_time(x.datetime()))
+        .single()
+        .expect("date time out of range of chrono"))
+}
+
+fn to_date(x: &NaiveDate) -> Option<Date> {
+    Date::new(
+        i16::try_from(x.year()).ok()?,
+        x.month() as i8,
+        x.day() as i8,
+    )
+    .ok()
+}
+
+fn to_time(x: &NaiveTime) -> Option<Time> {
+    Time::new(
+        x.hour() as i8,
+        x.minute() as i8,
+        x.second() as i8,
+        i32::try_from(x.nanosecond()).ok()?,
+    )
+    .ok()
+}
+
+fn to_offset(x: &FixedOffset) -> Option<Offset> {
+    Offset::from_seconds(x.local_minus_utc()).ok()
+}
+
+fn to_date_time(x: &NaiveDateTime) -> Option<civil::DateTime> {
+    Some(civil::DateTime::from_parts(
+        to_date(&x.date())?,
+        to_time(&x.time())?,
+    ))
+}
+
+fn to_zoned(x: &DateTime<FixedOffset>) -> Option<Zoned> {
+    let offset = to_offset(&x.offset().fix())?;
+    let timestamp = offset.to_timestamp(to_date_time(&x.naive_local())?).ok()?;
+    Some(timestamp.to_zoned(tz::TimeZone::fixed(offset)))
+}
+
+fn to_duration(x: &MgDuration) -> Option<SignedDuration> {
+    let (seconds, nanoseconds) = x.split_seconds()?;
+    SignedDuration::from_secs(seconds).checked_add(SignedDuration::from_nanos(nanoseconds))
+}
+
+impl From<Date> for QueryParam {
+    fn from(x: Date) -> Self {
+        QueryParam::Date(from_date(x))
+    }
+}
+
+impl From<Time> for QueryParam {
+    fn from(x: Time) -> Self {
+        QueryParam::LocalTime(from_time(x))
+    }
+}
+
+impl TryFrom<(Time, Offset)> for QueryParam {
+    type Error = MgError;
+
+    fn try_from(x: (Time, Offset)) -> Result<Self, Self::Error> {
+        Ok(QueryParam::Time(from_time(x.0), from_offset(x.1)?))
+    }
+}
+
+impl From<civil::DateTime> for QueryParam {
+    fn from(x: civil::DateTime) -> Self {
+        QueryParam::LocalDateTime(from_date_time(x))
+    }
+}
+
+impl TryFrom<Zoned> for QueryParam {
+    type Error = MgError;
+
+    fn try_from(x: Zoned) -> Result<Self, Self::Error> {
+        QueryParam::try_from(&x)
+    }
+}
+
+impl TryFrom<&Zoned> for QueryParam {
+    type Error = MgError;
+
+    fn try_from(x: &Zoned) -> Result<Self, Self::Error> {
+        Ok(QueryParam::DateTime(from_zoned(x)?))
+    }
+}
+
+/// Converts to a date time in UTC.
+impl From<Timestamp> for QueryParam {
+    fn from(x: Timestamp) -> Self {
+        let utc = from_zoned(&x.to_zoned(tz::TimeZone::UTC));
+        QueryParam::DateTime(utc.expect("UTC is in range of chrono"))
+    }
+}
+
+impl From<SignedDuration> for QueryParam {
+    fn from(x: SignedDuration) -> Self {
+        QueryParam::Duration(MgDuration::from_seconds(
+            x.as_secs(),
+            i64::from(x.subsec_nanos()),
+        ))
+    }
+}
+
+impl_try_from_value_checked! {
+    Date, "jiff::civil::Date": Value::Date(x) => to_date(x);
+    Time, "jiff::civil::Time": Value::LocalTime(x) => to_time(x);
+    (Time, Offset), "(jiff::civil::Time, jiff::tz::Offset)":
+        Value::Time(time, offset) => to_time(time).zip(to_offset(offset));
+    civil::DateTime, "jiff::civil::DateTime": Value::LocalDateTime(x) => to_date_time(x);
+    Zoned, "jiff::Zoned": Value::DateTime(x) => to_zoned(x);
+    Timestamp, "jiff::Timestamp": Value::DateTime(x) => to_zoned(x).map(|x| x.timestamp());
+    SignedDuration, "jiff::SignedDuration": Value::Duration(x) => to_duration(x);
+}
@@ -13,17 +13,24 @@
 // limitations under the License.
 
 use super::bindings;
-use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
-use std::collections::HashMap;
+use super::error::MgError;
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Offset,
+    TimeZone, Timelike,
+};
+use std::collections::{HashMap, HashSet};
 use std::convert::TryFrom;
 use std::ffi::{CStr, CString};
 use std::fmt;
 use std::fmt::Formatter;
-use std::num::TryFromIntError;
+use std::hash::Hash;
+use std::ops::Index;
 use std::os::raw::c_char;
 use std::slice;
+use std::sync::Arc;
 
 /// Representation of parameter value used in query.
+#[derive(Debug, Clone)]
 pub enum QueryParam {
     Null,
     Bool(bool),
@@ -31,9 +38,15 @@ pub enum QueryParam {
     Float(f64),
     String(String),
     Date(NaiveDate),
+    /// Time of day with offset from UTC.
+    Time(NaiveTime, FixedOffset),
     LocalTime(NaiveTime),
     LocalDateTime(NaiveDateTime),
-    Duration(Duration),
+    /// Date and time with offset from UTC. Bolt carries the offset in whole minutes, seconds of
+    /// an offset such as a historical local mean time are dropped, which moves the sent instant
+    /// by them.
+    DateTime(DateTime<FixedOffset>),
+    Duration(MgDuration),
     List(Vec<QueryParam>),
     Map(HashMap<String, QueryParam>),
 }
@@ -51,12 +64,18 @@ impl QueryParam {
                 QueryParam::Float(x) => bindings::mg_value_make_float(*x),
                 QueryParam::String(x) => bindings::mg_value_make_string(str_to_c_str(x.as_str())),
                 QueryParam::Date(x) => bindings::mg_value_make_date(naive_date_to_mg_date(x)),
+                QueryParam::Time(time, offset) => {
+                    bindings::mg_value_make_time(naive_time_to_mg_time(time, offset))
+                }
                 QueryParam::LocalTime(x) => {
                     bindings::mg_value_make_local_time(naive_local_time_to_mg_local_time(x))
                 }
                 QueryParam::LocalDateTime(x) => bindings::mg_value_make_local_date_time(
                     naive_local_date_time_to_mg_local_date_time(x),
                 ),
+                QueryParam::DateTime(x) => {
+                    bindings::mg_value_make_date_time(date_time_to_mg_date_time(x))
+                }
                 QueryParam::Duration(x) => {
                     bindings::mg_value_make_duration(duration_to_mg_duration(x))
                 }
@@ -67,6 +86,490 @@ impl QueryParam {
     }
 }
 
+macro_rules! impl_from_for_query_param {
+    ($($from:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl From<$from> for QueryParam {
+                fn from(x: $from) -> Self {
+                    QueryParam::$variant(x.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_for_query_param! {
+    bool => Bool,
+    i8 => Int,
+    i16 => Int,
+    i32 => Int,
+    i64 => Int,
+    u8 => Int,
+    u16 => Int,
+    u32 => Int,
+    f32 => Float,
+    f64 => Float,
+    String => String,
+    &str => String,
+    NaiveDate => Date,
+    NaiveTime => LocalTime,
+    NaiveDateTime => LocalDateTime,
+    Duration => Duration,
+    MgDuration => Duration,
+}
+
+impl From<(NaiveTime, FixedOffset)> for QueryParam {
+    fn from(x: (NaiveTime, FixedOffset)) -> Self {
+        QueryParam::Time(x.0, x.1)
+    }
+}
+
+impl<Tz: TimeZone> From<DateTime<Tz>> for QueryParam {
+    fn from(x: DateTime<Tz>) -> Self {
+        let offset = x.offset().fix();
+        QueryParam::DateTime(x.with_timezone(&offset))
+    }
+}
+
+impl From<&String> for QueryParam {
+    fn from(x: &String) -> Self {
+        QueryParam::String(x.clone())
+    }
+}
+
+impl<T: Into<QueryParam>> From<Option<T>> for QueryParam {
+    fn from(x: Option<T>) -> Self {
+        match x {
+            Some(x) => x.into(),
+            None => QueryParam::Null,
+        }
+    }
+}
+
+impl<T: Into<QueryParam>> From<Vec<T>> for QueryParam {
+    fn from(x: Vec<T>) -> Self {
+        QueryParam::List(x.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<K: Into<String>, V: Into<QueryParam>> From<HashMap<K, V>> for QueryParam {
+    fn from(x: HashMap<K, V>) -> Self {
+        QueryParam::Map(x.into_iter().map(|(k, v)| (k.into(), v.into())).collect())
+    }
+}
+
+/// Creates a `HashMap<String, QueryParam>` of query parameters.
+///
+/// Keys can be anything convertible into `String` and values anything convertible into
+/// `QueryParam`.
+///
+/// # Examples
+///
+/// ```
+/// use rsmgclient::params;
+///
+/// let params = params! {
+///     "name" => "Alice",
+///     "age" => 32,
+///     "languages" => vec!["en", "de"],
+///     "address" => params! { "city" => "Zagreb" },
+/// };
+/// assert_eq!(params.len(), 4);
+/// ```
+#[macro_export]
+macro_rules! params {
+    () => {
+        ::std::collections::HashMap::<::std::string::String, $crate::QueryParam>::new()
+    };
+    ($($key:expr => $value:expr),+ $(,)?) => {{
+        let mut params =
+            ::std::collections::HashMap::<::std::string::String, $crate::QueryParam>::new();
+        $(
+            params.insert(
+                ::std::string::String::from($key),
+                $crate::QueryParam::from($value),
+            );
+        )+
+        params
+    }};
+}
+
+/// Limits checked on query parameters before they are sent to the server.
+///
+/// The server does not advertise its limits, parameters violating these limits are rejected by
+/// the client with an error naming the offending parameter instead of failing on the server.
+/// Default limits correspond to the maximum sizes which can be encoded by Bolt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParamLimits {
+    /// Maximum nesting depth of lists and maps. A list of scalars has depth 1, a list of lists
+    /// depth 2, etc. Depth is not limited if `None`.
+    pub max_depth: Option<usize>,
+    /// Maximum number of elements of a single list or map.
+    pub max_collection_size: usize,
+    /// Maximum length of a single string in bytes.
+    pub max_string_length: usize,
+}
+
+impl Default for ParamLimits {
+    fn default() -> Self {
+        ParamLimits {
+            max_depth: None,
+            max_collection_size: u32::MAX as usize,
+            max_string_length: u32::MAX as usize,
+        }
+    }
+}
+
+impl ParamLimits {
+    /// Checks all parameters against the limits.
+    ///
+    /// Returns error describing the first parameter exceeding the limits. Elements of lists and
+    /// maps are only checked if a limit is stricter than the default, larger values can't be
+    /// encoded by Bolt and are rejected when the query is sent.
+    pub fn check(&self, params: &HashMap<String, QueryParam>) -> Result<(), MgError> {
+        if params.len() > self.max_collection_size {
+            return Err(MgError::new(format!(
+                "Number of parameters {} exceeds maximum of {}",
+                params.len(),
+                self.max_collection_size
+            )));
+        }
+        if self.max_depth.is_none()
+            && self.max_collection_size >= u32::MAX as usize
+            && self.max_string_length >= u32::MAX as usize
+        {
+            return Ok(());
+        }
+        for (key, value) in params {
+            self.check_value(&ParamPath::Param(key), value, 0)?;
+        }
+        Ok(())
+    }
+
+    fn check_value(
+        &self,
+        path: &ParamPath<'_>,
+        value: &QueryParam,
+        depth: usize,
+    ) -> Result<(), MgError> {
+        let size = match value {
+            QueryParam::String(x) => {
+                if x.len() > self.max_string_length {
+                    return Err(MgError::new(format!(
+                        "Parameter {} has length {} which exceeds maximum string length of {}",
+                        path,
+                        x.len(),
+                        self.max_string_length
+                    )));
+                }
+                return Ok(());
+            }
+            QueryParam::List(x) => x.len(),
+            QueryParam::Map(x) => x.len(),
+            _ => return Ok(()),
+        };
+
+        let depth = depth + 1;
+        if let Some(max_depth) = self.max_depth {
+            if depth > max_depth {
+                return Err(MgError::new(format!(
+                    "Parameter {} exceeds maximum nesting depth of {}",
+                    path, max_depth
+                )));
+            }
+        }
+        if size > self.max_collection_size {
+            return Err(MgError::new(format!(
+                "Parameter {} has {} elements which exceeds maximum of {}",
+                path, size, self.max_collection_size
+            )));
+        }
+
+        match value {
+            QueryParam::List(x) => {
+                for (i, element) in x.iter().enumerate() {
+                    self.check_value(&ParamPath::Index(path, i), element, depth)?;
+                }
+            }
+            QueryParam::Map(x) => {
+                for (key, element) in x {
+                    self.check_value(&ParamPath::Key(path, key), element, depth)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// Path to a value nested in a parameter, formatted only when it is reported in an error.
+enum ParamPath<'a> {
+    Param(&'a str),
+    Index(&'a ParamPath<'a>, usize),
+    Key(&'a ParamPath<'a>, &'a str),
+}
+
+impl fmt::Display for ParamPath<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamPath::Param(x) => write!(f, "${}", x),
+            ParamPath::Index(parent, i) => write!(f, "{}[{}]", parent, i),
+            ParamPath::Key(parent, key) => write!(f, "{}.{}", parent, key),
+        }
+    }
+}
+
+/// Builder of a list of maps with consistent keys, meant to be passed as a parameter of an
+/// `UNWIND` query.
+///
+/// Rows are validated when they are added. Every row must have the same keys as the first row
+/// and values of a key must have the same type in all rows, although any of them can be null.
+/// A malformed row is therefore rejected with an error naming the row and the key instead of
+/// silently producing nulls for the whole batch on the server.
+///
+/// # Examples
+///
+/// ```
+/// use rsmgclient::{params, BatchBuilder, MgError};
+/// # fn build() -> Result<(), MgError> {
+///
+/// let mut batch = BatchBuilder::new();
+/// batch.add_row(params! { "id" => 1, "name" => "Alice" })?;
+/// batch.add_row(params! { "id" => 2, "name" => None::<String> })?;
+/// assert!(batch.add_row(params! { "id" => "3", "name" => "Carol" }).is_err());
+/// assert!(batch.add_row(params! { "id" => 4 }).is_err());
+///
+/// let params = params! { "batch" => batch.build() };
+/// // connection.execute(
+/// //     "UNWIND $batch AS row CREATE (:Person {id: row.id, name: row.name})",
+/// //     Some(&params),
+/// // )?;
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BatchBuilder {
+    /// Type of values of every key, `None` if all values so far were null.
+    types: HashMap<String, Option<&'static str>>,
+    rows: Vec<QueryParam>,
+}
+
+impl BatchBuilder {
+    pub fn new() -> BatchBuilder {
+        BatchBuilder::default()
+    }
+
+    /// Adds a row to the batch.
+    ///
+    /// Returns error if the keys of the row or the types of its values don't match the rows
+    /// added before, in which case the row is not added.
+    pub fn add_row(&mut self, row: HashMap<String, QueryParam>) -> Result<(), MgError> {
+        let index = self.rows.len();
+        if index == 0 {
+            self.types = row
+                .iter()
+                .map(|(key, value)| (key.clone(), query_param_type(value)))
+                .collect();
+            self.rows.push(QueryParam::Map(row));
+            return Ok(());
+        }
+
+        if let Some(key) = self.types.keys().find(|key| !row.contains_key(*key)) {
+            return Err(MgError::new(format!(
+                "Row {} is missing key `{}`",
+                index, key
+            )));
+        }
+        let mut new_types = Vec::new();
+        for (key, value) in &row {
+            let type_ = query_param_type(value);
+            match (self.types.get(key), type_) {
+                (None, _) => {
+                    return Err(MgError::new(format!(
+                        "Row {} has unexpected key `{}`",
+                        index, key
+                    )))
+                }
+                (Some(Some(expected)), Some(actual)) if *expected != actual => {
+                    return Err(MgError::new(format!(
+                        "Row {} has value of type {} for key `{}`, expected {}",
+                        index, actual, key, expected
+                    )))
+                }
+                (Some(None), Some(_)) => new_types.push((key.clone(), type_)),
+                _ => {}
+            }
+        }
+        self.types.extend(new_types);
+        self.rows.push(QueryParam::Map(row));
+        Ok(())
+    }
+
+    /// Returns the number of rows in the batch.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns whether the batch has no rows.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Returns the batch as `QueryParam::List` of `QueryParam::Map` rows.
+    pub fn build(self) -> QueryParam {
+        QueryParam::List(self.rows)
+    }
+}
+
+/// Returns name of the parameter type, `None` for null.
+fn query_param_type(value: &QueryParam) -> Option<&'static str> {
+    let type_ = match value {
+        QueryParam::Null => return None,
+        QueryParam::Bool(_) => "Bool",
+        QueryParam::Int(_) => "Int",
+        QueryParam::Float(_) => "Float",
+        QueryParam::String(_) => "String",
+        QueryParam::Date(_) => "Date",
+        QueryParam::Time(_, _) => "Time",
+        QueryParam::LocalTime(_) => "LocalTime",
+        QueryParam::LocalDateTime(_) => "LocalDateTime",
+        QueryParam::DateTime(_) => "DateTime",
+        QueryParam::Duration(_) => "Duration",
+        QueryParam::List(_) => "List",
+        QueryParam::Map(_) => "Map",
+    };
+    Some(type_)
+}
+
+/// Representation of Bolt duration value.
+///
+/// Unlike `chrono::Duration`, months and days are kept separately from seconds since their
+/// length depends on the date the duration is applied to.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Hash)]
+pub struct MgDuration {
+    pub months: i64,
+    pub days: i64,
+    pub seconds: i64,
+    pub nanoseconds: i64,
+}
+
+impl MgDuration {
+    pub fn new(months: i64, days: i64, seconds: i64, nanoseconds: i64) -> MgDuration {
+        MgDuration {
+            months,
+            days,
+            seconds,
+            nanoseconds,
+        }
+    }
+
+    /// Converts to `chrono::Duration` counting a day as 24 hours.
+    ///
+    /// Returns `None` if the duration has months, which don't have a fixed length, or if it is
+    /// out of range of `chrono::Duration`.
+    pub fn to_chrono(&self) -> Option<Duration> {
+        let (seconds, nanoseconds) = self.split_seconds()?;
+        seconds.checked_mul(1000)?;
+        Duration::seconds(seconds).checked_add(&Duration::nanoseconds(nanoseconds))
+    }
+
+    /// Returns whole seconds, counting a day as 24 hours, and the remaining nanoseconds, which
+    /// are never negative. `None` if the duration has months or doesn't fit.
+    pub(crate) fn split_seconds(&self) -> Option<(i64, i64)> {
+        if self.months != 0 {
+            return None;
+        }
+        let nanoseconds = i128::from(self.days) * i128::from(days_as_seconds(1) * NSEC_IN_SEC)
+            + i128::from(self.seconds) * i128::from(NSEC_IN_SEC)
+            + i128::from(self.nanoseconds);
+        let seconds = i64::try_from(nanoseconds.div_euclid(i128::from(NSEC_IN_SEC))).ok()?;
+        let nanoseconds = nanoseconds.rem_euclid(i128::from(NSEC_IN_SEC)) as i64;
+        Some((seconds, nanoseconds))
+    }
+
+    /// Splits `seconds` into days and seconds, both with the same sign as `seconds`.
+    pub(crate) fn from_seconds(seconds: i64, nanoseconds: i64) -> MgDuration {
+        let day = days_as_seconds(1);
+        MgDuration::new(0, seconds / day, seconds % day, nanoseconds)
+    }
+}
+
+impl From<Duration> for MgDuration {
+    fn from(input: Duration) -> Self {
+        let seconds = input.num_seconds();
+        // Remainder is shorter than a second, there is no overflow.
+        let nanoseconds = (input - Duration::seconds(seconds))
+            .num_nanoseconds()
+            .unwrap();
+        MgDuration::from_seconds(seconds, nanoseconds)
+    }
+}
+
+impl fmt::Display for MgDuration {
+    /// Formats the duration in ISO 8601 format, e.g. `P1M2DT3.5S`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "P")?;
+        if self.months != 0 {
+            write!(f, "{}M", self.months)?;
+        }
+        if self.days != 0 {
+            write!(f, "{}D", self.days)?;
+        }
+        let nanoseconds =
+            i128::from(self.seconds) * i128::from(NSEC_IN_SEC) + i128::from(self.nanoseconds);
+        if nanoseconds == 0 && (self.months != 0 || self.days != 0) {
+            return Ok(());
+        }
+        let sign = if nanoseconds < 0 { "-" } else { "" };
+        let nanoseconds = nanoseconds.abs();
+        let seconds = nanoseconds / i128::from(NSEC_IN_SEC);
+        let fraction = nanoseconds % i128::from(NSEC_IN_SEC);
+        if fraction == 0 {
+            write!(f, "T{}{}S", sign, seconds)
+        } else {
+            let fraction = format!("{:09}", fraction);
+            write!(
+                f,
+                "T{}{}.{}S",
+                sign,
+                seconds,
+                fraction.trim_end_matches('0')
+            )
+        }
+    }
+}
+
+/// Properties of nodes and relationships.
+///
+/// Keys are shared by all nodes and relationships received on the same connection, so wide
+/// results repeating the same property names keep only one copy of each name. Properties can
+/// still be looked up by `&str`, e.g. `node.properties.get("name")`.
+pub type Properties = HashMap<Arc<str>, Value>;
+
+/// Maximum number of distinct property keys kept by `KeyInterner`.
+const MAX_INTERNED_KEYS: usize = 4096;
+
+/// Deduplicates property keys of received nodes and relationships.
+///
+/// Once `MAX_INTERNED_KEYS` distinct keys are kept, new keys are no longer remembered so that
+/// queries with generated property names can't grow the interner without bound.
+#[derive(Debug, Default)]
+pub(crate) struct KeyInterner {
+    keys: HashSet<Arc<str>>,
+}
+
+impl KeyInterner {
+    pub(crate) fn intern(&mut self, key: String) -> Arc<str> {
+        if let Some(x) = self.keys.get(key.as_str()) {
+            return x.clone();
+        }
+        let key: Arc<str> = Arc::from(key);
+        if self.keys.len() < MAX_INTERNED_KEYS {
+            self.keys.insert(key.clone());
+        }
+        key
+    }
+}
+
 /// Representation of node value from a labeled property graph.
 ///
 /// Consists of a unique identifier(within the scope of its origin graph), a list
@@ -78,7 +581,7 @@ pub struct Node {
     pub id: i64,
     pub label_count: u32,
     pub labels: Vec<String>,
-    pub properties: HashMap<String, Value>,
+    pub properties: Properties,
 }
 
 /// Representation of relationship value from a labeled property graph.
@@ -92,7 +595,7 @@ pub struct Relationship {
     pub start_id: i64,
     pub end_id: i64,
     pub type_: String,
-    pub properties: HashMap<String, Value>,
+    pub properties: Properties,
 }
 
 /// Representation of relationship from a labeled property graph.
@@ -103,7 +606,7 @@ pub struct Relationship {
 pub struct UnboundRelationship {
     pub id: i64,
     pub type_: String,
-    pub properties: HashMap<String, Value>,
+    pub properties: Properties,
 }
 
 /// Representation of sequence of alternating nodes and relationships corresponding
@@ -116,8 +619,23 @@ pub struct UnboundRelationship {
 pub struct Path {
     pub node_count: u32,
     pub relationship_count: u32,
+    /// Nodes in traversal order, starting with the first node of the path.
     pub nodes: Vec<Node>,
+    /// Relationships in traversal order.
     pub relationships: Vec<UnboundRelationship>,
+    /// Traversal sequence in Bolt format. Each step is encoded by two numbers: index of the
+    /// traversed relationship in `relationships` starting from 1, negative if the relationship
+    /// is traversed from its end node to its start node, followed by index of the reached node
+    /// in `nodes`.
+    pub sequence: Vec<i64>,
+}
+
+impl Path {
+    /// Returns whether the relationship at `index` in `relationships` is traversed from its end
+    /// node to its start node.
+    pub fn is_reversed(&self, index: usize) -> bool {
+        matches!(self.sequence.get(index * 2), Some(x) if *x < 0)
+    }
 }
 
 /// Representation of Bolt value returned by database.
@@ -132,9 +650,13 @@ pub enum Value {
     String(String),
     List(Vec<Value>),
     Date(NaiveDate),
+    /// Time of day with offset from UTC.
+    Time(NaiveTime, FixedOffset),
     LocalTime(NaiveTime),
     LocalDateTime(NaiveDateTime),
-    Duration(Duration),
+    /// Date and time with offset from UTC. Date times with a named time zone are not supported.
+    DateTime(DateTime<FixedOffset>),
+    Duration(MgDuration),
     Map(HashMap<String, Value>),
     Node(Node),
     Relationship(Relationship),
@@ -143,14 +665,22 @@ pub enum Value {
 }
 
 /// Representation of a single row returned by database.
+///
+/// Values can be accessed by position, `record[0]`, or by column name, `record["name"]`.
 pub struct Record {
+    /// Names of the columns, shared by all records of the same query.
+    columns: Arc<Vec<String>>,
     pub values: Vec<Value>,
 }
 
-fn mg_value_list_to_vec(mg_value: *const bindings::mg_value) -> Vec<Value> {
+fn mg_value_list_to_vec(
+    mg_value: *const bindings::mg_value,
+    strict: bool,
+    keys: &mut KeyInterner,
+) -> Result<Vec<Value>, MgError> {
     unsafe {
         let mg_list = bindings::mg_value_list(mg_value);
-        mg_list_to_vec(mg_list)
+        convert_mg_list(mg_list, strict, keys)
     }
 }
 
@@ -202,93 +732,193 @@ fn minutes_as_seconds(minutes: i64) -> i64 {
 
 const NSEC_IN_SEC: i64 = 1_000_000_000;
 
-pub(crate) fn mg_value_naive_date(mg_value: *const bindings::mg_value) -> Result<NaiveDate, ()> {
+pub(crate) fn mg_value_naive_date(
+    mg_value: *const bindings::mg_value,
+) -> Result<NaiveDate, MgError> {
     let c_date = unsafe { bindings::mg_value_date(mg_value) };
     let c_delta_days = unsafe { bindings::mg_date_days(c_date) };
-    let epoch_date = NaiveDate::from_ymd(1970, 1, 1);
-    let delta_days = Duration::days(c_delta_days);
-    Ok(epoch_date.checked_add_signed(delta_days).unwrap())
+    let epoch_days = DateTime::UNIX_EPOCH.date_naive().num_days_from_ce();
+    i32::try_from(c_delta_days)
+        .ok()
+        .and_then(|x| x.checked_add(epoch_days))
+        .and_then(NaiveDate::from_num_days_from_ce_opt)
+        .ok_or_else(|| {
+            MgError::new(format!(
+                "Date {} days from epoch is out of range",
+                c_delta_days
+            ))
+        })
+}
+
+fn nanoseconds_to_naive_time(c_nanoseconds: i64) -> Result<NaiveTime, MgError> {
+    let error = || {
+        MgError::new(format!(
+            "Time {} nanoseconds since midnight is out of range",
+            c_nanoseconds
+        ))
+    };
+    let seconds = u32::try_from(c_nanoseconds / NSEC_IN_SEC).map_err(|_| error())?;
+    let nanoseconds = u32::try_from(c_nanoseconds % NSEC_IN_SEC).map_err(|_| error())?;
+    NaiveTime::from_num_seconds_from_midnight_opt(seconds, nanoseconds).ok_or_else(error)
+}
+
+pub(crate) fn mg_value_naive_time(
+    mg_value: *const bindings::mg_value,
+) -> Result<(NaiveTime, FixedOffset), MgError> {
+    let c_time = unsafe { bindings::mg_value_time(mg_value) };
+    let c_nanoseconds = unsafe { bindings::mg_time_nanoseconds(c_time) };
+    let c_offset = unsafe { bindings::mg_time_tz_offset_seconds(c_time) };
+    let time = nanoseconds_to_naive_time(c_nanoseconds)?;
+    let offset = i32::try_from(c_offset)
+        .ok()
+        .and_then(FixedOffset::east_opt)
+        .ok_or_else(|| MgError::new(format!("Time offset {} seconds is out of range", c_offset)))?;
+    Ok((time, offset))
 }
 
 pub(crate) fn mg_value_naive_local_time(
     mg_value: *const bindings::mg_value,
-) -> Result<NaiveTime, TryFromIntError> {
+) -> Result<NaiveTime, MgError> {
     let c_local_time = unsafe { bindings::mg_value_local_time(mg_value) };
     let c_nanoseconds = unsafe { bindings::mg_local_time_nanoseconds(c_local_time) };
-    let seconds = u32::try_from(c_nanoseconds / NSEC_IN_SEC)?;
-    let nanoseconds = u32::try_from(c_nanoseconds % NSEC_IN_SEC)?;
-    Ok(NaiveTime::from_num_seconds_from_midnight(
-        seconds,
-        nanoseconds,
-    ))
+    nanoseconds_to_naive_time(c_nanoseconds)
 }
 
 pub(crate) fn mg_value_naive_local_date_time(
     mg_value: *const bindings::mg_value,
-) -> Result<NaiveDateTime, TryFromIntError> {
+) -> Result<NaiveDateTime, MgError> {
     let c_local_date_time = unsafe { bindings::mg_value_local_date_time(mg_value) };
     let c_seconds = unsafe { bindings::mg_local_date_time_seconds(c_local_date_time) };
     let c_nanoseconds = unsafe { bindings::mg_local_date_time_nanoseconds(c_local_date_time) };
-    let nanoseconds = u32::try_from(c_nanoseconds)?;
-    Ok(NaiveDateTime::from_timestamp(c_seconds, nanoseconds))
+    let error = || {
+        MgError::new(format!(
+            "Local date time {} seconds and {} nanoseconds from epoch is out of range",
+            c_seconds, c_nanoseconds
+        ))
+    };
+    let nanoseconds = u32::try_from(c_nanoseconds).map_err(|_| error())?;
+    DateTime::from_timestamp(c_seconds, nanoseconds)
+        .map(|x| x.naive_utc())
+        .ok_or_else(error)
 }
 
-pub(crate) fn mg_value_duration(mg_value: *const bindings::mg_value) -> Duration {
+pub(crate) fn mg_value_date_time_with_offset(
+    mg_value: *const bindings::mg_value,
+) -> Result<DateTime<FixedOffset>, MgError> {
+    let c_date_time = unsafe { bindings::mg_value_date_time(mg_value) };
+    let c_seconds = unsafe { bindings::mg_date_time_seconds(c_date_time) };
+    let c_nanoseconds = unsafe { bindings::mg_date_time_nanoseconds(c_date_time) };
+    let c_offset_minutes = unsafe { bindings::mg_date_time_tz_offset_minutes(c_date_time) };
+    let error = || {
+        MgError::new(format!(
+            "Date time {} seconds and {} nanoseconds from epoch with offset {} minutes is out of range",
+            c_seconds, c_nanoseconds, c_offset_minutes
+        ))
+    };
+    let offset = c_offset_minutes
+        .checked_mul(60)
+        .and_then(FixedOffset::east_opt)
+        .ok_or_else(error)?;
+    let nanoseconds = u32::try_from(c_nanoseconds).map_err(|_| error())?;
+    let local = DateTime::from_timestamp(c_seconds, nanoseconds)
+        .map(|x| x.naive_utc())
+        .ok_or_else(error)?;
+    offset
+        .from_local_datetime(&local)
+        .single()
+        .ok_or_else(error)
+}
+
+pub(crate) fn mg_value_duration(mg_value: *const bindings::mg_value) -> MgDuration {
     let c_duration = unsafe { bindings::mg_value_duration(mg_value) };
+    let months = unsafe { bindings::mg_duration_months(c_duration) };
     let days = unsafe { bindings::mg_duration_days(c_duration) };
     let seconds = unsafe { bindings::mg_duration_seconds(c_duration) };
     let nanoseconds = unsafe { bindings::mg_duration_nanoseconds(c_duration) };
-    Duration::days(days) + Duration::seconds(seconds) + Duration::nanoseconds(nanoseconds)
+    MgDuration::new(months, days, seconds, nanoseconds)
 }
 
 pub(crate) fn mg_map_to_hash_map(mg_map: *const bindings::mg_map) -> HashMap<String, Value> {
+    convert_mg_map(mg_map, false, &mut KeyInterner::default(), keep_key).unwrap_or_default()
+}
+
+fn keep_key(_: &mut KeyInterner, key: String) -> String {
+    key
+}
+
+fn convert_mg_map<K: Eq + Hash>(
+    mg_map: *const bindings::mg_map,
+    strict: bool,
+    keys: &mut KeyInterner,
+    make_key: fn(&mut KeyInterner, String) -> K,
+) -> Result<HashMap<K, Value>, MgError> {
     unsafe {
         let size = bindings::mg_map_size(mg_map);
-        let mut hash_map: HashMap<String, Value> = HashMap::new();
+        let mut hash_map: HashMap<K, Value> = HashMap::with_capacity(size as usize);
         for i in 0..size {
             let mg_string = bindings::mg_map_key_at(mg_map, i);
-            let key = mg_string_to_string(mg_string);
+            let key = make_key(keys, mg_string_to_string(mg_string));
             let map_value = bindings::mg_map_value_at(mg_map, i);
-            hash_map.insert(key, Value::from_mg_value(map_value));
+            let value = match strict {
+                true => Value::convert(map_value, true, keys)?,
+                false => Value::convert(map_value, false, keys).unwrap_or(Value::Null),
+            };
+            hash_map.insert(key, value);
         }
 
-        hash_map
+        Ok(hash_map)
     }
 }
 
-fn mg_value_map(mg_value: *const bindings::mg_value) -> HashMap<String, Value> {
+fn mg_value_map(
+    mg_value: *const bindings::mg_value,
+    strict: bool,
+    keys: &mut KeyInterner,
+) -> Result<HashMap<String, Value>, MgError> {
     unsafe {
         let mg_map = bindings::mg_value_map(mg_value);
-        mg_map_to_hash_map(mg_map)
+        convert_mg_map(mg_map, strict, keys, keep_key)
     }
 }
 
-fn c_mg_node_to_mg_node(c_mg_node: *const bindings::mg_node) -> Node {
+fn c_mg_node_to_mg_node(
+    c_mg_node: *const bindings::mg_node,
+    strict: bool,
+    keys: &mut KeyInterner,
+) -> Result<Node, MgError> {
     let id = unsafe { bindings::mg_node_id(c_mg_node) };
     let label_count = unsafe { bindings::mg_node_label_count(c_mg_node) };
-    let mut labels: Vec<String> = Vec::new();
+    let mut labels: Vec<String> = Vec::with_capacity(label_count as usize);
     for i in 0..label_count {
         let label = unsafe { bindings::mg_node_label_at(c_mg_node, i) };
         labels.push(mg_string_to_string(label));
     }
 
     let properties_map = unsafe { bindings::mg_node_properties(c_mg_node) };
-    let properties: HashMap<String, Value> = mg_map_to_hash_map(properties_map);
+    let properties = convert_mg_map(properties_map, strict, keys, KeyInterner::intern)?;
 
-    Node {
+    Ok(Node {
         id,
         label_count,
         labels,
         properties,
-    }
+    })
 }
 
-fn mg_value_node(mg_value: *const bindings::mg_value) -> Node {
+fn mg_value_node(
+    mg_value: *const bindings::mg_value,
+    strict: bool,
+    keys: &mut KeyInterner,
+) -> Result<Node, MgError> {
     let c_mg_node = unsafe { bindings::mg_value_node(mg_value) };
-    c_mg_node_to_mg_node(c_mg_node)
+    c_mg_node_to_mg_node(c_mg_node, strict, keys)
 }
 
-fn mg_value_relationship(mg_value: *const bindings::mg_value) -> Relationship {
+fn mg_value_relationship(
+    mg_value: *const bindings::mg_value,
+    strict: bool,
+    keys: &mut KeyInterner,
+) -> Result<Relationship, MgError> {
     let c_mg_relationship = unsafe { bindings::mg_value_relationship(mg_value) };
 
     let id = unsafe { bindings::mg_relationship_id(c_mg_relationship) };
@@ -297,53 +927,64 @@ fn mg_value_relationship(mg_value: *const bindings::mg_value) -> Relationship {
     let type_mg_string = unsafe { bindings::mg_relationship_type(c_mg_relationship) };
     let type_ = mg_string_to_string(type_mg_string);
     let properties_mg_map = unsafe { bindings::mg_relationship_properties(c_mg_relationship) };
-    let properties = mg_map_to_hash_map(properties_mg_map);
+    let properties = convert_mg_map(properties_mg_map, strict, keys, KeyInterner::intern)?;
 
-    Relationship {
+    Ok(Relationship {
         id,
         start_id,
         end_id,
         type_,
         properties,
-    }
+    })
 }
 
 fn c_mg_unbound_relationship_to_mg_unbound_relationship(
     c_mg_unbound_relationship: *const bindings::mg_unbound_relationship,
-) -> UnboundRelationship {
+    strict: bool,
+    keys: &mut KeyInterner,
+) -> Result<UnboundRelationship, MgError> {
     let id = unsafe { bindings::mg_unbound_relationship_id(c_mg_unbound_relationship) };
     let type_mg_string =
         unsafe { bindings::mg_unbound_relationship_type(c_mg_unbound_relationship) };
     let type_ = mg_string_to_string(type_mg_string);
     let properties_mg_map =
         unsafe { bindings::mg_unbound_relationship_properties(c_mg_unbound_relationship) };
-    let properties = mg_map_to_hash_map(properties_mg_map);
+    let properties = convert_mg_map(properties_mg_map, strict, keys, KeyInterner::intern)?;
 
-    UnboundRelationship {
+    Ok(UnboundRelationship {
         id,
         type_,
         properties,
-    }
+    })
 }
 
-fn mg_value_unbound_relationship(mg_value: *const bindings::mg_value) -> UnboundRelationship {
+fn mg_value_unbound_relationship(
+    mg_value: *const bindings::mg_value,
+    strict: bool,
+    keys: &mut KeyInterner,
+) -> Result<UnboundRelationship, MgError> {
     let c_mg_unbound_relationship = unsafe { bindings::mg_value_unbound_relationship(mg_value) };
-    c_mg_unbound_relationship_to_mg_unbound_relationship(c_mg_unbound_relationship)
+    c_mg_unbound_relationship_to_mg_unbound_relationship(c_mg_unbound_relationship, strict, keys)
 }
 
-fn mg_value_path(mg_value: *const bindings::mg_value) -> Path {
+fn mg_value_path(
+    mg_value: *const bindings::mg_value,
+    strict: bool,
+    keys: &mut KeyInterner,
+) -> Result<Path, MgError> {
     let c_mg_path = unsafe { bindings::mg_value_path(mg_value) };
     let mut node_count = 0;
     let mut relationship_count = 0;
     let mut nodes: Vec<Node> = Vec::new();
     let mut relationships: Vec<UnboundRelationship> = Vec::new();
+    let mut sequence: Vec<i64> = Vec::new();
     loop {
         let c_mg_node = unsafe { bindings::mg_path_node_at(c_mg_path, node_count) };
         if c_mg_node.is_null() {
             break;
         }
         node_count += 1;
-        nodes.push(c_mg_node_to_mg_node(c_mg_node));
+        nodes.push(c_mg_node_to_mg_node(c_mg_node, strict, keys)?);
     }
     loop {
         let c_mg_unbound_relationship =
@@ -351,28 +992,59 @@ fn mg_value_path(mg_value: *const bindings::mg_value) -> Path {
         if c_mg_unbound_relationship.is_null() {
             break;
         }
+        let reversed =
+            unsafe { bindings::mg_path_relationship_reversed_at(c_mg_path, relationship_count) };
         relationship_count += 1;
         relationships.push(c_mg_unbound_relationship_to_mg_unbound_relationship(
             c_mg_unbound_relationship,
-        ));
+            strict,
+            keys,
+        )?);
+        let index = i64::from(relationship_count);
+        sequence.push(if reversed == 1 { -index } else { index });
+        sequence.push(index);
     }
-    Path {
+    Ok(Path {
         node_count,
         relationship_count,
         nodes,
         relationships,
-    }
+        sequence,
+    })
 }
 
-pub(crate) unsafe fn mg_list_to_vec(mg_list: *const bindings::mg_list) -> Vec<Value> {
+pub(crate) unsafe fn mg_list_to_vec(
+    mg_list: *const bindings::mg_list,
+    keys: &mut KeyInterner,
+) -> Vec<Value> {
+    convert_mg_list(mg_list, false, keys).unwrap_or_default()
+}
+
+/// Converts list like `mg_list_to_vec`, but returns error if any of the values can't be
+/// converted instead of replacing it with `Value::Null`.
+pub(crate) unsafe fn try_mg_list_to_vec(
+    mg_list: *const bindings::mg_list,
+    keys: &mut KeyInterner,
+) -> Result<Vec<Value>, MgError> {
+    convert_mg_list(mg_list, true, keys)
+}
+
+unsafe fn convert_mg_list(
+    mg_list: *const bindings::mg_list,
+    strict: bool,
+    keys: &mut KeyInterner,
+) -> Result<Vec<Value>, MgError> {
     let size = bindings::mg_list_size(mg_list);
-    let mut mg_values: Vec<Value> = Vec::new();
+    let mut mg_values: Vec<Value> = Vec::with_capacity(size as usize);
     for i in 0..size {
         let mg_value = bindings::mg_list_at(mg_list, i);
-        mg_values.push(Value::from_mg_value(mg_value));
+        mg_values.push(match strict {
+            true => Value::convert(mg_value, true, keys)?,
+            false => Value::convert(mg_value, false, keys).unwrap_or(Value::Null),
+        });
     }
 
-    mg_values
+    Ok(mg_values)
 }
 
 pub(crate) fn hash_map_to_mg_map(hash_map: &HashMap<String, QueryParam>) -> *mut bindings::mg_map {
@@ -397,12 +1069,34 @@ pub(crate) fn naive_date_to_mg_date(input: &NaiveDate) -> *mut bindings::mg_date
     unsafe { bindings::mg_date_make((input.num_days_from_ce() - unix_epoch) as i64) }
 }
 
-pub(crate) fn naive_local_time_to_mg_local_time(input: &NaiveTime) -> *mut bindings::mg_local_time {
+// Chrono represents leap seconds by nanoseconds exceeding a second, which Memgraph can't store,
+// so they are clamped to the last nanosecond of the preceding second.
+fn subsecond_nanoseconds(nanoseconds: u32) -> i64 {
+    (nanoseconds as i64).min(NSEC_IN_SEC - 1)
+}
+
+fn naive_time_to_nanoseconds(input: &NaiveTime) -> i64 {
     let hours_ns = hours_as_seconds(input.hour() as i64) * NSEC_IN_SEC;
     let minutes_ns = minutes_as_seconds(input.minute() as i64) * NSEC_IN_SEC;
     let seconds_ns = (input.second() as i64) * NSEC_IN_SEC;
-    let nanoseconds = input.nanosecond() as i64;
-    unsafe { bindings::mg_local_time_make(hours_ns + minutes_ns + seconds_ns + nanoseconds) }
+    let nanoseconds = subsecond_nanoseconds(input.nanosecond());
+    hours_ns + minutes_ns + seconds_ns + nanoseconds
+}
+
+pub(crate) fn naive_time_to_mg_time(
+    input: &NaiveTime,
+    offset: &FixedOffset,
+) -> *mut bindings::mg_time {
+    unsafe {
+        bindings::mg_time_make(
+            naive_time_to_nanoseconds(input),
+            offset.local_minus_utc() as i64,
+        )
+    }
+}
+
+pub(crate) fn naive_local_time_to_mg_local_time(input: &NaiveTime) -> *mut bindings::mg_local_time {
+    unsafe { bindings::mg_local_time_make(naive_time_to_nanoseconds(input)) }
 }
 
 pub(crate) fn naive_local_date_time_to_mg_local_date_time(
@@ -413,24 +1107,31 @@ pub(crate) fn naive_local_date_time_to_mg_local_date_time(
     let hours_s = hours_as_seconds(input.hour() as i64);
     let minutes_s = minutes_as_seconds(input.minute() as i64);
     let seconds_s = input.second() as i64;
-    let nanoseconds = input.nanosecond() as i64;
+    let nanoseconds = subsecond_nanoseconds(input.nanosecond());
     unsafe {
         bindings::mg_local_date_time_make(days_s + hours_s + minutes_s + seconds_s, nanoseconds)
     }
 }
 
-pub(crate) fn duration_to_mg_duration(input: &Duration) -> *mut bindings::mg_duration {
-    // Duration returns total number of nanoseconds, in order to create a valid mg_duration object,
-    // days and seconds have to be reducted from the total duration. In addition, one can get numer
-    // of nanoseconds and then substract days and seconds, but since nanoseconds can overflow quite
-    // quicky (with 292 years), it's better to use Duration and first reduce days and seconds.
-    let mut duration = *input;
-    let days = input.num_days();
-    duration = duration - Duration::days(days);
-    let seconds = input.num_seconds();
-    duration = duration - Duration::seconds(seconds);
-    let nanoseconds = duration.num_nanoseconds().unwrap();
-    unsafe { bindings::mg_duration_make(0, days, seconds, nanoseconds) }
+pub(crate) fn date_time_to_mg_date_time(
+    input: &DateTime<FixedOffset>,
+) -> *mut bindings::mg_date_time {
+    // Seconds are counted from epoch in the local time of the date time. The offset is truncated
+    // to whole minutes, see `QueryParam::DateTime`.
+    let local = input.naive_local().and_utc();
+    unsafe {
+        bindings::mg_date_time_make(
+            local.timestamp(),
+            subsecond_nanoseconds(local.timestamp_subsec_nanos()),
+            input.offset().local_minus_utc() / 60,
+        )
+    }
+}
+
+pub(crate) fn duration_to_mg_duration(input: &MgDuration) -> *mut bindings::mg_duration {
+    unsafe {
+        bindings::mg_duration_make(input.months, input.days, input.seconds, input.nanoseconds)
+    }
 }
 
 pub(crate) fn vector_to_mg_list(vector: &[QueryParam]) -> *mut bindings::mg_list {
@@ -444,9 +1145,36 @@ pub(crate) fn vector_to_mg_list(vector: &[QueryParam]) -> *mut bindings::mg_list
     mg_list
 }
 
+fn conversion_error(strict: bool, err: MgError) -> Result<Value, MgError> {
+    if strict {
+        Err(err)
+    } else {
+        Ok(Value::Null)
+    }
+}
+
 impl Value {
+    /// Converts value received from the database. Values which can't be converted, e.g. out of
+    /// range temporal values or values of unknown type, are replaced by `Value::Null`.
     pub(crate) unsafe fn from_mg_value(c_mg_value: *const bindings::mg_value) -> Value {
-        match bindings::mg_value_get_type(c_mg_value) {
+        Value::convert(c_mg_value, false, &mut KeyInterner::default()).unwrap_or(Value::Null)
+    }
+
+    /// Converts value received from the database like `from_mg_value`, but returns error if the
+    /// value or any of the values it contains can't be converted.
+    #[cfg(test)]
+    pub(crate) unsafe fn try_from_mg_value(
+        c_mg_value: *const bindings::mg_value,
+    ) -> Result<Value, MgError> {
+        Value::convert(c_mg_value, true, &mut KeyInterner::default())
+    }
+
+    unsafe fn convert(
+        c_mg_value: *const bindings::mg_value,
+        strict: bool,
+        keys: &mut KeyInterner,
+    ) -> Result<Value, MgError> {
+        Ok(match bindings::mg_value_get_type(c_mg_value) {
             bindings::mg_value_type_MG_VALUE_TYPE_NULL => Value::Null,
             bindings::mg_value_type_MG_VALUE_TYPE_BOOL => Value::Bool(mg_value_bool(c_mg_value)),
             bindings::mg_value_type_MG_VALUE_TYPE_INTEGER => Value::Int(mg_value_int(c_mg_value)),
@@ -454,113 +1182,426 @@ impl Value {
             bindings::mg_value_type_MG_VALUE_TYPE_STRING => {
                 Value::String(mg_value_string(c_mg_value))
             }
-            bindings::mg_value_type_MG_VALUE_TYPE_DATE => {
-                Value::Date(mg_value_naive_date(c_mg_value).unwrap())
-            }
+            bindings::mg_value_type_MG_VALUE_TYPE_DATE => match mg_value_naive_date(c_mg_value) {
+                Ok(x) => Value::Date(x),
+                Err(err) => conversion_error(strict, err)?,
+            },
+            bindings::mg_value_type_MG_VALUE_TYPE_TIME => match mg_value_naive_time(c_mg_value) {
+                Ok((time, offset)) => Value::Time(time, offset),
+                Err(err) => conversion_error(strict, err)?,
+            },
             bindings::mg_value_type_MG_VALUE_TYPE_LOCAL_TIME => {
-                Value::LocalTime(mg_value_naive_local_time(c_mg_value).unwrap())
+                match mg_value_naive_local_time(c_mg_value) {
+                    Ok(x) => Value::LocalTime(x),
+                    Err(err) => conversion_error(strict, err)?,
+                }
             }
             bindings::mg_value_type_MG_VALUE_TYPE_LOCAL_DATE_TIME => {
-                Value::LocalDateTime(mg_value_naive_local_date_time(c_mg_value).unwrap())
+                match mg_value_naive_local_date_time(c_mg_value) {
+                    Ok(x) => Value::LocalDateTime(x),
+                    Err(err) => conversion_error(strict, err)?,
+                }
+            }
+            bindings::mg_value_type_MG_VALUE_TYPE_DATE_TIME => {
+                match mg_value_date_time_with_offset(c_mg_value) {
+                    Ok(x) => Value::DateTime(x),
+                    Err(err) => conversion_error(strict, err)?,
+                }
             }
             bindings::mg_value_type_MG_VALUE_TYPE_DURATION => {
                 Value::Duration(mg_value_duration(c_mg_value))
             }
             bindings::mg_value_type_MG_VALUE_TYPE_LIST => {
-                Value::List(mg_value_list_to_vec(c_mg_value))
+                Value::List(mg_value_list_to_vec(c_mg_value, strict, keys)?)
+            }
+            bindings::mg_value_type_MG_VALUE_TYPE_MAP => {
+                Value::Map(mg_value_map(c_mg_value, strict, keys)?)
+            }
+            bindings::mg_value_type_MG_VALUE_TYPE_NODE => {
+                Value::Node(mg_value_node(c_mg_value, strict, keys)?)
             }
-            bindings::mg_value_type_MG_VALUE_TYPE_MAP => Value::Map(mg_value_map(c_mg_value)),
-            bindings::mg_value_type_MG_VALUE_TYPE_NODE => Value::Node(mg_value_node(c_mg_value)),
             bindings::mg_value_type_MG_VALUE_TYPE_RELATIONSHIP => {
-                Value::Relationship(mg_value_relationship(c_mg_value))
+                Value::Relationship(mg_value_relationship(c_mg_value, strict, keys)?)
             }
             bindings::mg_value_type_MG_VALUE_TYPE_UNBOUND_RELATIONSHIP => {
-                Value::UnboundRelationship(mg_value_unbound_relationship(c_mg_value))
+                Value::UnboundRelationship(mg_value_unbound_relationship(c_mg_value, strict, keys)?)
             }
-            bindings::mg_value_type_MG_VALUE_TYPE_PATH => Value::Path(mg_value_path(c_mg_value)),
-            bindings::mg_value_type_MG_VALUE_TYPE_UNKNOWN => Value::Null,
-            _ => panic!("Unknown type"),
-        }
+            bindings::mg_value_type_MG_VALUE_TYPE_PATH => {
+                Value::Path(mg_value_path(c_mg_value, strict, keys)?)
+            }
+            x => conversion_error(strict, MgError::new(format!("Unknown value type {}", x)))?,
+        })
     }
 }
 
 impl fmt::Display for Value {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        match self {
-            Value::Null => write!(f, "NULL"),
-            Value::Bool(x) => write!(f, "{}", x),
-            Value::Int(x) => write!(f, "{}", x),
-            Value::Float(x) => write!(f, "{}", x),
-            Value::String(x) => write!(f, "'{}'", x),
-            Value::Date(x) => write!(f, "'{}'", x),
-            Value::LocalTime(x) => write!(f, "'{}'", x),
-            Value::LocalDateTime(x) => write!(f, "'{}'", x),
-            Value::Duration(x) => write!(f, "'{}'", x),
-            Value::List(x) => write!(
-                f,
-                "{}",
-                x.iter()
-                    .map(|val| val.to_string())
-                    .collect::<Vec<String>>()
-                    .join(", ")
-            ),
-            Value::Map(x) => write!(f, "{}", mg_map_to_string(x)),
-            Value::Node(x) => write!(f, "{}", x),
-            Value::Relationship(x) => write!(f, "{}", x),
-            Value::UnboundRelationship(x) => write!(f, "{}", x),
-            Value::Path(x) => write!(f, "{}", x),
-        }
+        fmt::Display::fmt(&self.display_with(&DisplayOptions::default()), f)
     }
 }
 
-fn mg_map_to_string(mg_map: &HashMap<String, Value>) -> String {
+fn mg_map_to_string<K: fmt::Display + Ord>(
+    mg_map: &HashMap<K, Value>,
+    options: &DisplayOptions,
+) -> String {
     let mut properties: Vec<String> = Vec::new();
     let mut sorted: Vec<_> = mg_map.iter().collect();
     sorted.sort_by(|x, y| x.0.cmp(y.0));
     for (key, value) in sorted {
-        properties.push(format!("'{}': {}", key, value));
+        properties.push(format!("'{}': {}", key, value.display_with(options)));
     }
     format!("{{{}}}", properties.join(", "))
 }
 
+fn write_node(f: &mut Formatter<'_>, node: &Node, options: &DisplayOptions) -> fmt::Result {
+    write!(
+        f,
+        "(:{} {})",
+        node.labels.join(", "),
+        mg_map_to_string(&node.properties, options)
+    )
+}
+
+fn write_relationship(
+    f: &mut Formatter<'_>,
+    type_: &str,
+    properties: &Properties,
+    options: &DisplayOptions,
+) -> fmt::Result {
+    write!(f, "[:{} {}]", type_, mg_map_to_string(properties, options))
+}
+
+fn write_path(f: &mut Formatter<'_>, path: &Path, options: &DisplayOptions) -> fmt::Result {
+    for (i, node) in path.nodes.iter().enumerate() {
+        if let Some(relationship) = i.checked_sub(1).and_then(|x| path.relationships.get(x)) {
+            let reversed = path.is_reversed(i - 1);
+            f.write_str(if reversed { "<-" } else { "-" })?;
+            write_relationship(f, &relationship.type_, &relationship.properties, options)?;
+            f.write_str(if reversed { "-" } else { "->" })?;
+        }
+        write_node(f, node, options)?;
+    }
+    Ok(())
+}
+
 impl fmt::Display for Node {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "(:{} {})",
-            self.labels.join(", "),
-            mg_map_to_string(&self.properties)
-        )
+        write_node(f, self, &DisplayOptions::default())
     }
 }
 
 impl fmt::Display for Relationship {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "[:{} {}]",
-            self.type_,
-            mg_map_to_string(&self.properties)
-        )
+        write_relationship(f, &self.type_, &self.properties, &DisplayOptions::default())
     }
 }
 
 impl fmt::Display for UnboundRelationship {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "[:{} {}]",
-            self.type_,
-            mg_map_to_string(&self.properties)
-        )
+        write_relationship(f, &self.type_, &self.properties, &DisplayOptions::default())
     }
 }
 
 impl fmt::Display for Path {
-    fn fmt(&self, _f: &mut Formatter<'_>) -> fmt::Result {
-        unimplemented!();
+    /// Formats the path as alternating nodes and relationships in traversal order, e.g.
+    /// `(:Person {})-[:KNOWS {}]->(:Person {})<-[:KNOWS {}]-(:Person {})`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write_path(f, self, &DisplayOptions::default())
+    }
+}
+
+impl Value {
+    pub(crate) fn type_name(&self) -> &'static str {
+        match self {
+            Value::Null => "Null",
+            Value::Bool(_) => "Bool",
+            Value::Int(_) => "Int",
+            Value::Float(_) => "Float",
+            Value::String(_) => "String",
+            Value::List(_) => "List",
+            Value::Date(_) => "Date",
+            Value::Time(..) => "Time",
+            Value::LocalTime(_) => "LocalTime",
+            Value::LocalDateTime(_) => "LocalDateTime",
+            Value::DateTime(_) => "DateTime",
+            Value::Duration(_) => "Duration",
+            Value::Map(_) => "Map",
+            Value::Node(_) => "Node",
+            Value::Relationship(_) => "Relationship",
+            Value::UnboundRelationship(_) => "UnboundRelationship",
+            Value::Path(_) => "Path",
+        }
+    }
+}
+
+macro_rules! impl_try_from_value {
+    ($($variant:ident => $to:ty),* $(,)?) => {
+        $(
+            impl TryFrom<Value> for $to {
+                type Error = MgError;
+
+                fn try_from(value: Value) -> Result<Self, Self::Error> {
+                    match value {
+                        Value::$variant(x) => Ok(x),
+                        x => Err(MgError::new(format!(
+                            "Can't convert {} value to {}",
+                            x.type_name(),
+                            stringify!($to)
+                        ))),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+/// Implements `TryFrom<Value>` using a conversion which returns `None` if the value is out of
+/// range of the target type, used for types of other crates.
+#[cfg(any(feature = "time", feature = "jiff"))]
+macro_rules! impl_try_from_value_checked {
+    ($($to:ty, $name:literal: $pattern:pat => $convert:expr;)*) => {
+        $(
+            impl TryFrom<Value> for $to {
+                type Error = MgError;
+
+                fn try_from(value: Value) -> Result<Self, Self::Error> {
+                    match &value {
+                        $pattern => $convert.ok_or_else(|| {
+                            MgError::new(format!(
+                                "Can't convert {} value {} to {}, it is out of range",
+                                value.type_name(),
+                                value,
+                                $name
+                            ))
+                        }),
+                        _ => Err(MgError::new(format!(
+                            "Can't convert {} value to {}",
+                            value.type_name(),
+                            $name
+                        ))),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from_value! {
+    Bool => bool,
+    Int => i64,
+    Float => f64,
+    String => String,
+    List => Vec<Value>,
+    Date => NaiveDate,
+    LocalTime => NaiveTime,
+    LocalDateTime => NaiveDateTime,
+    DateTime => DateTime<FixedOffset>,
+    Duration => MgDuration,
+    Map => HashMap<String, Value>,
+    Node => Node,
+    Relationship => Relationship,
+    UnboundRelationship => UnboundRelationship,
+    Path => Path,
+}
+
+impl TryFrom<Value> for (NaiveTime, FixedOffset) {
+    type Error = MgError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Time(time, offset) => Ok((time, offset)),
+            x => Err(MgError::new(format!(
+                "Can't convert {} value to (NaiveTime, FixedOffset)",
+                x.type_name()
+            ))),
+        }
+    }
+}
+
+/// Converts `Value::Null` to `None` and any other value to `Some` using the conversion of `T`.
+impl<T: TryFrom<Value, Error = MgError>> TryFrom<Value> for Option<T> {
+    type Error = MgError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Null => Ok(None),
+            x => T::try_from(x).map(Some),
+        }
     }
 }
 
+impl Record {
+    /// Creates a record of `values` of the `columns`, which can be shared by all records of the
+    /// same query.
+    pub fn new(columns: Arc<Vec<String>>, values: Vec<Value>) -> Record {
+        Record { columns, values }
+    }
+
+    /// Returns names of the columns of the record.
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// Returns values of the record keyed by their column names.
+    ///
+    /// Values without a matching column name are dropped, as are column names without a
+    /// matching value.
+    pub fn into_map(self) -> HashMap<String, Value> {
+        self.columns.iter().cloned().zip(self.values).collect()
+    }
+
+    /// Returns value of the column with the given name or `None` if there is no such column.
+    pub fn get(&self, column: &str) -> Option<&Value> {
+        self.columns
+            .iter()
+            .position(|x| x == column)
+            .and_then(|i| self.values.get(i))
+    }
+}
+
+impl Index<usize> for Record {
+    type Output = Value;
+
+    fn index(&self, index: usize) -> &Value {
+        &self.values[index]
+    }
+}
+
+impl Index<&str> for Record {
+    type Output = Value;
+
+    /// Returns value of the column with the given name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no column with the given name.
+    fn index(&self, column: &str) -> &Value {
+        match self.get(column) {
+            Some(x) => x,
+            None => panic!("No column named {}", column),
+        }
+    }
+}
+
+impl IntoIterator for Record {
+    type Item = Value;
+    type IntoIter = std::vec::IntoIter<Value>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values.into_iter()
+    }
+}
+
+/// Type which can be built from a record, e.g. a row yielded by a procedure, see
+/// `Connection::call`.
+///
+/// Implemented for `Record` itself and for tuples of up to 12 elements using their
+/// `TryFrom<Record>` conversion.
+///
+/// # Examples
+///
+/// ```
+/// use rsmgclient::{FromRecord, MgError, Record};
+/// use std::convert::TryFrom;
+///
+/// struct Procedure {
+///     name: String,
+///     signature: String,
+/// }
+///
+/// impl FromRecord for Procedure {
+///     const COLUMNS: &'static [&'static str] = &["name", "signature"];
+///
+///     fn from_record(record: Record) -> Result<Self, MgError> {
+///         let (name, signature) = <(String, String)>::try_from(record)?;
+///         Ok(Procedure { name, signature })
+///     }
+/// }
+/// ```
+pub trait FromRecord: Sized {
+    /// Names of the columns the type is built from, in the order of the record values. Empty if
+    /// the type uses all columns in their original order.
+    const COLUMNS: &'static [&'static str] = &[];
+
+    fn from_record(record: Record) -> Result<Self, MgError>;
+}
+
+impl FromRecord for Record {
+    fn from_record(record: Record) -> Result<Self, MgError> {
+        Ok(record)
+    }
+}
+
+macro_rules! impl_try_from_record_for_tuple {
+    ($len:expr => $($name:ident),+) => {
+        /// Converts a record with exactly as many values as the tuple has elements, converting
+        /// each value with its `TryFrom<Value>` conversion.
+        impl<$($name),+> TryFrom<Record> for ($($name,)+)
+        where
+            $($name: TryFrom<Value, Error = MgError>,)+
+        {
+            type Error = MgError;
+
+            fn try_from(record: Record) -> Result<Self, Self::Error> {
+                if record.values.len() != $len {
+                    return Err(MgError::new(format!(
+                        "Can't convert record with {} values to tuple of {} elements",
+                        record.values.len(),
+                        $len
+                    )));
+                }
+                let mut values = record.values.into_iter();
+                Ok(($($name::try_from(values.next().unwrap())?,)+))
+            }
+        }
+
+        impl<$($name),+> FromRecord for ($($name,)+)
+        where
+            $($name: TryFrom<Value, Error = MgError>,)+
+        {
+            fn from_record(record: Record) -> Result<Self, MgError> {
+                Self::try_from(record)
+            }
+        }
+    };
+}
+
+impl_try_from_record_for_tuple!(1 => T1);
+impl_try_from_record_for_tuple!(2 => T1, T2);
+impl_try_from_record_for_tuple!(3 => T1, T2, T3);
+impl_try_from_record_for_tuple!(4 => T1, T2, T3, T4);
+impl_try_from_record_for_tuple!(5 => T1, T2, T3, T4, T5);
+impl_try_from_record_for_tuple!(6 => T1, T2, T3, T4, T5, T6);
+impl_try_from_record_for_tuple!(7 => T1, T2, T3, T4, T5, T6, T7);
+impl_try_from_record_for_tuple!(8 => T1, T2, T3, T4, T5, T6, T7, T8);
+impl_try_from_record_for_tuple!(9 => T1, T2, T3, T4, T5, T6, T7, T8, T9);
+impl_try_from_record_for_tuple!(10 => T1, T2, T3, T4, T5, T6, T7, T8, T9, T10);
+impl_try_from_record_for_tuple!(11 => T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11);
+impl_try_from_record_for_tuple!(12 => T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12);
+
+mod display;
+pub use display::{DisplayOptions, TemporalFormat, ValueDisplay};
+
+mod borrowed;
+pub use borrowed::{ListIter, ListRef, MapIter, MapRef, RecordRef, ValueRef};
+
+#[cfg(feature = "serde")]
+mod serde;
+
+#[cfg(feature = "time")]
+mod time;
+
+#[cfg(feature = "jiff")]
+mod jiff;
+
+#[cfg(feature = "uuid")]
+mod uuid;
+
+#[cfg(feature = "rust_decimal")]
+mod decimal;
+
+#[cfg(feature = "num-bigint")]
+mod big_int;
+
 #[cfg(test)]
 mod tests;
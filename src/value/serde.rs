@@ -0,0 +1,204 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Serde support, enabled by the `serde` feature.
+//!
+//! Values are serialized into their natural JSON-like representation: graph elements become
+//! maps of their fields and temporal values become ISO 8601 strings. Deserialized `QueryParam`
+//! strings always become `QueryParam::String`, temporal parameters therefore don't round-trip.
+
+use super::{Node, Path, QueryParam, Relationship, UnboundRelationship, Value};
+use chrono::{FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
+
+fn date_to_string(x: &NaiveDate) -> String {
+    x.format("%Y-%m-%d").to_string()
+}
+
+fn time_to_string(x: &NaiveTime) -> String {
+    x.format("%H:%M:%S%.f").to_string()
+}
+
+fn time_with_offset_to_string(x: &NaiveTime, offset: &FixedOffset) -> String {
+    format!("{}{}", time_to_string(x), offset)
+}
+
+fn date_time_to_string(x: &NaiveDateTime) -> String {
+    x.format("%Y-%m-%dT%H:%M:%S%.f").to_string()
+}
+
+impl Serialize for Value {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Value::Null => serializer.serialize_unit(),
+            Value::Bool(x) => serializer.serialize_bool(*x),
+            Value::Int(x) => serializer.serialize_i64(*x),
+            Value::Float(x) => serializer.serialize_f64(*x),
+            Value::String(x) => serializer.serialize_str(x),
+            Value::List(x) => serializer.collect_seq(x),
+            Value::Date(x) => serializer.serialize_str(&date_to_string(x)),
+            Value::Time(time, offset) => {
+                serializer.serialize_str(&time_with_offset_to_string(time, offset))
+            }
+            Value::LocalTime(x) => serializer.serialize_str(&time_to_string(x)),
+            Value::LocalDateTime(x) => serializer.serialize_str(&date_time_to_string(x)),
+            Value::DateTime(x) => serializer.serialize_str(&x.to_rfc3339()),
+            Value::Duration(x) => serializer.serialize_str(&x.to_string()),
+            Value::Map(x) => serializer.collect_map(x),
+            Value::Node(x) => x.serialize(serializer),
+            Value::Relationship(x) => x.serialize(serializer),
+            Value::UnboundRelationship(x) => x.serialize(serializer),
+            Value::Path(x) => x.serialize(serializer),
+        }
+    }
+}
+
+impl Serialize for Node {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Node", 3)?;
+        state.serialize_field("id", &self.id)?;
+        state.serialize_field("labels", &self.labels)?;
+        state.serialize_field("properties", &self.properties)?;
+        state.end()
+    }
+}
+
+impl Serialize for Relationship {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Relationship", 5)?;
+        state.serialize_field("id", &self.id)?;
+        state.serialize_field("start_id", &self.start_id)?;
+        state.serialize_field("end_id", &self.end_id)?;
+        state.serialize_field("type", &self.type_)?;
+        state.serialize_field("properties", &self.properties)?;
+        state.end()
+    }
+}
+
+impl Serialize for UnboundRelationship {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("UnboundRelationship", 3)?;
+        state.serialize_field("id", &self.id)?;
+        state.serialize_field("type", &self.type_)?;
+        state.serialize_field("properties", &self.properties)?;
+        state.end()
+    }
+}
+
+impl Serialize for Path {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Path", 3)?;
+        state.serialize_field("nodes", &self.nodes)?;
+        state.serialize_field("relationships", &self.relationships)?;
+        state.serialize_field("sequence", &self.sequence)?;
+        state.end()
+    }
+}
+
+impl Serialize for QueryParam {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            QueryParam::Null => serializer.serialize_unit(),
+            QueryParam::Bool(x) => serializer.serialize_bool(*x),
+            QueryParam::Int(x) => serializer.serialize_i64(*x),
+            QueryParam::Float(x) => serializer.serialize_f64(*x),
+            QueryParam::String(x) => serializer.serialize_str(x),
+            QueryParam::Date(x) => serializer.serialize_str(&date_to_string(x)),
+            QueryParam::Time(time, offset) => {
+                serializer.serialize_str(&time_with_offset_to_string(time, offset))
+            }
+            QueryParam::LocalTime(x) => serializer.serialize_str(&time_to_string(x)),
+            QueryParam::LocalDateTime(x) => serializer.serialize_str(&date_time_to_string(x)),
+            QueryParam::DateTime(x) => serializer.serialize_str(&x.to_rfc3339()),
+            QueryParam::Duration(x) => serializer.serialize_str(&x.to_string()),
+            QueryParam::List(x) => serializer.collect_seq(x),
+            QueryParam::Map(x) => serializer.collect_map(x),
+        }
+    }
+}
+
+struct QueryParamVisitor;
+
+impl<'de> Visitor<'de> for QueryParamVisitor {
+    type Value = QueryParam;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a query parameter")
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<QueryParam, E> {
+        Ok(QueryParam::Null)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<QueryParam, E> {
+        Ok(QueryParam::Null)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<QueryParam, D::Error> {
+        QueryParam::deserialize(deserializer)
+    }
+
+    fn visit_bool<E: de::Error>(self, x: bool) -> Result<QueryParam, E> {
+        Ok(QueryParam::Bool(x))
+    }
+
+    fn visit_i64<E: de::Error>(self, x: i64) -> Result<QueryParam, E> {
+        Ok(QueryParam::Int(x))
+    }
+
+    fn visit_u64<E: de::Error>(self, x: u64) -> Result<QueryParam, E> {
+        match i64::try_from(x) {
+            Ok(x) => Ok(QueryParam::Int(x)),
+            Err(_) => Err(E::custom(format!("integer {} is out of range", x))),
+        }
+    }
+
+    fn visit_f64<E: de::Error>(self, x: f64) -> Result<QueryParam, E> {
+        Ok(QueryParam::Float(x))
+    }
+
+    fn visit_str<E: de::Error>(self, x: &str) -> Result<QueryParam, E> {
+        Ok(QueryParam::String(x.to_string()))
+    }
+
+    fn visit_string<E: de::Error>(self, x: String) -> Result<QueryParam, E> {
+        Ok(QueryParam::String(x))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<QueryParam, A::Error> {
+        let mut list = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(x) = seq.next_element()? {
+            list.push(x);
+        }
+        Ok(QueryParam::List(list))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<QueryParam, A::Error> {
+        let mut params = HashMap::with_capacity(map.size_hint().unwrap_or(0));
+        while let Some((key, value)) = map.next_entry()? {
+            params.insert(key, value);
+        }
+        Ok(QueryParam::Map(params))
+    }
+}
+
+impl<'de> Deserialize<'de> for QueryParam {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<QueryParam, D::Error> {
+        deserializer.deserialize_any(QueryParamVisitor)
+    }
+}
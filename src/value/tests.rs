@@ -1,4 +1,5 @@
 use super::*;
+use std::convert::TryInto;
 use std::ffi::CString;
 use std::mem;
 extern crate libc;
@@ -62,12 +63,16 @@ fn mg_value_to_c_mg_value(mg_value: &Value) -> *mut bindings::mg_value {
             Value::Float(x) => bindings::mg_value_make_float(*x),
             Value::String(x) => bindings::mg_value_make_string(str_to_c_str(x.as_str())),
             Value::Date(x) => bindings::mg_value_make_date(naive_date_to_mg_date(x)),
+            Value::Time(time, offset) => {
+                bindings::mg_value_make_time(naive_time_to_mg_time(time, offset))
+            }
             Value::LocalTime(x) => {
                 bindings::mg_value_make_local_time(naive_local_time_to_mg_local_time(x))
             }
             Value::LocalDateTime(x) => bindings::mg_value_make_local_date_time(
                 naive_local_date_time_to_mg_local_date_time(x),
             ),
+            Value::DateTime(x) => bindings::mg_value_make_date_time(date_time_to_mg_date_time(x)),
             Value::Duration(x) => bindings::mg_value_make_duration(duration_to_mg_duration(x)),
             Value::List(x) => {
                 bindings::mg_value_make_list(bindings::mg_list_copy(vector_to_mg_list(x)))
@@ -110,15 +115,8 @@ fn mg_value_to_c_mg_value(mg_value: &Value) -> *mut bindings::mg_value {
                 )
             }
             Value::Path(x) => {
-                let mut sequence = Vec::new();
-                for i in 0..x.node_count {
-                    sequence.push(x.nodes[i as usize].id);
-                    if i < x.relationship_count {
-                        sequence.push(x.relationships[i as usize].id);
-                    }
-                }
-                let sequence_length = sequence.len() as u32;
-                let seq_ptr = to_c_int_array(&sequence);
+                let sequence_length = x.sequence.len() as u32;
+                let seq_ptr = to_c_int_array(&x.sequence);
 
                 let nodes_box = to_array_of_nodes(&x.nodes);
                 let unbound_relationship_box = to_array_of_unbound_relationships(&x.relationships);
@@ -146,12 +144,16 @@ fn vector_to_mg_list(vector: &[Value]) -> *mut bindings::mg_list {
     mg_list
 }
 
-fn hash_map_to_mg_map(hash_map: &HashMap<String, Value>) -> *mut bindings::mg_map {
+fn hash_map_to_mg_map<K: AsRef<str>>(hash_map: &HashMap<K, Value>) -> *mut bindings::mg_map {
     let size = hash_map.len();
     let mg_map = unsafe { bindings::mg_map_make_empty(size as u32) };
     for (key, val) in hash_map {
         unsafe {
-            bindings::mg_map_insert(mg_map, str_to_c_str(key), mg_value_to_c_mg_value(val));
+            bindings::mg_map_insert(
+                mg_map,
+                str_to_c_str(key.as_ref()),
+                mg_value_to_c_mg_value(val),
+            );
         }
     }
 
@@ -284,6 +286,35 @@ fn from_c_mg_value_local_time() {
     assert_eq!(format!("{}", mg_value), "'14:40:35.851241'");
 }
 
+#[test]
+fn from_c_mg_value_time() {
+    let c_time = bindings::mg_time {
+        nanoseconds: 52835851241000,
+        tz_offset_seconds: 2 * 60 * 60,
+    };
+    let c_mg_value = unsafe { bindings::mg_value_make_time(bindings::mg_time_copy(&c_time)) };
+    let mg_value = unsafe { Value::from_mg_value(c_mg_value) };
+    assert_eq!(
+        Value::Time(
+            NaiveTime::from_hms_micro(14, 40, 35, 851241),
+            FixedOffset::east(2 * 60 * 60)
+        ),
+        mg_value
+    );
+    assert_eq!(format!("{}", mg_value), "'14:40:35.851241+02:00'");
+}
+
+#[test]
+fn from_c_mg_value_time_offset_out_of_range() {
+    let c_time = bindings::mg_time {
+        nanoseconds: 0,
+        tz_offset_seconds: 48 * 60 * 60,
+    };
+    let c_mg_value = unsafe { bindings::mg_value_make_time(bindings::mg_time_copy(&c_time)) };
+    assert_eq!(unsafe { Value::from_mg_value(c_mg_value) }, Value::Null);
+    assert!(unsafe { Value::try_from_mg_value(c_mg_value) }.is_err());
+}
+
 #[test]
 fn from_c_mg_value_local_date_time1() {
     let c_local_date_time = bindings::mg_local_date_time {
@@ -352,11 +383,49 @@ fn from_c_mg_value_duration() {
     let c_mg_value =
         unsafe { bindings::mg_value_make_duration(bindings::mg_duration_copy(&c_duration)) };
     let mg_value = unsafe { Value::from_mg_value(c_mg_value) };
+    assert_eq!(Value::Duration(MgDuration::new(0, 10, 100, 1000)), mg_value);
+    assert_eq!(format!("{}", mg_value), "'P10DT100.000001S'");
+}
+
+#[test]
+fn from_c_mg_value_duration_with_months() {
+    let c_duration = bindings::mg_duration {
+        months: 14,
+        days: -3,
+        seconds: 0,
+        nanoseconds: 0,
+    };
+    let c_mg_value =
+        unsafe { bindings::mg_value_make_duration(bindings::mg_duration_copy(&c_duration)) };
+    let mg_value = unsafe { Value::from_mg_value(c_mg_value) };
+    assert_eq!(Value::Duration(MgDuration::new(14, -3, 0, 0)), mg_value);
+    assert_eq!(format!("{}", mg_value), "'P14M-3D'");
+    match mg_value {
+        Value::Duration(x) => assert_eq!(x.to_chrono(), None),
+        _ => panic!("Duration converted into a wrong Value type!"),
+    }
+}
+
+#[test]
+fn mg_duration_from_chrono() {
+    let duration = MgDuration::from(Duration::seconds(-90000) - Duration::milliseconds(500));
+    assert_eq!(duration, MgDuration::new(0, -1, -3600, -500000000));
+    assert_eq!(duration.to_string(), "P-1DT-3600.5S");
     assert_eq!(
-        Value::Duration(Duration::days(10) + Duration::seconds(100) + Duration::nanoseconds(1000)),
-        mg_value
+        duration.to_chrono(),
+        Some(Duration::seconds(-90000) - Duration::milliseconds(500))
     );
-    assert_eq!(format!("{}", mg_value), "'PT864100.000001S'");
+    assert_eq!(MgDuration::default().to_string(), "PT0S");
+    assert_eq!(MgDuration::new(0, 0, i64::MAX, 0).to_chrono(), None);
+}
+
+#[test]
+fn from_duration_with_months_to_mg_value() {
+    let query_param = QueryParam::from(MgDuration::new(1, 2, 3, 4));
+    let c_mg_value = unsafe { *(query_param.to_c_mg_value()) };
+    let mg_value = unsafe { Value::from_mg_value(&c_mg_value) };
+    assert_eq!(mg_value, Value::Duration(MgDuration::new(1, 2, 3, 4)));
+    assert_eq!(mg_value.to_string(), "'P1M2DT3.000000004S'");
 }
 
 #[test]
@@ -371,7 +440,7 @@ fn from_c_mg_value_list() {
             end_id: 2,
             type_: "test".to_string(),
             properties: hashmap! {
-                String::from("name") => Value::Null,
+                Arc::from("name") => Value::Null,
             },
         }),
     ];
@@ -393,7 +462,7 @@ fn from_c_mg_value_list_display() {
             end_id: 2,
             type_: "test".to_string(),
             properties: hashmap! {
-                String::from("name") => Value::Null,
+                Arc::from("name") => Value::Null,
             },
         }),
     ]);
@@ -416,7 +485,7 @@ fn from_c_mg_value_map() {
             end_id: 2,
             type_: String::from("test"),
             properties: hashmap!{
-                String::from("name") => Value::Null,
+                Arc::from("name") => Value::Null,
             }
         }),
     };
@@ -443,7 +512,7 @@ fn from_c_mg_value_map_display() {
             end_id: 2,
             type_: String::from("test"),
             properties: hashmap!{
-                String::from("name") => Value::Null,
+                Arc::from("name") => Value::Null,
             }
         }),
     });
@@ -458,14 +527,14 @@ fn from_c_mg_value_map_display() {
 fn from_c_mg_value_node() {
     let values = vec![String::from("test")];
     let mg_map = hashmap! {
-        String::from("id") => Value::Int(128),
-        String::from("rel") => Value::Relationship(Relationship {
+        Arc::from("id") => Value::Int(128),
+        Arc::from("rel") => Value::Relationship(Relationship {
             id: 1,
             start_id: 1,
             end_id: 2,
             type_: String::from("test"),
             properties: hashmap!{
-                String::from("name") => Value::Null,
+                Arc::from("name") => Value::Null,
             }
         }),
     };
@@ -487,14 +556,14 @@ fn from_c_mg_value_node() {
 fn from_c_mg_value_node_display() {
     let values = vec![String::from("test")];
     let mg_map = hashmap! {
-        String::from("id") => Value::Int(128),
-        String::from("rel") => Value::Relationship(Relationship {
+        Arc::from("id") => Value::Int(128),
+        Arc::from("rel") => Value::Relationship(Relationship {
             id: 1,
             start_id: 1,
             end_id: 2,
             type_: String::from("test"),
             properties: hashmap!{
-                String::from("name") => Value::Null,
+                Arc::from("name") => Value::Null,
             }
         }),
     };
@@ -514,14 +583,14 @@ fn from_c_mg_value_node_display() {
 #[test]
 fn from_c_mg_value_relationship() {
     let mg_map = hashmap! {
-        String::from("id") => Value::Int(128),
-        String::from("rel") => Value::Relationship(Relationship {
+        Arc::from("id") => Value::Int(128),
+        Arc::from("rel") => Value::Relationship(Relationship {
             id: 1,
             start_id: 1,
             end_id: 2,
             type_: String::from("test"),
             properties: hashmap!{
-                String::from("name") => Value::Null,
+                Arc::from("name") => Value::Null,
             }
         }),
     };
@@ -541,14 +610,14 @@ fn from_c_mg_value_relationship() {
 #[test]
 fn from_c_mg_value_relationship_display() {
     let mg_map = hashmap! {
-        String::from("id") => Value::Int(128),
-        String::from("rel") => Value::Relationship(Relationship {
+        Arc::from("id") => Value::Int(128),
+        Arc::from("rel") => Value::Relationship(Relationship {
             id: 1,
             start_id: 1,
             end_id: 2,
             type_: String::from("test"),
             properties: hashmap!{
-                String::from("name") => Value::Null,
+                Arc::from("name") => Value::Null,
             }
         }),
     };
@@ -569,14 +638,14 @@ fn from_c_mg_value_relationship_display() {
 #[test]
 fn from_c_mg_value_unbound_relationship() {
     let mg_map = hashmap! {
-        String::from("id") => Value::Int(128),
-        String::from("rel") => Value::Relationship(Relationship {
+        Arc::from("id") => Value::Int(128),
+        Arc::from("rel") => Value::Relationship(Relationship {
             id: 1,
             start_id: 1,
             end_id: 2,
             type_: String::from("test"),
             properties: hashmap!{
-                String::from("name") => Value::Null,
+                Arc::from("name") => Value::Null,
             }
         }),
     };
@@ -594,14 +663,14 @@ fn from_c_mg_value_unbound_relationship() {
 #[test]
 fn from_c_mg_value_unbound_relationship_display() {
     let mg_map = hashmap! {
-        String::from("id") => Value::Int(128),
-        String::from("rel") => Value::Relationship(Relationship {
+        Arc::from("id") => Value::Int(128),
+        Arc::from("rel") => Value::Relationship(Relationship {
             id: 1,
             start_id: 1,
             end_id: 2,
             type_: String::from("test"),
             properties: hashmap!{
-                String::from("name") => Value::Null,
+                Arc::from("name") => Value::Null,
             }
         }),
     };
@@ -621,14 +690,14 @@ fn from_c_mg_value_path() {
     let values = vec![String::from("test")];
     let values2 = vec![String::from("test")];
     let mg_map = hashmap! {
-        String::from("id") => Value::Int(128),
-        String::from("rel") => Value::Relationship(Relationship {
+        Arc::from("id") => Value::Int(128),
+        Arc::from("rel") => Value::Relationship(Relationship {
             id: 1,
             start_id: 1,
             end_id: 2,
             type_: String::from("test"),
             properties: hashmap!{
-                String::from("name") => Value::Null,
+                Arc::from("name") => Value::Null,
             }
         }),
     };
@@ -638,26 +707,26 @@ fn from_c_mg_value_path() {
         properties: mg_map,
     };
     let mg_map2 = hashmap! {
-        String::from("id") => Value::Int(128),
-        String::from("rel") => Value::Relationship(Relationship {
+        Arc::from("id") => Value::Int(128),
+        Arc::from("rel") => Value::Relationship(Relationship {
             id: 1,
             start_id: 1,
             end_id: 2,
             type_: String::from("test"),
             properties: hashmap!{
-                String::from("name") => Value::Null,
+                Arc::from("name") => Value::Null,
             }
         }),
     };
     let mg_map3 = hashmap! {
-        String::from("id") => Value::Int(128),
-        String::from("rel") => Value::Relationship(Relationship {
+        Arc::from("id") => Value::Int(128),
+        Arc::from("rel") => Value::Relationship(Relationship {
             id: 1,
             start_id: 1,
             end_id: 2,
             type_: String::from("test"),
             properties: hashmap!{
-                String::from("name") => Value::Null,
+                Arc::from("name") => Value::Null,
             }
         }),
     };
@@ -678,6 +747,7 @@ fn from_c_mg_value_path() {
         relationship_count: 1,
         nodes: vec![c_node, c_node2],
         relationships: vec![c_unbound_relationship],
+        sequence: vec![-1, 1],
     });
 
     let c_mg_value = mg_value_to_c_mg_value(&c_path);
@@ -685,6 +755,42 @@ fn from_c_mg_value_path() {
     assert_eq!(c_path, mg_value);
 }
 
+#[test]
+fn path_display() {
+    let node = |id: i64, name: &str| Node {
+        id,
+        label_count: 1,
+        labels: vec![String::from("Person")],
+        properties: hashmap! {
+            Arc::from("name") => Value::String(String::from(name)),
+        },
+    };
+    let path = Path {
+        node_count: 3,
+        relationship_count: 2,
+        nodes: vec![node(1, "Alice"), node(2, "Bob"), node(1, "Alice")],
+        relationships: vec![
+            UnboundRelationship {
+                id: 10,
+                type_: String::from("KNOWS"),
+                properties: HashMap::new(),
+            },
+            UnboundRelationship {
+                id: 11,
+                type_: String::from("LIKES"),
+                properties: HashMap::new(),
+            },
+        ],
+        sequence: vec![1, 1, -2, 2],
+    };
+    assert!(!path.is_reversed(0));
+    assert!(path.is_reversed(1));
+    assert_eq!(
+        Value::Path(path).to_string(),
+        "(:Person {'name': 'Alice'})-[:KNOWS {}]->(:Person {'name': 'Bob'})<-[:LIKES {}]-(:Person {'name': 'Alice'})"
+    );
+}
+
 #[test]
 fn from_to_c_mg_value_null() {
     let query_param_null = QueryParam::Null;
@@ -786,6 +892,66 @@ fn from_naive_local_time_param_to_mg_value() {
     }
 }
 
+#[test]
+fn from_naive_time_param_to_mg_value() {
+    let query_param = QueryParam::Time(
+        NaiveTime::from_hms_nano(2, 3, 4, 1234),
+        FixedOffset::west(5 * 60 * 60 + 30 * 60),
+    );
+    let c_mg_value = unsafe { *(query_param.to_c_mg_value()) };
+    assert_eq!(c_mg_value.type_, bindings::mg_value_type_MG_VALUE_TYPE_TIME);
+    let mg_value = unsafe { Value::from_mg_value(&c_mg_value) };
+    assert_eq!(mg_value.to_string(), "'02:03:04.000001234-05:30'");
+    match mg_value {
+        Value::Time(time, offset) => {
+            assert_eq!(time, NaiveTime::from_hms_nano(2, 3, 4, 1234));
+            assert_eq!(offset.local_minus_utc(), -(5 * 60 * 60 + 30 * 60));
+        }
+        _ => {
+            panic!("QueryParam::Time converted into a wrong Value type!");
+        }
+    }
+}
+
+#[test]
+fn from_date_time_param_to_mg_value() {
+    let date_time = FixedOffset::east(2 * 60 * 60)
+        .ymd(2021, 7, 14)
+        .and_hms_nano(18, 30, 5, 1234);
+    let query_param = QueryParam::DateTime(date_time);
+    let c_mg_value = unsafe { *(query_param.to_c_mg_value()) };
+    assert_eq!(
+        c_mg_value.type_,
+        bindings::mg_value_type_MG_VALUE_TYPE_DATE_TIME
+    );
+    let c_date_time = unsafe { *bindings::mg_value_date_time(&c_mg_value) };
+    assert_eq!(
+        c_date_time.seconds,
+        NaiveDate::from_ymd(2021, 7, 14)
+            .and_hms(18, 30, 5)
+            .timestamp()
+    );
+    assert_eq!(c_date_time.tz_offset_minutes, 120);
+    let mg_value = unsafe { Value::from_mg_value(&c_mg_value) };
+    assert_eq!(mg_value, Value::DateTime(date_time));
+    assert_eq!(
+        mg_value.to_string(),
+        "'2021-07-14 18:30:05.000001234 +02:00'"
+    );
+}
+
+#[test]
+fn from_date_time_utc_param() {
+    let date_time = chrono::Utc.ymd(2021, 7, 14).and_hms(18, 30, 5);
+    match QueryParam::from(date_time) {
+        QueryParam::DateTime(x) => {
+            assert_eq!(x.offset().local_minus_utc(), 0);
+            assert_eq!(x, date_time);
+        }
+        x => panic!("Unexpected query param {:?}", x),
+    }
+}
+
 #[test]
 fn from_naive_local_date_time_param_to_mg_value() {
     let query_param =
@@ -815,16 +981,18 @@ fn from_naive_local_date_time_param_to_mg_value() {
 
 #[test]
 fn from_duration_to_mg_value_1() {
-    let query_param = QueryParam::Duration(Duration::nanoseconds(86403000000000));
+    let query_param = QueryParam::from(Duration::nanoseconds(86403000000000));
     let c_mg_value = unsafe { *(query_param.to_c_mg_value()) };
     assert_eq!(
         c_mg_value.type_,
         bindings::mg_value_type_MG_VALUE_TYPE_DURATION
     );
     let mg_value = unsafe { Value::from_mg_value(&c_mg_value) };
-    assert_eq!(mg_value.to_string(), "'PT86403S'");
+    assert_eq!(mg_value.to_string(), "'P1DT3S'");
     match mg_value {
         Value::Duration(x) => {
+            assert_eq!(x, MgDuration::new(0, 1, 3, 0));
+            let x = x.to_chrono().unwrap();
             assert_eq!(x.num_weeks(), 0);
             assert_eq!(x.num_hours(), 24);
             assert_eq!(x.num_days(), 1);
@@ -839,7 +1007,7 @@ fn from_duration_to_mg_value_1() {
 
 #[test]
 fn from_duration_to_mg_value_2() {
-    let query_param = QueryParam::Duration(Duration::nanoseconds(123456789));
+    let query_param = QueryParam::from(Duration::nanoseconds(123456789));
     let c_mg_value = unsafe { *(query_param.to_c_mg_value()) };
     assert_eq!(
         c_mg_value.type_,
@@ -849,6 +1017,7 @@ fn from_duration_to_mg_value_2() {
     assert_eq!(mg_value.to_string(), "'PT0.123456789S'");
     match mg_value {
         Value::Duration(x) => {
+            let x = x.to_chrono().unwrap();
             assert_eq!(x.num_weeks(), 0);
             assert_eq!(x.num_hours(), 0);
             assert_eq!(x.num_days(), 0);
@@ -904,3 +1073,867 @@ fn from_c_mg_value_unknown() {
         )
     };
 }
+
+#[test]
+fn param_limits_default_accepts_nested() {
+    let params = hashmap! {
+        String::from("list") => QueryParam::List(vec![QueryParam::List(vec![QueryParam::Map(
+            hashmap! { String::from("name") => QueryParam::String(String::from("test")) },
+        )])]),
+    };
+    assert!(ParamLimits::default().check(&params).is_ok());
+}
+
+#[test]
+fn param_limits_max_depth() {
+    let limits = ParamLimits {
+        max_depth: Some(2),
+        ..Default::default()
+    };
+    let ok_params = hashmap! {
+        String::from("rows") => QueryParam::List(vec![QueryParam::Map(hashmap! {
+            String::from("id") => QueryParam::Int(1),
+        })]),
+    };
+    assert!(limits.check(&ok_params).is_ok());
+
+    let params = hashmap! {
+        String::from("rows") => QueryParam::List(vec![QueryParam::Map(hashmap! {
+            String::from("tags") => QueryParam::List(vec![QueryParam::Int(1)]),
+        })]),
+    };
+    let err = limits.check(&params).err().unwrap();
+    assert_eq!(
+        format!("{}", err),
+        "Parameter $rows[0].tags exceeds maximum nesting depth of 2"
+    );
+}
+
+#[test]
+fn param_limits_max_collection_size() {
+    let limits = ParamLimits {
+        max_collection_size: 2,
+        ..Default::default()
+    };
+    let params = hashmap! {
+        String::from("ids") => QueryParam::List(vec![
+            QueryParam::Int(1),
+            QueryParam::Int(2),
+            QueryParam::Int(3),
+        ]),
+    };
+    let err = limits.check(&params).err().unwrap();
+    assert_eq!(
+        format!("{}", err),
+        "Parameter $ids has 3 elements which exceeds maximum of 2"
+    );
+}
+
+#[test]
+fn param_limits_max_string_length() {
+    let limits = ParamLimits {
+        max_string_length: 3,
+        ..Default::default()
+    };
+    let params = hashmap! {
+        String::from("name") => QueryParam::String(String::from("Alice")),
+    };
+    let err = limits.check(&params).err().unwrap();
+    assert_eq!(
+        format!("{}", err),
+        "Parameter $name has length 5 which exceeds maximum string length of 3"
+    );
+}
+
+#[test]
+fn query_param_from() {
+    assert!(matches!(QueryParam::from(true), QueryParam::Bool(true)));
+    assert!(matches!(QueryParam::from(3), QueryParam::Int(3)));
+    assert!(matches!(QueryParam::from(3u32), QueryParam::Int(3)));
+    assert!(matches!(QueryParam::from(1.5), QueryParam::Float(x) if x == 1.5));
+    assert!(matches!(QueryParam::from("abc"), QueryParam::String(x) if x == "abc"));
+    assert!(matches!(QueryParam::from(None::<i64>), QueryParam::Null));
+    assert!(matches!(QueryParam::from(Some(5)), QueryParam::Int(5)));
+    assert!(matches!(
+        QueryParam::from(NaiveDate::from_ymd(2020, 1, 1)),
+        QueryParam::Date(x) if x == NaiveDate::from_ymd(2020, 1, 1)
+    ));
+    match QueryParam::from(vec![1, 2]) {
+        QueryParam::List(x) => {
+            assert_eq!(x.len(), 2);
+            assert!(matches!(x[1], QueryParam::Int(2)));
+        }
+        x => panic!("Unexpected query param {:?}", x),
+    }
+    match QueryParam::from(hashmap! { "a" => 1 }) {
+        QueryParam::Map(x) => assert!(matches!(x.get("a"), Some(QueryParam::Int(1)))),
+        x => panic!("Unexpected query param {:?}", x),
+    }
+}
+
+#[test]
+fn params_macro() {
+    assert!(params! {}.is_empty());
+
+    let params = params! {
+        "name" => "Alice",
+        String::from("age") => 32,
+        "tags" => vec!["a", "b"],
+        "address" => params! { "city" => "Zagreb" },
+    };
+    assert_eq!(params.len(), 4);
+    assert!(matches!(params.get("name"), Some(QueryParam::String(x)) if x == "Alice"));
+    assert!(matches!(params.get("age"), Some(QueryParam::Int(32))));
+    assert!(matches!(params.get("tags"), Some(QueryParam::List(x)) if x.len() == 2));
+    match params.get("address") {
+        Some(QueryParam::Map(x)) => {
+            assert!(matches!(x.get("city"), Some(QueryParam::String(x)) if x == "Zagreb"))
+        }
+        x => panic!("Unexpected query param {:?}", x),
+    }
+}
+
+#[test]
+fn batch_builder() {
+    let mut batch = BatchBuilder::new();
+    assert!(batch.is_empty());
+    assert!(batch
+        .add_row(params! { "id" => 1, "name" => None::<String> })
+        .is_ok());
+    assert!(batch
+        .add_row(params! { "id" => 2, "name" => "Bob" })
+        .is_ok());
+    assert!(batch
+        .add_row(params! { "id" => None::<i64>, "name" => "Carol" })
+        .is_ok());
+
+    let err = batch.add_row(params! { "id" => 4 }).err().unwrap();
+    assert_eq!(format!("{}", err), "Row 3 is missing key `name`");
+    let err = batch
+        .add_row(params! { "id" => 4, "name" => "Dave", "age" => 30 })
+        .err()
+        .unwrap();
+    assert_eq!(format!("{}", err), "Row 3 has unexpected key `age`");
+    let err = batch
+        .add_row(params! { "id" => 4, "name" => 5 })
+        .err()
+        .unwrap();
+    assert_eq!(
+        format!("{}", err),
+        "Row 3 has value of type Int for key `name`, expected String"
+    );
+    assert_eq!(batch.len(), 3);
+
+    match batch.build() {
+        QueryParam::List(rows) => {
+            assert_eq!(rows.len(), 3);
+            assert!(matches!(&rows[1], QueryParam::Map(x) if x.len() == 2));
+        }
+        x => panic!("Unexpected query param {:?}", x),
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serialize_value() {
+    let value = Value::List(vec![
+        Value::Null,
+        Value::Int(1),
+        Value::Date(NaiveDate::from_ymd(2020, 1, 31)),
+        Value::LocalDateTime(NaiveDate::from_ymd(2020, 1, 31).and_hms_milli(12, 30, 0, 500)),
+        Value::Node(Node {
+            id: 1,
+            label_count: 1,
+            labels: vec![String::from("Person")],
+            properties: hashmap! { Arc::from("name") => Value::String(String::from("Alice")) },
+        }),
+        Value::Relationship(Relationship {
+            id: 2,
+            start_id: 1,
+            end_id: 3,
+            type_: String::from("KNOWS"),
+            properties: HashMap::new(),
+        }),
+    ]);
+    assert_eq!(
+        serde_json::to_value(&value).unwrap(),
+        serde_json::json!([
+            null,
+            1,
+            "2020-01-31",
+            "2020-01-31T12:30:00.500",
+            {"id": 1, "labels": ["Person"], "properties": {"name": "Alice"}},
+            {"id": 2, "start_id": 1, "end_id": 3, "type": "KNOWS", "properties": {}},
+        ])
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn query_param_serde_round_trip() {
+    let json = serde_json::json!({
+        "name": "Alice",
+        "age": 32,
+        "score": 1.5,
+        "active": true,
+        "manager": null,
+        "tags": ["a", "b"],
+        "address": {"city": "Zagreb"},
+    });
+    let param: QueryParam = serde_json::from_value(json.clone()).unwrap();
+    match &param {
+        QueryParam::Map(x) => {
+            assert!(matches!(x.get("age"), Some(QueryParam::Int(32))));
+            assert!(matches!(x.get("manager"), Some(QueryParam::Null)));
+            assert!(matches!(x.get("tags"), Some(QueryParam::List(x)) if x.len() == 2));
+        }
+        x => panic!("Unexpected query param {:?}", x),
+    }
+    assert_eq!(serde_json::to_value(&param).unwrap(), json);
+
+    assert!(serde_json::from_value::<QueryParam>(serde_json::json!(u64::MAX)).is_err());
+}
+
+#[test]
+fn from_c_mg_value_date_out_of_range() {
+    let c_date = bindings::mg_date { days: i64::MAX };
+    let c_mg_value = unsafe { bindings::mg_value_make_date(bindings::mg_date_copy(&c_date)) };
+    assert_eq!(unsafe { Value::from_mg_value(c_mg_value) }, Value::Null);
+    let err = unsafe { Value::try_from_mg_value(c_mg_value) }
+        .err()
+        .unwrap();
+    assert!(format!("{}", err).contains("out of range"));
+}
+
+#[test]
+fn from_c_mg_value_local_time_out_of_range() {
+    let c_local_time = bindings::mg_local_time {
+        nanoseconds: 25 * 3600 * 1_000_000_000,
+    };
+    let c_mg_value =
+        unsafe { bindings::mg_value_make_local_time(bindings::mg_local_time_copy(&c_local_time)) };
+    assert_eq!(unsafe { Value::from_mg_value(c_mg_value) }, Value::Null);
+    assert!(unsafe { Value::try_from_mg_value(c_mg_value) }.is_err());
+}
+
+#[test]
+fn try_mg_list_to_vec_nested_error() {
+    let c_date = bindings::mg_date { days: i64::MIN };
+    let c_mg_list = unsafe {
+        let mg_list = bindings::mg_list_make_empty(2);
+        bindings::mg_list_append(mg_list, bindings::mg_value_make_integer(1));
+        bindings::mg_list_append(
+            mg_list,
+            bindings::mg_value_make_date(bindings::mg_date_copy(&c_date)),
+        );
+        mg_list
+    };
+    let mut keys = KeyInterner::default();
+    assert_eq!(
+        unsafe { mg_list_to_vec(c_mg_list, &mut keys) },
+        vec![Value::Int(1), Value::Null]
+    );
+    assert!(unsafe { try_mg_list_to_vec(c_mg_list, &mut keys) }.is_err());
+}
+
+#[test]
+fn record_ref_borrows_values() {
+    let columns = vec![
+        String::from("name"),
+        String::from("tags"),
+        String::from("date"),
+    ];
+    let date = NaiveDate::from_ymd(2020, 1, 1);
+    let values = vec![
+        Value::String(String::from("Alice")),
+        Value::List(vec![Value::String(String::from("a")), Value::Null]),
+        Value::Date(date),
+    ];
+    let c_mg_list = vector_to_mg_list(&values);
+    let record = unsafe { RecordRef::new(&columns, c_mg_list) };
+
+    assert_eq!(record.len(), 3);
+    match record.get_by_name("name") {
+        Some(ValueRef::String(x)) => assert_eq!(x, "Alice"),
+        x => panic!("Unexpected value {:?}", x),
+    }
+    match record.get(1) {
+        Some(ValueRef::List(x)) => {
+            assert_eq!(x.len(), 2);
+            assert!(matches!(x.get(0), Some(ValueRef::String("a"))));
+            assert!(matches!(x.get(1), Some(ValueRef::Null)));
+            assert!(x.get(2).is_none());
+        }
+        x => panic!("Unexpected value {:?}", x),
+    }
+    assert!(matches!(record.get(2), Some(ValueRef::Other(Value::Date(x))) if x == date));
+    assert!(record.get(3).is_none());
+    assert_eq!(record.to_values(), values);
+}
+
+#[test]
+fn key_interner_shares_keys() {
+    let mut keys = KeyInterner::default();
+    let name = keys.intern(String::from("name"));
+    assert!(Arc::ptr_eq(&name, &keys.intern(String::from("name"))));
+    assert!(!Arc::ptr_eq(&name, &keys.intern(String::from("age"))));
+
+    for i in 0..MAX_INTERNED_KEYS {
+        keys.intern(format!("key{}", i));
+    }
+    assert_eq!(keys.keys.len(), MAX_INTERNED_KEYS);
+    let extra = keys.intern(String::from("extra"));
+    assert_eq!(&*extra, "extra");
+    assert!(!Arc::ptr_eq(&extra, &keys.intern(String::from("extra"))));
+    assert!(Arc::ptr_eq(&name, &keys.intern(String::from("name"))));
+}
+
+#[test]
+fn mg_list_to_vec_interns_property_keys() {
+    let node = |id: i64| {
+        Value::Node(Node {
+            id,
+            label_count: 0,
+            labels: vec![],
+            properties: hashmap! { Arc::from("name") => Value::Int(id) },
+        })
+    };
+    let c_mg_list = vector_to_mg_list(&[node(1), node(2)]);
+    let values = unsafe { mg_list_to_vec(c_mg_list, &mut KeyInterner::default()) };
+    let key = |value: &Value| match value {
+        Value::Node(x) => x.properties.keys().next().unwrap().clone(),
+        _ => panic!("Expected node"),
+    };
+    assert!(Arc::ptr_eq(&key(&values[0]), &key(&values[1])));
+}
+
+#[test]
+fn value_try_into() {
+    assert!(bool::try_from(Value::Bool(true)).unwrap());
+    assert_eq!(i64::try_from(Value::Int(3)).unwrap(), 3);
+    assert_eq!(
+        String::try_from(Value::String(String::from("a"))).unwrap(),
+        "a"
+    );
+    assert_eq!(Option::<i64>::try_from(Value::Null).unwrap(), None);
+    assert_eq!(Option::<i64>::try_from(Value::Int(1)).unwrap(), Some(1));
+    assert_eq!(
+        i64::try_from(Value::String(String::from("1")))
+            .unwrap_err()
+            .to_string(),
+        "Can't convert String value to i64"
+    );
+}
+
+#[test]
+fn record_into_map_and_iter() {
+    let columns = Arc::new(vec![String::from("a"), String::from("b")]);
+    let record = Record::new(columns, vec![Value::Int(1), Value::Bool(false)]);
+    assert_eq!(record.columns(), ["a", "b"]);
+    let map = record.into_map();
+    assert_eq!(map.len(), 2);
+    assert_eq!(map["a"], Value::Int(1));
+    assert_eq!(map["b"], Value::Bool(false));
+
+    let record = Record::new(Arc::default(), vec![Value::Int(1), Value::Null]);
+    let values: Vec<Value> = record.into_iter().collect();
+    assert_eq!(values, vec![Value::Int(1), Value::Null]);
+}
+
+#[test]
+fn record_try_into_tuple() {
+    let record = Record::new(
+        Arc::default(),
+        vec![Value::Int(1), Value::String(String::from("a")), Value::Null],
+    );
+    let (x, y, z): (i64, String, Option<f64>) = record.try_into().unwrap();
+    assert_eq!((x, y, z), (1, String::from("a"), None));
+
+    let record = Record::new(Arc::default(), vec![Value::Int(1)]);
+    let err = <(i64, i64)>::try_from(record).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Can't convert record with 1 values to tuple of 2 elements"
+    );
+
+    let record = Record::new(Arc::default(), vec![Value::Int(1), Value::Bool(true)]);
+    assert!(<(i64, i64)>::try_from(record).is_err());
+}
+
+#[test]
+fn record_index() {
+    let record = Record::new(
+        Arc::new(vec![String::from("name"), String::from("age")]),
+        vec![Value::String(String::from("Alice")), Value::Int(30)],
+    );
+    assert_eq!(record["name"], Value::String(String::from("Alice")));
+    assert_eq!(record["age"], Value::Int(30));
+    assert_eq!(record[1], Value::Int(30));
+    assert_eq!(record.get("age"), Some(&Value::Int(30)));
+    assert_eq!(record.get("email"), None);
+}
+
+#[test]
+#[should_panic(expected = "No column named email")]
+fn record_index_missing_column() {
+    let record = Record::new(Arc::new(vec![String::from("name")]), vec![Value::Null]);
+    let _ = &record["email"];
+}
+
+#[test]
+fn record_from_record() {
+    let record = Record::new(
+        Arc::default(),
+        vec![Value::String(String::from("a")), Value::Int(1)],
+    );
+    let (x, y) = <(String, i64)>::from_record(record).unwrap();
+    assert_eq!((x.as_str(), y), ("a", 1));
+    assert!(<(String, i64)>::COLUMNS.is_empty());
+}
+
+fn query_param_round_trip(query_param: &QueryParam) -> Value {
+    let c_mg_value = unsafe { *(query_param.to_c_mg_value()) };
+    unsafe { Value::from_mg_value(&c_mg_value) }
+}
+
+#[test]
+fn temporal_nanoseconds_round_trip() {
+    for nanoseconds in [0, 1, 100, 999, 1_000, 123_456_789, 999_999_999] {
+        let time = NaiveTime::from_hms_nano(23, 59, 59, nanoseconds);
+        let offset = FixedOffset::west(9 * 60 * 60 + 30 * 60);
+        assert_eq!(
+            query_param_round_trip(&QueryParam::LocalTime(time)),
+            Value::LocalTime(time)
+        );
+        assert_eq!(
+            query_param_round_trip(&QueryParam::Time(time, offset)),
+            Value::Time(time, offset)
+        );
+        for date in [
+            NaiveDate::from_ymd(1969, 12, 31),
+            NaiveDate::from_ymd(1970, 1, 1),
+            NaiveDate::from_ymd(1900, 2, 28),
+            NaiveDate::from_ymd(2262, 4, 11),
+        ] {
+            let local = date.and_time(time);
+            assert_eq!(
+                query_param_round_trip(&QueryParam::LocalDateTime(local)),
+                Value::LocalDateTime(local)
+            );
+            let date_time = offset.from_local_datetime(&local).unwrap();
+            assert_eq!(
+                query_param_round_trip(&QueryParam::DateTime(date_time)),
+                Value::DateTime(date_time)
+            );
+        }
+        for duration in [
+            MgDuration::new(0, 0, 0, nanoseconds as i64),
+            MgDuration::new(0, 0, -1, -(nanoseconds as i64)),
+            MgDuration::new(3, -2, 59, nanoseconds as i64),
+        ] {
+            assert_eq!(
+                query_param_round_trip(&QueryParam::Duration(duration)),
+                Value::Duration(duration)
+            );
+        }
+    }
+}
+
+#[test]
+fn temporal_leap_second_clamped() {
+    let leap_second = NaiveTime::from_hms_nano(23, 59, 59, 1_500_000_000);
+    let clamped = NaiveTime::from_hms_nano(23, 59, 59, 999_999_999);
+    assert_eq!(
+        query_param_round_trip(&QueryParam::LocalTime(leap_second)),
+        Value::LocalTime(clamped)
+    );
+    let date = NaiveDate::from_ymd(2016, 12, 31);
+    assert_eq!(
+        query_param_round_trip(&QueryParam::LocalDateTime(date.and_time(leap_second))),
+        Value::LocalDateTime(date.and_time(clamped))
+    );
+    let offset = FixedOffset::east(0);
+    assert_eq!(
+        query_param_round_trip(&QueryParam::DateTime(
+            offset
+                .from_local_datetime(&date.and_time(leap_second))
+                .unwrap()
+        )),
+        Value::DateTime(offset.from_local_datetime(&date.and_time(clamped)).unwrap())
+    );
+}
+
+#[test]
+fn value_display_with_options() {
+    let local = NaiveDate::from_ymd(2021, 7, 14).and_hms_nano(18, 30, 5, 120_000);
+    let utc = FixedOffset::east(0).from_local_datetime(&local).unwrap();
+    let west = FixedOffset::west(5 * 60 * 60 + 30 * 60);
+    let values = Value::List(vec![
+        Value::Date(local.date()),
+        Value::LocalTime(local.time()),
+        Value::Time(local.time(), west),
+        Value::LocalDateTime(local),
+        Value::DateTime(utc),
+        Value::Duration(MgDuration::new(0, 1, 3, 5)),
+        Value::Int(7),
+    ]);
+
+    assert_eq!(
+        values.display_with(&DisplayOptions::default()).to_string(),
+        values.to_string()
+    );
+    let iso8601 = DisplayOptions {
+        temporal_format: TemporalFormat::Iso8601,
+        subsecond_digits: None,
+    };
+    assert_eq!(
+        values.display_with(&iso8601).to_string(),
+        "'2021-07-14', '18:30:05.000120', '18:30:05.000120-05:30', \
+         '2021-07-14T18:30:05.000120', '2021-07-14T18:30:05.000120+00:00', 'P1DT3.000000005S', 7"
+    );
+    let rfc3339 = DisplayOptions {
+        temporal_format: TemporalFormat::Rfc3339,
+        subsecond_digits: Some(3),
+    };
+    assert_eq!(
+        Value::DateTime(utc).display_with(&rfc3339).to_string(),
+        "'2021-07-14T18:30:05.000Z'"
+    );
+    let map = Value::Map(hashmap! {
+        String::from("at") => Value::LocalTime(NaiveTime::from_hms(1, 2, 3)),
+    });
+    assert_eq!(
+        map.display_with(&rfc3339).to_string(),
+        "{'at': '01:02:03.000'}"
+    );
+    let whole_seconds = DisplayOptions {
+        subsecond_digits: Some(0),
+        ..rfc3339
+    };
+    assert_eq!(
+        Value::Time(local.time(), west)
+            .display_with(&whole_seconds)
+            .to_string(),
+        "'18:30:05-05:30'"
+    );
+    assert_eq!(
+        Value::LocalTime(NaiveTime::from_hms_nano(23, 59, 59, 1_000_000_001))
+            .display_with(&iso8601)
+            .to_string(),
+        "'23:59:60.000000001'"
+    );
+
+    let custom = DisplayOptions {
+        temporal_format: TemporalFormat::Custom {
+            date: String::from("%d.%m.%Y"),
+            time: String::from("%H:%M %:z"),
+            date_time: String::from("%d.%m.%Y %H:%M:%S%.9f"),
+        },
+        subsecond_digits: None,
+    };
+    assert_eq!(
+        Value::Date(local.date()).display_with(&custom).to_string(),
+        "'14.07.2021'"
+    );
+    assert_eq!(
+        Value::Time(local.time(), west)
+            .display_with(&custom)
+            .to_string(),
+        "'18:30 -05:30'"
+    );
+    assert_eq!(
+        Value::DateTime(utc).display_with(&custom).to_string(),
+        "'14.07.2021 18:30:05.000120000'"
+    );
+    // Offset can't be formatted for a local time, so it falls back to the default format.
+    assert_eq!(
+        Value::LocalTime(local.time())
+            .display_with(&custom)
+            .to_string(),
+        "'18:30:05.000120'"
+    );
+
+    // Properties of graph elements are formatted using the options too.
+    let node = Node {
+        id: 1,
+        label_count: 1,
+        labels: vec![String::from("Event")],
+        properties: hashmap! {
+            Arc::from("at") => Value::DateTime(utc),
+        },
+    };
+    assert_eq!(
+        Value::Node(node.clone()).display_with(&iso8601).to_string(),
+        "(:Event {'at': '2021-07-14T18:30:05.000120+00:00'})"
+    );
+    let relationship = UnboundRelationship {
+        id: 2,
+        type_: String::from("NEXT"),
+        properties: hashmap! {
+            Arc::from("on") => Value::Date(local.date()),
+        },
+    };
+    let path = Value::Path(Path {
+        node_count: 2,
+        relationship_count: 1,
+        nodes: vec![node.clone(), node],
+        relationships: vec![relationship],
+        sequence: vec![1, 1],
+    });
+    assert_eq!(
+        path.display_with(&custom).to_string(),
+        "(:Event {'at': '14.07.2021 18:30:05.000120000'})-[:NEXT {'on': '14.07.2021'}]->\
+         (:Event {'at': '14.07.2021 18:30:05.000120000'})"
+    );
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn time_crate_conversions() {
+    use ::time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+    let date = Date::from_calendar_date(1969, Month::December, 31).unwrap();
+    let time = Time::from_hms_nano(23, 59, 59, 999_999_999).unwrap();
+    let offset = UtcOffset::from_hms(-5, -30, 0).unwrap();
+    let local = PrimitiveDateTime::new(date, time);
+    let date_time = local.assume_offset(offset);
+    let duration = ::time::Duration::new(-90000, -500_000_000);
+
+    let value = query_param_round_trip(&QueryParam::from(date));
+    assert_eq!(value, Value::Date(NaiveDate::from_ymd(1969, 12, 31)));
+    assert_eq!(Date::try_from(value).unwrap(), date);
+    let value = query_param_round_trip(&QueryParam::from(time));
+    assert_eq!(Time::try_from(value).unwrap(), time);
+    let value = query_param_round_trip(&QueryParam::try_from((time, offset)).unwrap());
+    assert_eq!(
+        <(Time, UtcOffset)>::try_from(value).unwrap(),
+        (time, offset)
+    );
+    let value = query_param_round_trip(&QueryParam::from(local));
+    assert_eq!(PrimitiveDateTime::try_from(value).unwrap(), local);
+    let value = query_param_round_trip(&QueryParam::try_from(date_time).unwrap());
+    assert_eq!(OffsetDateTime::try_from(value).unwrap(), date_time);
+    let value = query_param_round_trip(&QueryParam::from(duration));
+    assert_eq!(
+        value,
+        Value::Duration(MgDuration::new(0, -1, -3600, -500_000_000))
+    );
+    assert_eq!(::time::Duration::try_from(value).unwrap(), duration);
+
+    assert_eq!(
+        Date::try_from(Value::Date(NaiveDate::from_ymd(10000, 1, 1)))
+            .unwrap_err()
+            .to_string(),
+        "Can't convert Date value '+10000-01-01' to time::Date, it is out of range"
+    );
+    assert_eq!(
+        ::time::Duration::try_from(Value::Duration(MgDuration::new(1, 0, 0, 0)))
+            .unwrap_err()
+            .to_string(),
+        "Can't convert Duration value 'P1M' to time::Duration, it is out of range"
+    );
+    assert_eq!(
+        Date::try_from(Value::Int(1)).unwrap_err().to_string(),
+        "Can't convert Int value to time::Date"
+    );
+    let offset = UtcOffset::from_hms(25, 0, 0).unwrap();
+    assert_eq!(
+        QueryParam::try_from(local.assume_offset(offset))
+            .unwrap_err()
+            .to_string(),
+        "Can't convert offset +25:00:00 to QueryParam, it is out of range"
+    );
+    assert!(QueryParam::try_from((time, offset)).is_err());
+}
+
+#[cfg(feature = "jiff")]
+#[test]
+fn jiff_crate_conversions() {
+    use ::jiff::civil::{self, Date, Time};
+    use ::jiff::tz::Offset;
+    use ::jiff::{SignedDuration, Timestamp, Zoned};
+
+    let date = Date::new(1969, 12, 31).unwrap();
+    let time = Time::new(23, 59, 59, 999_999_999).unwrap();
+    let offset = Offset::from_seconds(-(5 * 60 * 60 + 30 * 60)).unwrap();
+    let local = civil::DateTime::from_parts(date, time);
+    let zoned = offset
+        .to_timestamp(local)
+        .unwrap()
+        .to_zoned(::jiff::tz::TimeZone::fixed(offset));
+    let duration = SignedDuration::new(-90000, -500_000_000);
+
+    let value = query_param_round_trip(&QueryParam::from(date));
+    assert_eq!(value, Value::Date(NaiveDate::from_ymd(1969, 12, 31)));
+    assert_eq!(Date::try_from(value).unwrap(), date);
+    let value = query_param_round_trip(&QueryParam::from(time));
+    assert_eq!(Time::try_from(value).unwrap(), time);
+    let value = query_param_round_trip(&QueryParam::try_from((time, offset)).unwrap());
+    assert_eq!(<(Time, Offset)>::try_from(value).unwrap(), (time, offset));
+    let value = query_param_round_trip(&QueryParam::from(local));
+    assert_eq!(civil::DateTime::try_from(value).unwrap(), local);
+    let value = query_param_round_trip(&QueryParam::try_from(&zoned).unwrap());
+    assert_eq!(
+        value,
+        Value::DateTime(
+            FixedOffset::west(5 * 60 * 60 + 30 * 60)
+                .ymd(1969, 12, 31)
+                .and_hms_nano(23, 59, 59, 999_999_999)
+        )
+    );
+    assert_eq!(
+        Timestamp::try_from(value.clone()).unwrap(),
+        zoned.timestamp()
+    );
+    assert_eq!(Zoned::try_from(value).unwrap(), zoned);
+    let value = query_param_round_trip(&QueryParam::from(zoned.timestamp()));
+    assert_eq!(Timestamp::try_from(value).unwrap(), zoned.timestamp());
+    let value = query_param_round_trip(&QueryParam::from(duration));
+    assert_eq!(
+        value,
+        Value::Duration(MgDuration::new(0, -1, -3600, -500_000_000))
+    );
+    assert_eq!(SignedDuration::try_from(value).unwrap(), duration);
+
+    assert!(Date::try_from(Value::Date(NaiveDate::from_ymd(10000, 1, 1))).is_err());
+    assert_eq!(
+        Zoned::try_from(Value::Null).unwrap_err().to_string(),
+        "Can't convert Null value to jiff::Zoned"
+    );
+    let offset = Offset::from_seconds(25 * 60 * 60).unwrap();
+    assert!(QueryParam::try_from((time, offset)).is_err());
+    assert!(
+        QueryParam::try_from(zoned.with_time_zone(::jiff::tz::TimeZone::fixed(offset))).is_err()
+    );
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn uuid_conversions() {
+    let id = ::uuid::Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+
+    let value = query_param_round_trip(&QueryParam::from(id));
+    assert_eq!(
+        value,
+        Value::String(String::from("67e55044-10b1-426f-9247-bb680e5fe0c8"))
+    );
+    assert_eq!(value.try_into_uuid().unwrap(), id);
+    let value = query_param_round_trip(&QueryParam::uuid_bytes(id));
+    assert_eq!(
+        value,
+        Value::List(
+            id.as_bytes()
+                .iter()
+                .map(|x| Value::Int(i64::from(*x)))
+                .collect()
+        )
+    );
+    assert_eq!(::uuid::Uuid::try_from(value).unwrap(), id);
+    let value = Value::String(String::from("67E5504410B1426F9247BB680E5FE0C8"));
+    assert_eq!(value.try_into_uuid().unwrap(), id);
+
+    assert!(Value::String(String::from("67e55044"))
+        .try_into_uuid()
+        .unwrap_err()
+        .to_string()
+        .starts_with("Invalid UUID '67e55044': "));
+    assert_eq!(
+        Value::List(vec![Value::Int(256); 16])
+            .try_into_uuid()
+            .unwrap_err()
+            .to_string(),
+        format!("Invalid UUID bytes {}", vec!["256"; 16].join(", "))
+    );
+    assert_eq!(
+        Value::Int(1).try_into_uuid().unwrap_err().to_string(),
+        "Can't convert Int value to Uuid"
+    );
+}
+
+#[cfg(feature = "rust_decimal")]
+#[test]
+fn decimal_conversions() {
+    use rust_decimal::Decimal;
+
+    let decimal = Decimal::new(-12345, 2);
+    let value = query_param_round_trip(&QueryParam::try_from(decimal).unwrap());
+    assert_eq!(value, Value::Float(-123.45));
+    assert_eq!(Decimal::try_from(value).unwrap(), decimal);
+    assert_eq!(
+        Decimal::try_from(Value::Float(0.1)).unwrap(),
+        Decimal::new(1, 1)
+    );
+    assert_eq!(
+        Decimal::try_from(Value::Int(-7)).unwrap(),
+        Decimal::new(-7, 0)
+    );
+    let precise = Decimal::from_str_exact("0.12345678901234567890").unwrap();
+    assert_eq!(
+        Decimal::try_from(Value::String(precise.to_string())).unwrap(),
+        precise
+    );
+
+    assert_eq!(
+        QueryParam::try_from(precise).unwrap_err().to_string(),
+        "Decimal 0.12345678901234567890 can't be represented exactly as Float, pass it as a \
+         string instead"
+    );
+    assert_eq!(
+        Decimal::try_from(Value::Float(1e30))
+            .unwrap_err()
+            .to_string(),
+        "Can't convert Float value 1000000000000000000000000000000 to Decimal, it is out of range"
+    );
+    assert!(Decimal::try_from(Value::Float(f64::NAN)).is_err());
+    assert!(Decimal::try_from(Value::String(String::from("1.5x"))).is_err());
+    assert_eq!(
+        Decimal::try_from(Value::Null).unwrap_err().to_string(),
+        "Can't convert Null value to Decimal"
+    );
+}
+
+#[cfg(feature = "num-bigint")]
+#[test]
+fn big_int_conversions() {
+    use num_bigint::BigInt;
+
+    let value = query_param_round_trip(&QueryParam::try_from(BigInt::from(i64::MIN)).unwrap());
+    assert_eq!(value, Value::Int(i64::MIN));
+    assert_eq!(BigInt::try_from(value).unwrap(), BigInt::from(i64::MIN));
+    let big: BigInt = "123456789012345678901234567890".parse().unwrap();
+    assert_eq!(
+        BigInt::try_from(Value::String(big.to_string())).unwrap(),
+        big
+    );
+    assert_eq!(
+        BigInt::try_from(Value::Float(-1e20)).unwrap(),
+        "-100000000000000000000".parse::<BigInt>().unwrap()
+    );
+    assert_eq!(
+        BigInt::try_from(Value::Float(2f64.powi(70))).unwrap(),
+        BigInt::from(1) << 70
+    );
+    assert_eq!(
+        BigInt::try_from(Value::Float(3.0)).unwrap(),
+        BigInt::from(3)
+    );
+    assert_eq!(
+        BigInt::try_from(Value::Float(-0.0)).unwrap(),
+        BigInt::from(0)
+    );
+
+    assert_eq!(
+        QueryParam::try_from(big).unwrap_err().to_string(),
+        "BigInt 123456789012345678901234567890 is out of range of Int, pass it as a string \
+         instead"
+    );
+    assert_eq!(
+        BigInt::try_from(Value::Float(1.5)).unwrap_err().to_string(),
+        "Can't convert Float value 1.5 to BigInt, it isn't a whole number"
+    );
+    assert!(BigInt::try_from(Value::Float(f64::INFINITY)).is_err());
+    assert_eq!(
+        BigInt::try_from(Value::Bool(true)).unwrap_err().to_string(),
+        "Can't convert Bool value to BigInt"
+    );
+}
@@ -0,0 +1,156 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversions of `time` crate types, enabled by the `time` feature.
+//!
+//! `QueryParam`s are created using `From`, e.g. `QueryParam::from(time::Date)`, and values are
+//! converted using `TryFrom`, e.g. `time::Date::try_from(value)`. Conversions of values fail if
+//! they are out of range of the `time` types, e.g. years beyond 9999 or durations with months.
+//!
+//! Times and date times with an offset are converted to `QueryParam` using `TryFrom`, which fails
+//! for offsets of 24 hours or more, out of range of `chrono`. Creating a `QueryParam` panics if
+//! the date is out of range of `chrono`, which is only possible for years beyond 262143 with the
+//! `large-dates` feature of `time`.
+
+use super::{MgDuration, QueryParam, Value};
+use crate::error::MgError;
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+use chrono::{Offset, Timelike};
+use std::convert::TryFrom;
+use time::{Date, Duration, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+fn from_date(x: Date) -> NaiveDate {
+    NaiveDate::from_yo_opt(x.year(), u32::from(x.ordinal())).expect("date out of range of chrono")
+}
+
+fn from_time(x: Time) -> NaiveTime {
+    let (hour, minute, second, nanosecond) = x.as_hms_nano();
+    NaiveTime::from_hms_nano_opt(
+        u32::from(hour),
+        u32::from(minute),
+        u32::from(second),
+        nanosecond,
+    )
+    .expect("time out of range of chrono")
+}
+
+fn from_offset(x: UtcOffset) -> Result<FixedOffset, MgError> {
+    FixedOffset::east_opt(x.whole_seconds()).ok_or_else(|| {
+        MgError::new(format!(
+            "Can't convert offset {} to QueryParam, it is out of range",
+            x
+        ))
+    })
+}
+
+fn from_primitive_date_time(x: PrimitiveDateTime) -> NaiveDateTime {
+    NaiveDateTime::new(from_date(x.date()), from_time(x.time()))
+}
+
+fn from_offset_date_time(x: OffsetDateTime) -> Result<DateTime<FixedOffset>, MgError> {
+    let local = NaiveDateTime::new(from_date(x.date()), from_time(x.time()));
+    Ok(from_offset(x.offset())?
+        .from_local_datetime(&local)
+        .single()
+        .expect("date time out of range of chrono"))
+}
+
+fn to_date(x: &NaiveDate) -> Option<Date> {
+    Date::from_ordinal_date(x.year(), u16::try_from(x.ordinal()).ok()?).ok()
+}
+
+fn to_time(x: &NaiveTime) -> Option<Time> {
+    Time::from_hms_nano(
+        u8::try_from(x.hour()).ok()?,
+        u8::try_from(x.minute()).ok()?,
+        u8::try_from(x.second()).ok()?,
+        x.nanosecond(),
+    )
+    .ok()
+}
+
+fn to_offset(x: &FixedOffset) -> Option<UtcOffset> {
+    UtcOffset::from_whole_seconds(x.local_minus_utc()).ok()
+}
+
+fn to_primitive_date_time(x: &NaiveDateTime) -> Option<PrimitiveDateTime> {
+    Some(PrimitiveDateTime::new(
+        to_date(&x.date())?,
+        to_time(&x.time())?,
+    ))
+}
+
+fn to_offset_date_time(x: &DateTime<FixedOffset>) -> Option<OffsetDateTime> {
+    let local = to_primitive_date_time(&x.naive_local())?;
+    Some(local.assume_offset(to_offset(&x.offset().fix())?))
+}
+
+fn to_duration(x: &MgDuration) -> Option<Duration> {
+    let (seconds, nanoseconds) = x.split_seconds()?;
+    Duration::seconds(seconds).checked_add(Duration::nanoseconds(nanoseconds))
+}
+
+impl From<Date> for QueryParam {
+    fn from(x: Date) -> Self {
+        QueryParam::Date(from_date(x))
+    }
+}
+
+impl From<Time> for QueryParam {
+    fn from(x: Time) -> Self {
+        QueryParam::LocalTime(from_time(x))
+    }
+}
+
+impl TryFrom<(Time, UtcOffset)> for QueryParam {
+    type Error = MgError;
+
+    fn try_from(x: (Time, UtcOffset)) -> Result<Self, Self::Error> {
+        Ok(QueryParam::Time(from_time(x.0), from_offset(x.1)?))
+    }
+}
+
+impl From<PrimitiveDateTime> for QueryParam {
+    fn from(x: PrimitiveDateTime) -> Self {
+        QueryParam::LocalDateTime(from_primitive_date_time(x))
+    }
+}
+
+impl TryFrom<OffsetDateTime> for QueryParam {
+    type Error = MgError;
+
+    fn try_from(x: OffsetDateTime) -> Result<Self, Self::Error> {
+        Ok(QueryParam::DateTime(from_offset_date_time(x)?))
+    }
+}
+
+impl From<Duration> for QueryParam {
+    fn from(x: Duration) -> Self {
+        QueryParam::Duration(MgDuration::from_seconds(
+            x.whole_seconds(),
+            i64::from(x.subsec_nanoseconds()),
+        ))
+    }
+}
+
+impl_try_from_value_checked! {
+    Date, "time::Date": Value::Date(x) => to_date(x);
+    Time, "time::Time": Value::LocalTime(x) => to_time(x);
+    (Time, UtcOffset), "(time::Time, time::UtcOffset)":
+        Value::Time(time, offset) => to_time(time).zip(to_offset(offset));
+    PrimitiveDateTime, "time::PrimitiveDateTime":
+        Value::LocalDateTime(x) => to_primitive_date_time(x);
+    OffsetDateTime, "time::OffsetDateTime": Value::DateTime(x) => to_offset_date_time(x);
+    Duration, "time::Duration": Value::Duration(x) => to_duration(x);
+}
@@ -0,0 +1,87 @@
+// Copyright (c) 2016-2022 Memgraph Ltd. [https://memgraph.com]
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversions of UUIDs, enabled by the `uuid` feature.
+//!
+//! Memgraph has no UUID type, so UUIDs are stored either as strings in the hyphenated form, e.g.
+//! `67e55044-10b1-426f-9247-bb680e5fe0c8`, or as lists of 16 byte integers.
+
+use super::{QueryParam, Value};
+use crate::error::MgError;
+use std::convert::TryFrom;
+use uuid::Uuid;
+
+/// Converts to a string in the hyphenated form.
+impl From<Uuid> for QueryParam {
+    fn from(x: Uuid) -> Self {
+        QueryParam::String(x.hyphenated().to_string())
+    }
+}
+
+impl QueryParam {
+    /// Returns UUID as a list of its 16 bytes, more compact than the default string form.
+    pub fn uuid_bytes(id: Uuid) -> QueryParam {
+        QueryParam::List(id.as_bytes().iter().map(|x| QueryParam::from(*x)).collect())
+    }
+
+    /// Returns a new random UUID in the string form, e.g. for ids of created nodes.
+    pub fn new_uuid() -> QueryParam {
+        QueryParam::from(Uuid::new_v4())
+    }
+}
+
+impl Value {
+    /// Converts a string or a list of 16 bytes to UUID, see `QueryParam::from(Uuid)` and
+    /// `QueryParam::uuid_bytes`. Strings can be in any form parsed by `Uuid::parse_str`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rsmgclient::Value;
+    ///
+    /// let value = Value::String(String::from("67e55044-10b1-426f-9247-bb680e5fe0c8"));
+    /// let id = value.try_into_uuid().unwrap();
+    /// assert_eq!(id.to_string(), "67e55044-10b1-426f-9247-bb680e5fe0c8");
+    /// ```
+    pub fn try_into_uuid(&self) -> Result<Uuid, MgError> {
+        match self {
+            Value::String(x) => Uuid::parse_str(x)
+                .map_err(|err| MgError::new(format!("Invalid UUID '{}': {}", x, err))),
+            Value::List(x) => {
+                let bytes: Option<Vec<u8>> = x
+                    .iter()
+                    .map(|x| match x {
+                        Value::Int(x) => u8::try_from(*x).ok(),
+                        _ => None,
+                    })
+                    .collect();
+                bytes
+                    .and_then(|x| Uuid::from_slice(&x).ok())
+                    .ok_or_else(|| MgError::new(format!("Invalid UUID bytes {}", self)))
+            }
+            x => Err(MgError::new(format!(
+                "Can't convert {} value to Uuid",
+                x.type_name()
+            ))),
+        }
+    }
+}
+
+impl TryFrom<Value> for Uuid {
+    type Error = MgError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        value.try_into_uuid()
+    }
+}